@@ -0,0 +1,265 @@
+//! Benchmarks of the analysis on a synthetic monorepo.
+//!
+//! Every module defines a class whose methods access attributes of other modules, so
+//! most of the time is spent collecting, resolving and counting references.
+//! Besides timings, the peak memory of the analysis and the memory of the references of
+//! the whole project, stored as interned records or as a name and file path per
+//! reference (the former representation), are printed.
+//! Run with `cargo bench`; set `SKYLOS_BENCH_MODULES` to change the project size.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use rustpython_ast::Stmt;
+use rustpython_parser::{parse, Mode};
+use skylos_rs::analyzer::Skylos;
+use skylos_rs::interner::Interner;
+use skylos_rs::utils::LineIndex;
+use skylos_rs::visitor::{Reference, SkylosVisitor};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Allocator counting the bytes currently allocated and their peak.
+struct CountingAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let allocated = ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(allocated, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+            let allocated = ALLOCATED.fetch_add(new_size, Ordering::Relaxed) + new_size;
+            PEAK.fetch_max(allocated, Ordering::Relaxed);
+        }
+        new_ptr
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Runs `f` and returns its result, with the bytes it still holds allocated afterwards
+/// and the peak of bytes allocated meanwhile, both above the allocations before it.
+fn measure<T>(f: impl FnOnce() -> T) -> (T, usize, usize) {
+    let before = ALLOCATED.load(Ordering::Relaxed);
+    PEAK.store(before, Ordering::Relaxed);
+    let value = f();
+    let retained = ALLOCATED.load(Ordering::Relaxed).saturating_sub(before);
+    let peak = PEAK.load(Ordering::Relaxed) - before;
+    (value, retained, peak)
+}
+
+/// Formats a number of bytes in MB.
+fn megabytes(bytes: usize) -> String {
+    format!("{:.1} MB", bytes as f64 / 1_000_000.0)
+}
+
+/// Number of modules per package.
+const MODULES_PER_PACKAGE: usize = 25;
+
+/// Generates the source of module `index` of the synthetic project.
+fn module_source(index: usize, modules: usize) -> String {
+    let mut source = String::new();
+    let imports: Vec<usize> = (1..=3).map(|k| (index + k * 7) % modules).collect();
+    for i in &imports {
+        let _ = writeln!(
+            source,
+            "from pkg{}.mod{} import Service{}",
+            i / MODULES_PER_PACKAGE,
+            i,
+            i
+        );
+    }
+    let _ = writeln!(source, "\n\nclass Service{}:", index);
+    let _ = writeln!(source, "    def __init__(self, config):");
+    let _ = writeln!(source, "        self.config = config");
+    for i in &imports {
+        let _ = writeln!(source, "        self.dep{} = Service{}(config)", i, i);
+    }
+    for method in 0..8 {
+        let _ = writeln!(source, "\n    def handle_{}(self, request):", method);
+        for i in &imports {
+            let _ = writeln!(
+                source,
+                "        result = self.dep{}.handle_{}(request.data.payload)",
+                i,
+                (method + 1) % 8
+            );
+            let _ = writeln!(
+                source,
+                "        self.config.logger.info(result.status, request.user.name)"
+            );
+        }
+        let _ = writeln!(source, "        return self.config.settings.timeout");
+    }
+    let _ = writeln!(source, "\n\ndef unused_helper_{}(value):", index);
+    let _ = writeln!(source, "    return value.strip().lower()");
+    source
+}
+
+/// Writes a synthetic project with `modules` modules and returns its root.
+fn write_project(root: &Path, modules: usize) -> PathBuf {
+    for package in 0..modules.div_ceil(MODULES_PER_PACKAGE) {
+        let dir = root.join(format!("pkg{}", package));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("__init__.py"), "").unwrap();
+    }
+    for index in 0..modules {
+        let path = root
+            .join(format!("pkg{}", index / MODULES_PER_PACKAGE))
+            .join(format!("mod{}.py", index));
+        fs::write(path, module_source(index, modules)).unwrap();
+    }
+    root.to_path_buf()
+}
+
+/// Number of modules of the synthetic project.
+fn module_count() -> usize {
+    std::env::var("SKYLOS_BENCH_MODULES")
+        .ok()
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(500)
+}
+
+/// Parses module `index` of the synthetic project.
+fn parse_module(index: usize, modules: usize) -> (String, Vec<Stmt>) {
+    let source = module_source(index, modules);
+    let body = match parse(&source, Mode::Module, "mod.py").unwrap() {
+        rustpython_ast::Mod::Module(module) => module.body,
+        _ => unreachable!(),
+    };
+    (source, body)
+}
+
+/// Visits a module and returns its references with their names.
+fn visit(index: usize, source: &str, body: &[Stmt]) -> (PathBuf, Vec<String>, Vec<Reference>) {
+    let path = PathBuf::from(format!(
+        "project/pkg{}/mod{}.py",
+        index / MODULES_PER_PACKAGE,
+        index
+    ));
+    let line_index = LineIndex::new(source);
+    let mut visitor = SkylosVisitor::new(path.clone(), format!("mod{}", index), &line_index);
+    for stmt in body {
+        visitor.visit_stmt(stmt);
+    }
+    let names = visitor.reference_names().map(str::to_string).collect();
+    (path, names, visitor.references)
+}
+
+/// Stores the references of every module as a name and a file path each, as the
+/// analysis did before names were interned.
+fn string_references(visited: &[(PathBuf, Vec<String>, Vec<Reference>)]) -> Vec<(String, PathBuf)> {
+    visited
+        .iter()
+        .flat_map(|(path, names, _)| names.iter().map(|name| (name.clone(), path.clone())))
+        .collect()
+}
+
+/// Stores the references of every module as records, with names interned per file.
+fn interned_references(
+    visited: &[(PathBuf, Vec<String>, Vec<Reference>)],
+) -> Vec<(Interner, Vec<Reference>)> {
+    visited
+        .iter()
+        .map(|(_, names, references)| {
+            let mut interner = Interner::new();
+            let references = names
+                .iter()
+                .zip(references)
+                .map(|(name, reference)| Reference {
+                    name: interner.intern(name),
+                    ..*reference
+                })
+                .collect();
+            (interner, references)
+        })
+        .collect()
+}
+
+/// Compares the memory and time of both reference representations for the project.
+fn bench_references(c: &mut Criterion) {
+    let modules = module_count();
+    let visited: Vec<_> = (0..modules)
+        .map(|index| {
+            let (source, body) = parse_module(index, modules);
+            visit(index, &source, &body)
+        })
+        .collect();
+
+    let (strings, string_bytes, _) = measure(|| string_references(&visited));
+    let (interned, interned_bytes, _) = measure(|| interned_references(&visited));
+    println!(
+        "references of {} modules: {} as names and paths ({} references), {} interned",
+        modules,
+        megabytes(string_bytes),
+        strings.len(),
+        megabytes(interned_bytes)
+    );
+    drop((strings, interned));
+
+    let mut group = c.benchmark_group("references");
+    group.bench_function("names_and_paths", |b| {
+        b.iter(|| string_references(&visited))
+    });
+    group.bench_function("interned", |b| b.iter(|| interned_references(&visited)));
+    group.finish();
+}
+
+fn bench_analyze(c: &mut Criterion) {
+    let modules = module_count();
+    let dir = tempfile::tempdir().unwrap();
+    let root = write_project(dir.path(), modules);
+
+    let (_, _, peak) = measure(|| Skylos::new(60, false, false, false).analyze(&root));
+    println!(
+        "analysis of {} modules: peak memory {}",
+        modules,
+        megabytes(peak)
+    );
+
+    let mut group = c.benchmark_group("analyze");
+    group.sample_size(10);
+    group.bench_function(format!("{}_modules", modules), |b| {
+        b.iter(|| Skylos::new(60, false, false, false).analyze(&root).unwrap())
+    });
+    group.finish();
+}
+
+fn bench_visit(c: &mut Criterion) {
+    let (source, body) = parse_module(0, 1000);
+    let line_index = LineIndex::new(&source);
+    c.bench_function("visit_module", |b| {
+        b.iter(|| {
+            let mut visitor = SkylosVisitor::new(
+                PathBuf::from("pkg0/mod0.py"),
+                "pkg0.mod0".into(),
+                &line_index,
+            );
+            for stmt in &body {
+                visitor.visit_stmt(stmt);
+            }
+            visitor.references.len()
+        })
+    });
+}
+
+criterion_group!(benches, bench_analyze, bench_references, bench_visit);
+criterion_main!(benches);
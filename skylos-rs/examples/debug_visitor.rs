@@ -61,7 +61,7 @@ class ChildClass(BaseClass):
 
     // Print all found references (variable usage, function calls).
     println!("\n=== REFERENCES ===");
-    for (ref_name, _) in &visitor.references {
+    for ref_name in visitor.reference_names() {
         println!("{}", ref_name);
     }
 }
@@ -1,18 +1,255 @@
+use crate::baseline::BaselineEntry;
+use crate::cycles::{ImportCycle, ImportGraph};
+use crate::dependencies::DependencyFinding;
+use crate::discovery::{common_root, Discovery, SourceFile};
 use crate::framework::FrameworkAwareVisitor;
+use crate::graph::{Graph, REFERENCE_EDGE};
+use crate::hierarchy::ClassHierarchy;
+use crate::interner::{FileId, Interner, Symbol};
+use crate::reachability::ReferenceGraph;
 use crate::rules::danger::{DangerFinding, DangerVisitor};
-use crate::rules::quality::{QualityFinding, QualityVisitor};
+use crate::rules::quality::{QualityFinding, QualityVisitor, DEFAULT_MAX_DEPTH};
 use crate::rules::secrets::{scan_secrets, SecretFinding};
+use crate::suppression::{Rules, Suppressions, UnusedSuppression};
+use crate::symbols::SymbolTable;
 use crate::test_utils::TestAwareVisitor;
-use crate::utils::LineIndex;
-use crate::visitor::{Definition, SkylosVisitor};
+use crate::unreachable::{UnreachableCode, UnreachableVisitor};
+use crate::utils::{qualify, LineIndex};
+use crate::visitor::{
+    Definition, ImportBinding, ImportContext, Redefinition, Reference, SkylosVisitor,
+};
 use anyhow::Result;
 use rayon::prelude::*;
 use rustpython_parser::{parse, Mode};
 use serde::Serialize;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fs;
-use std::path::Path;
-use walkdir::WalkDir;
+use std::path::{Path, PathBuf};
+
+/// Confidence penalty for definitions in a module that accesses names dynamically in a
+/// way that cannot be bounded (matches the Python version's `dynamic_module` penalty).
+const DYNAMIC_MODULE_PENALTY: u8 = 40;
+
+/// Confidence penalty for definitions matching a reflectively accessed name prefix
+/// (e.g. `handle_*` for `getattr(self, f"handle_{x}")`).
+const DYNAMIC_PREFIX_PENALTY: u8 = 60;
+
+/// Files that tools run or load by convention rather than by import.
+const TOOL_FILES: &[&str] = &["setup.py", "conftest.py", "manage.py", "noxfile.py"];
+
+/// Confidence that a module nothing imports is dead, before penalties.
+const DEAD_MODULE_CONFIDENCE: u8 = 100;
+
+/// Confidence that a method or attribute of a class with a base outside the project is
+/// unused. It may override a member the library calls, like `visit_Name` of an
+/// `ast.NodeVisitor` or `run` of a `threading.Thread`.
+const EXTERNAL_OVERRIDE_CONFIDENCE: u8 = 50;
+
+/// Confidence that a parameter of a method overriding a base class method (in the
+/// project or outside it) is unused: the base method dictates the signature.
+const OVERRIDE_PARAMETER_CONFIDENCE: u8 = 50;
+
+/// Base classes outside the project without members a subclass would override.
+const MEMBERLESS_BASES: &[&str] = &[
+    "object",
+    "Exception",
+    "BaseException",
+    "abc.ABC",
+    "typing.Generic",
+    "typing.Protocol",
+];
+
+/// Per-file output of the parallel pass: collected facts and rule findings.
+struct FileResult {
+    /// Path of the file.
+    path: PathBuf,
+    /// Fully qualified module name of the file.
+    module_name: String,
+    /// Number of lines in the file.
+    line_count: usize,
+    /// Whether the file is run directly: a `__main__.py`, a file run by a tool
+    /// (`setup.py`, `conftest.py`, ...) or a module with an `if __name__ == "__main__":` block.
+    is_script: bool,
+    /// Whether the file is a test file.
+    is_test_file: bool,
+    /// Dotted names the file imports (statically or dynamically) or names in strings.
+    imported_modules: Vec<String>,
+    /// Dotted names of the file's import statements, with their line and context.
+    module_imports: Vec<(String, usize, ImportContext)>,
+    definitions: Vec<Definition>,
+    /// References made in the file. Taken once they are resolved.
+    references: Vec<Reference>,
+    /// Names of `references`, interned per file.
+    symbols: Interner,
+    imports: Vec<ImportBinding>,
+    /// Indices into `definitions` of imports made in `if TYPE_CHECKING:` blocks.
+    type_checking_imports: Vec<usize>,
+    star_imports: Vec<ImportBinding>,
+    /// Names bound at module level.
+    module_bindings: Vec<String>,
+    /// Names listed in `__all__`, with the line of each entry.
+    exports: Vec<(String, usize)>,
+    /// Name prefixes accessed reflectively.
+    dynamic_prefixes: Vec<String>,
+    redefinitions: Vec<Redefinition>,
+    /// Indices into `definitions` of implicitly used definitions (reachability roots).
+    roots: Vec<usize>,
+    secrets: Vec<SecretFinding>,
+    danger: Vec<DangerFinding>,
+    quality: Vec<QualityFinding>,
+    unreachable: Vec<UnreachableCode>,
+    /// Pragmas suppressing findings in the file.
+    suppressions: Suppressions,
+    /// Why the file could not be read or parsed, if it could not.
+    error: Option<FileError>,
+}
+
+impl FileResult {
+    /// Whether the module defines `__all__`.
+    fn defines_all(&self) -> bool {
+        self.module_bindings.iter().any(|name| name == "__all__")
+    }
+}
+
+/// Number of references to a name.
+#[derive(Default, Clone, Copy)]
+struct RefCount {
+    /// All references.
+    total: usize,
+    /// References needed at runtime, i.e. not only for typing.
+    runtime: usize,
+}
+
+impl RefCount {
+    /// A single reference.
+    fn once(typing_only: bool) -> Self {
+        Self {
+            total: 1,
+            runtime: usize::from(!typing_only),
+        }
+    }
+
+    /// Adds the references of `other`.
+    fn add(&mut self, other: RefCount) {
+        self.total += other.total;
+        self.runtime += other.runtime;
+    }
+}
+
+/// Project-wide reference counts, indexed by interned name.
+#[derive(Default)]
+struct RefCounts {
+    /// Every referenced name, and the names definitions are matched under.
+    names: Interner,
+    /// Reference counts, indexed by the symbols of `names`.
+    counts: Vec<RefCount>,
+}
+
+impl RefCounts {
+    /// Creates the counts of the given names, interned in name order so that symbols do
+    /// not depend on the order the names were counted in.
+    fn from_counts(counts: HashMap<&str, RefCount>) -> Self {
+        let mut counts: Vec<(&str, RefCount)> = counts.into_iter().collect();
+        counts.sort_unstable_by_key(|&(name, _)| name);
+        let mut ref_counts = RefCounts::default();
+        for (name, count) in counts {
+            ref_counts.add(name, count);
+        }
+        ref_counts
+    }
+
+    /// Adds references to a name and returns its symbol.
+    fn add(&mut self, name: &str, count: RefCount) -> Symbol {
+        let symbol = self.intern(name);
+        self.counts[symbol.index()].add(count);
+        symbol
+    }
+
+    /// Returns the symbol of a name, interning it if it is new.
+    fn intern(&mut self, name: &str) -> Symbol {
+        let symbol = self.names.intern(name);
+        if symbol.index() == self.counts.len() {
+            self.counts.push(RefCount::default());
+        }
+        symbol
+    }
+
+    /// Returns the number of references to a name, or `None` if it is never referenced.
+    fn total(&self, name: &str) -> Option<usize> {
+        Some(self.get(name).total).filter(|&total| total > 0)
+    }
+
+    /// Returns the number of references to a name that are needed at runtime.
+    fn runtime(&self, name: &str) -> usize {
+        self.get(name).runtime
+    }
+
+    fn get(&self, name: &str) -> RefCount {
+        self.names
+            .get(name)
+            .map(|symbol| self.counts[symbol.index()])
+            .unwrap_or_default()
+    }
+}
+
+/// The references of a file, resolved to the names they count for.
+struct ResolvedReferences {
+    /// Resolved names, interned per file.
+    names: Interner,
+    /// Reference counts, indexed by the symbols of `names`.
+    counts: Vec<RefCount>,
+    /// Distinct `(enclosing definition, resolved name)` pairs, sorted.
+    edges: Vec<(Option<u32>, Symbol)>,
+}
+
+/// A name listed in a module's `__all__` that the module does not define.
+/// `from module import *` would fail with an `AttributeError`.
+#[derive(Serialize, Clone, Debug)]
+pub struct ExportFinding {
+    /// The undefined name.
+    pub name: String,
+    /// Description of the issue.
+    pub message: String,
+    /// Unique rule identifier.
+    pub rule_id: String,
+    /// File where the issue was found.
+    pub file: PathBuf,
+    /// Line number of the `__all__` entry.
+    pub line: usize,
+    /// Severity level.
+    pub severity: String,
+}
+
+/// A module that no other module imports, and that is neither run as a script nor a test.
+#[derive(Serialize, Clone, Debug)]
+pub struct DeadModule {
+    /// Fully qualified module name.
+    pub module_name: String,
+    /// Description of the issue.
+    pub message: String,
+    /// Unique rule identifier.
+    pub rule_id: String,
+    /// Path of the module file.
+    pub file: PathBuf,
+    /// Total number of lines in the file, to prioritize deletions.
+    pub line_count: usize,
+    /// Confidence score (0-100) that the module is dead.
+    pub confidence: u8,
+    /// Severity level.
+    pub severity: String,
+}
+
+/// A file that could not be read or parsed. Only its secrets can be reported; its
+/// definitions, references and other findings are missing from the analysis.
+#[derive(Serialize, Clone, Debug)]
+pub struct FileError {
+    /// Path of the file.
+    pub file: PathBuf,
+    /// Line of the syntax error, if the file could be read.
+    pub line: Option<usize>,
+    /// Description of the error.
+    pub message: String,
+}
 
 /// Holds the results of the analysis.
 /// This struct is serialized to JSON if requested.
@@ -26,12 +263,49 @@ pub struct AnalysisResult {
     pub unused_classes: Vec<Definition>,
     /// List of variables that were defined but never used.
     pub unused_variables: Vec<Definition>,
+    /// List of function parameters that are never used in the function body.
+    pub unused_parameters: Vec<Definition>,
+    /// List of class and instance attributes that are assigned but never read.
+    pub unused_attributes: Vec<Definition>,
+    /// List of imports that are only used in type annotations, but are imported at runtime
+    /// (they could be moved into an `if TYPE_CHECKING:` block).
+    pub typing_only_imports: Vec<Definition>,
+    /// List of modules that are never imported, largest first.
+    pub dead_modules: Vec<DeadModule>,
+    /// List of declared third-party dependencies that are never imported.
+    pub unused_dependencies: Vec<DependencyFinding>,
+    /// List of imported third-party packages that are not declared as dependencies.
+    pub undeclared_dependencies: Vec<DependencyFinding>,
+    /// List of import cycles between the project's modules.
+    pub import_cycles: Vec<ImportCycle>,
+    /// List of names exported through `__all__` that are never defined.
+    pub undefined_exports: Vec<ExportFinding>,
+    /// List of statement blocks inside functions that can never run.
+    pub unreachable_code: Vec<UnreachableCode>,
+    /// List of functions, classes and imports replaced by a later definition before
+    /// they were ever used.
+    pub redefinitions: Vec<Redefinition>,
+    /// List of suppression pragmas that did not suppress anything, if
+    /// `report_unused_suppressions` is set.
+    pub unused_suppressions: Vec<UnusedSuppression>,
+    /// Entries of the baseline that no longer match a finding, filled in by
+    /// `Baseline::apply`.
+    pub stale_baseline: Vec<BaselineEntry>,
     /// List of discovered secrets (e.g., API keys).
     pub secrets: Vec<SecretFinding>,
     /// List of security vulnerabilities found.
     pub danger: Vec<DangerFinding>,
     /// List of code quality issues found.
     pub quality: Vec<QualityFinding>,
+    /// Files that could not be read or parsed.
+    pub file_errors: Vec<FileError>,
+    /// The import graph between the project's modules, if `collect_graphs` is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub module_graph: Option<Graph>,
+    /// The reference graph between functions, methods and classes, if `collect_graphs`
+    /// is set.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol_graph: Option<Graph>,
     /// Summary statistics of the analysis.
     pub analysis_summary: AnalysisSummary,
 }
@@ -60,6 +334,28 @@ pub struct Skylos {
     pub enable_danger: bool,
     /// Whether to scan for quality issues.
     pub enable_quality: bool,
+    /// Qualified names (or name prefixes, e.g. `pkg.api`) of the public API.
+    /// These definitions are treated as used even if nothing in the project calls them.
+    pub public_api: Vec<String>,
+    /// Names declared as used by whitelist files (simple or fully qualified).
+    /// They are counted as references from module-level code.
+    pub whitelist: Vec<String>,
+    /// Whether to export the module and symbol graphs in the result.
+    pub collect_graphs: bool,
+    /// Glob patterns (gitignore syntax) of files and directories to skip, relative to
+    /// the analyzed directory.
+    pub exclude: Vec<String>,
+    /// Glob patterns of the files to analyze. If not empty, other files are skipped.
+    pub include: Vec<String>,
+    /// Folders to analyze even though they are excluded by default (e.g. `build`).
+    pub include_folders: Vec<String>,
+    /// Severity of individual rules, overriding their default (rule ID -> severity).
+    pub severities: HashMap<String, String>,
+    /// Nesting depth above which code is reported as deeply nested (`SKY-Q001`).
+    pub max_nesting_depth: usize,
+    /// Whether to report the suppression pragmas that did not suppress any finding.
+    /// Pragmas that may apply to a rule which did not run are not reported.
+    pub report_unused_suppressions: bool,
 }
 
 impl Skylos {
@@ -75,6 +371,15 @@ impl Skylos {
             enable_secrets,
             enable_danger,
             enable_quality,
+            public_api: Vec::new(),
+            whitelist: Vec::new(),
+            collect_graphs: false,
+            exclude: Vec::new(),
+            include: Vec::new(),
+            include_folders: Vec::new(),
+            severities: HashMap::new(),
+            max_nesting_depth: DEFAULT_MAX_DEPTH,
+            report_unused_suppressions: false,
         }
     }
 
@@ -86,46 +391,78 @@ impl Skylos {
     /// 3. Parses each file into an AST.
     /// 4. Runs visitors to collect definitions, references, and findings.
     /// 5. Aggregates results from all files.
-    /// 6. Calculates cross-file usage to identify unused code.
-    /// 7. Returns the final `AnalysisResult`.
+    /// 6. Resolves imports through a project-wide symbol table and calculates
+    ///    cross-file usage to identify unused code.
+    /// 7. Relates methods and attributes to the base class members they override
+    ///    through a project-wide class hierarchy.
+    /// 8. Walks the reference graph from the entry roots to find code that is only
+    ///    referenced from other dead code.
+    /// 9. Returns the final `AnalysisResult`.
     pub fn analyze(&self, path: &Path) -> Result<AnalysisResult> {
-        // Find all Python files in the given path.
-        // We use WalkDir to recursively traverse directories.
-        let files: Vec<_> = WalkDir::new(path)
-            .into_iter()
-            .filter_map(|e| e.ok())
-            // Keep only files with the .py extension
-            .filter(|e| e.path().extension().map_or(false, |ext| ext == "py"))
+        self.analyze_paths(&[path.to_path_buf()])
+    }
+
+    /// Runs the analysis on several files or directories as a single project.
+    ///
+    /// Module names are computed relative to the path each file was found under, and
+    /// project files (`pyproject.toml`, requirements) are read from the deepest directory
+    /// containing all of the paths.
+    pub fn analyze_paths(&self, paths: &[PathBuf]) -> Result<AnalysisResult> {
+        // Find all Python files in the given paths, skipping ignored and excluded ones.
+        let discovery = Discovery {
+            exclude: self.exclude.clone(),
+            include: self.include.clone(),
+            include_folders: self.include_folders.clone(),
+        };
+        let files = discovery.find_files(paths)?;
+
+        // When a single file is analyzed, its directory acts as the root.
+        let roots: Vec<&Path> = paths
+            .iter()
+            .map(|path| {
+                if path.is_file() {
+                    path.parent().unwrap_or(path)
+                } else {
+                    path
+                }
+            })
             .collect();
+        let root = common_root(&roots);
+        let root = root.as_path();
 
         let total_files = files.len();
 
         // Process files in parallel to speed up analysis.
         // rayon::par_iter() automatically distributes work across threads.
-        let results: Vec<(
-            Vec<Definition>,
-            Vec<(String, std::path::PathBuf)>,
-            Vec<SecretFinding>,
-            Vec<DangerFinding>,
-            Vec<QualityFinding>,
-        )> = files
+        let mut results: Vec<FileResult> = files
             .par_iter()
-            .map(|entry| {
-                let path = entry.path();
-                // Read file content. If it fails, treat as empty.
-                let source = fs::read_to_string(path).unwrap_or_default();
+            .enumerate()
+            .map(|(index, file)| {
+                let path = file.path.as_path();
+                // Read file content. If it fails, treat as empty and record the error.
+                let mut error = None;
+                let source = fs::read_to_string(path).unwrap_or_else(|e| {
+                    error = Some(FileError {
+                        file: path.to_path_buf(),
+                        line: None,
+                        message: format!("failed to read file: {}", e),
+                    });
+                    String::new()
+                });
                 // Create a line index for mapping byte offsets to line numbers.
                 let line_index = LineIndex::new(&source);
-                // Check for "no skylos" comments to ignore specific lines.
-                let ignored_lines = crate::utils::get_ignored_lines(&source);
+                // Read the `# skylos: ignore` pragmas suppressing findings.
+                let suppressions = Suppressions::parse(&source, &line_index);
 
-                // Determine the module name from the file name.
-                let module_name = path.file_stem().unwrap().to_string_lossy().to_string();
+                // Determine the fully qualified module name (e.g. "pkg.a.utils"),
+                // relative to the analyzed path the file was found under.
+                let module_name = crate::utils::module_name_from_path(&file.root, path);
 
                 // Initialize visitors.
                 // SkylosVisitor collects definitions and references.
                 let mut visitor =
                     SkylosVisitor::new(path.to_path_buf(), module_name.clone(), &line_index);
+                visitor.file_id = FileId::new(index);
                 // FrameworkAwareVisitor checks for framework-specific patterns (e.g. Django, Flask).
                 let mut framework_visitor = FrameworkAwareVisitor::new(&line_index);
                 // TestAwareVisitor checks if the file is a test file or contains tests.
@@ -134,171 +471,1138 @@ impl Skylos {
                 let mut secrets = Vec::new();
                 let mut danger = Vec::new();
                 let mut quality = Vec::new();
+                let mut unreachable = Vec::new();
+                let mut is_script = path.ends_with("__main__.py")
+                    || TOOL_FILES.iter().any(|name| path.ends_with(name));
 
                 // Scan for secrets using regex matching if enabled.
                 if self.enable_secrets {
-                    secrets = scan_secrets(&source, &path.to_path_buf());
+                    secrets = scan_secrets(&source, path);
                 }
 
                 // Parse the Python source code into an AST.
-                if let Ok(ast) = parse(&source, Mode::Module, path.to_str().unwrap()) {
-                    if let rustpython_ast::Mod::Module(module) = &ast {
-                        // Detect entry point calls (if __name__ == "__main__")
-                        // These are treated as usage roots to prevent false positives.
-                        let entry_point_calls =
-                            crate::entry_point::detect_entry_point_calls(&module.body);
-
-                        // Run main visitors over the AST.
-                        for stmt in &module.body {
-                            framework_visitor.visit_stmt(stmt);
-                            test_visitor.visit_stmt(stmt);
-                            visitor.visit_stmt(stmt);
-                        }
+                let parsed = parse(&source, Mode::Module, &path.to_string_lossy());
+                if let (Err(e), None) = (&parsed, &error) {
+                    error = Some(FileError {
+                        file: path.to_path_buf(),
+                        line: Some(line_index.line_index(e.offset)),
+                        message: format!("syntax error: {}", e.error),
+                    });
+                }
+                if let Ok(rustpython_ast::Mod::Module(module)) = parsed {
+                    // Detect entry point calls (if __name__ == "__main__")
+                    // These are treated as usage roots to prevent false positives.
+                    let entry_point_calls =
+                        crate::entry_point::detect_entry_point_calls(&module.body);
+                    is_script |= crate::entry_point::has_main_guard(&module.body);
+
+                    // Run main visitors over the AST.
+                    for stmt in &module.body {
+                        framework_visitor.visit_stmt(stmt);
+                        test_visitor.visit_stmt(stmt);
+                        visitor.visit_stmt(stmt);
+                    }
 
-                        // Add entry point calls as references to mark them as used.
-                        for call_name in &entry_point_calls {
-                            // Try both simple name and qualified name
-                            visitor.add_ref(call_name.clone());
-                            if !module_name.is_empty() {
-                                let qualified = format!("{}.{}", module_name, call_name);
-                                visitor.add_ref(qualified);
-                            }
+                    // Add entry point calls as references to mark them as used.
+                    for call_name in &entry_point_calls {
+                        // Try both simple name and qualified name
+                        visitor.add_ref(call_name);
+                        if !module_name.is_empty() {
+                            let qualified = format!("{}.{}", module_name, call_name);
+                            visitor.add_ref(&qualified);
                         }
+                    }
 
-                        // Run danger visitor if enabled.
-                        if self.enable_danger {
-                            let mut danger_visitor =
-                                DangerVisitor::new(path.to_path_buf(), &line_index);
-                            for stmt in &module.body {
-                                danger_visitor.visit_stmt(stmt);
-                            }
-                            danger = danger_visitor.findings;
+                    // Run danger visitor if enabled.
+                    if self.enable_danger {
+                        let mut danger_visitor =
+                            DangerVisitor::new(path.to_path_buf(), &line_index);
+                        for stmt in &module.body {
+                            danger_visitor.visit_stmt(stmt);
                         }
+                        danger = danger_visitor.findings;
+                    }
 
-                        // Run quality visitor if enabled.
-                        if self.enable_quality {
-                            let mut quality_visitor =
-                                QualityVisitor::new(path.to_path_buf(), &line_index);
-                            for stmt in &module.body {
-                                quality_visitor.visit_stmt(stmt);
-                            }
-                            quality = quality_visitor.findings;
+                    // Run quality visitor if enabled.
+                    if self.enable_quality {
+                        let mut quality_visitor =
+                            QualityVisitor::new(path.to_path_buf(), &line_index);
+                        quality_visitor.max_depth = self.max_nesting_depth;
+                        for stmt in &module.body {
+                            quality_visitor.visit_stmt(stmt);
                         }
+                        quality = quality_visitor.findings;
+                    }
+
+                    // Find dead statements inside function bodies.
+                    let mut unreachable_visitor =
+                        UnreachableVisitor::new(path.to_path_buf(), &line_index);
+                    for stmt in &module.body {
+                        unreachable_visitor.visit_stmt(stmt);
                     }
+                    unreachable = unreachable_visitor.findings;
                 }
 
-                // Apply penalties/adjustments based on framework/test status and pragmas.
+                // Apply penalties/adjustments based on framework/test status.
                 // This modifies the confidence score of definitions.
                 for def in &mut visitor.definitions {
-                    apply_penalties(def, &framework_visitor, &test_visitor, &ignored_lines);
+                    apply_penalties(def, &framework_visitor, &test_visitor);
+                    // Unbounded dynamic access (`getattr(obj, name)`, `globals()[name]`)
+                    // may reach any module-level name.
+                    if visitor.has_dynamic_access && !def.is_local {
+                        def.confidence = def.confidence.saturating_sub(DYNAMIC_MODULE_PENALTY);
+                    }
+                }
+
+                // Module-level definitions listed in `__all__` are exported.
+                for def in &mut visitor.definitions {
+                    if visitor.exports.contains(&def.simple_name)
+                        && def.full_name == qualify(&module_name, &def.simple_name)
+                    {
+                        def.is_exported = true;
+                    }
                 }
 
+                // Collect the reachability roots of this file: implicitly used names
+                // and exports, tests and framework handlers.
+                let roots = visitor
+                    .definitions
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, def)| {
+                        def.is_exported
+                            || test_visitor.is_test_file
+                            || test_visitor.test_decorated_lines.contains(&def.line)
+                            || framework_visitor
+                                .framework_decorated_lines
+                                .contains(&def.line)
+                    })
+                    .map(|(i, _)| i)
+                    .collect();
+
+                let module_bindings = visitor.module_bindings().map(str::to_string).collect();
+                let module_imports = visitor
+                    .imported_modules
+                    .iter()
+                    .cloned()
+                    .zip(visitor.import_lines)
+                    .zip(visitor.import_contexts)
+                    .map(|((name, line), context)| (name, line, context))
+                    .collect();
+                let mut imported_modules = visitor.imported_modules;
+                imported_modules.extend(visitor.dynamic_imports);
+                imported_modules.extend(visitor.dotted_strings);
+                let exports = visitor
+                    .exports
+                    .into_iter()
+                    .zip(visitor.export_lines)
+                    .collect();
+
                 // Return the results for this file.
-                (
-                    visitor.definitions,
-                    visitor.references,
+                FileResult {
+                    path: path.to_path_buf(),
+                    module_name,
+                    line_count: source.lines().count(),
+                    is_script,
+                    is_test_file: test_visitor.is_test_file,
+                    imported_modules,
+                    module_imports,
+                    definitions: visitor.definitions,
+                    references: visitor.references,
+                    symbols: visitor.symbols,
+                    imports: visitor.imports,
+                    type_checking_imports: visitor.type_checking_imports,
+                    star_imports: visitor.star_imports,
+                    module_bindings,
+                    exports,
+                    dynamic_prefixes: visitor.dynamic_prefixes,
+                    redefinitions: visitor.redefinitions,
+                    roots,
                     secrets,
                     danger,
                     quality,
-                )
+                    unreachable,
+                    suppressions,
+                    error,
+                }
             })
             .collect();
 
-        // Aggregate results from all files.
-        let mut all_defs = Vec::new();
-        let mut all_refs = Vec::new();
+        // Build the project-wide symbol table from the import bindings of every module.
+        let mut symbols = SymbolTable::new();
+        for file in &results {
+            symbols.add_module(&file.module_name, &file.imports);
+        }
+
+        // Expand every star import into one import binding per name the target module
+        // provides, and check that every name listed in `__all__` is defined.
+        // Star imports from modules outside the project cannot be expanded.
+        let provided = star_exports(&results);
+        let module_names: HashSet<&str> = results.iter().map(|f| f.module_name.as_str()).collect();
+        // Per file, per star import: the qualified names of the bindings it created.
+        let mut star_aliases: Vec<Vec<Option<Vec<String>>>> = Vec::with_capacity(results.len());
+        let mut undefined_exports = Vec::new();
+        for file in &results {
+            let bound: HashSet<&str> = file.module_bindings.iter().map(String::as_str).collect();
+            let mut bindings = Vec::new();
+            let mut aliases = Vec::with_capacity(file.star_imports.len());
+            for star in &file.star_imports {
+                let Some(names) = provided.get(star.target.as_str()) else {
+                    aliases.push(None);
+                    continue;
+                };
+                // Names the module binds itself take precedence.
+                let names: Vec<&str> = names
+                    .iter()
+                    .copied()
+                    .filter(|name| !bound.contains(name))
+                    .collect();
+                aliases.push(Some(
+                    names
+                        .iter()
+                        .map(|name| qualify(&file.module_name, name))
+                        .collect(),
+                ));
+                bindings.extend(names.into_iter().map(|name| ImportBinding {
+                    local_name: name.to_string(),
+                    target: qualify(&star.target, name),
+                    line: star.line,
+                }));
+            }
+
+            // A module-level `__getattr__` or an unresolved star import may provide
+            // any name; `__all__` may also list submodules of a package.
+            let is_open = bound.contains("__getattr__") || aliases.iter().any(Option::is_none);
+            if !is_open {
+                for (name, line) in &file.exports {
+                    let is_defined = bound.contains(name.as_str())
+                        || bindings.iter().any(|b| b.local_name == *name)
+                        || module_names.contains(qualify(&file.module_name, name).as_str());
+                    if !is_defined {
+                        undefined_exports.push(ExportFinding {
+                            name: name.clone(),
+                            message: format!("Undefined name '{}' in __all__", name),
+                            rule_id: "SKY-E001".to_string(),
+                            file: file.path.clone(),
+                            line: *line,
+                            severity: "HIGH".to_string(),
+                        });
+                    }
+                }
+            }
+
+            symbols.add_module(&file.module_name, &bindings);
+            star_aliases.push(aliases);
+        }
+
+        // Resolve reflectively accessed name prefixes. Prefixes qualified with an object
+        // (`mod.handle_`) are resolved through the imports of their module; bare prefixes
+        // match any definition by simple name.
+        let mut qualified_prefixes = Vec::new();
+        let mut bare_prefixes = Vec::new();
+        for file in &results {
+            for prefix in &file.dynamic_prefixes {
+                if prefix.contains('.') {
+                    qualified_prefixes.extend(symbols.resolve_reference(&file.module_name, prefix));
+                    qualified_prefixes.push(prefix.clone());
+                } else {
+                    bare_prefixes.push(prefix.clone());
+                }
+            }
+        }
+
+        // Find modules that nothing imports.
+        let dead_modules = self.find_dead_modules(&results, root);
+
+        // Compare the dependencies declared in the project manifests to the imports.
+        let (mut unused_dependencies, mut undeclared_dependencies) =
+            check_dependencies(&results, root);
+        unused_dependencies.retain(|d| !self.whitelist.contains(&d.name));
+        undeclared_dependencies.retain(|d| !self.whitelist.contains(&d.name));
+
+        // Find import cycles between the project's modules.
+        let import_graph = build_import_graph(&results);
+        let import_cycles = import_graph.find_cycles();
+
+        // Build the class hierarchy, resolving base classes through the imports of the
+        // module they are used in.
+        let mut hierarchy = ClassHierarchy::new();
+        for file in &results {
+            for def in &file.definitions {
+                match def.def_type.as_str() {
+                    "class" => {
+                        let bases = def
+                            .base_classes
+                            .iter()
+                            .map(|base| resolve_class_name(&symbols, file, base))
+                            .collect();
+                        hierarchy.add_class(&def.full_name, bases);
+                    }
+                    "method" | "attribute" => {
+                        if let Some((class, member)) = def.full_name.rsplit_once('.') {
+                            hierarchy.add_member(class, member);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        // Resolve the references of every file in parallel (see `resolve_references`).
+        let resolved: Vec<ResolvedReferences> = results
+            .par_iter_mut()
+            .map(|file| resolve_references(file, &symbols, &hierarchy))
+            .collect();
+
+        // Count references globally: the per-file counts are merged in parallel, then
+        // every file's names are mapped to their project-wide symbols.
+        let merged = resolved
+            .par_iter()
+            .fold(
+                HashMap::new,
+                |mut counts: HashMap<&str, RefCount>, references| {
+                    for (name, text) in references.names.iter() {
+                        counts
+                            .entry(text)
+                            .or_default()
+                            .add(references.counts[name.index()]);
+                    }
+                    counts
+                },
+            )
+            .reduce(HashMap::new, |a, b| {
+                let (mut into, from) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+                for (name, count) in from {
+                    into.entry(name).or_default().add(count);
+                }
+                into
+            });
+        let mut ref_counts = RefCounts::from_counts(merged);
+        let global_symbols: Vec<Vec<Option<Symbol>>> = resolved
+            .par_iter()
+            .map(|references| {
+                references
+                    .names
+                    .iter()
+                    .map(|(_, text)| ref_counts.names.get(text))
+                    .collect()
+            })
+            .collect();
+
+        // Aggregate results from all files. Every reference is recorded in the reference
+        // graph, attributed to the definition it occurs in.
+        let def_count = results.iter().map(|f| f.definitions.len()).sum();
+        let mut graph = ReferenceGraph::new(def_count);
+        let mut all_defs = Vec::with_capacity(def_count);
         let mut all_secrets = Vec::new();
         let mut all_danger = Vec::new();
         let mut all_quality = Vec::new();
+        let mut unreachable_code = Vec::new();
+        let mut redefinitions = Vec::new();
+        let mut file_errors = Vec::new();
+        // The pragma suppressing each definition, and the pragmas of every file that has any.
+        let mut suppressed = Vec::with_capacity(def_count);
+        let mut suppressions = HashMap::new();
+        // Imports made in `if TYPE_CHECKING:` blocks.
+        let mut type_checking_imports = HashSet::new();
+
+        // Star import definitions, with the qualified names of the bindings they created.
+        let mut star_defs: HashMap<usize, Option<Vec<String>>> = HashMap::new();
+
+        // Whitelisted names are used as if referenced from module-level code.
+        let whitelist: HashSet<&str> = self.whitelist.iter().map(String::as_str).collect();
+        for name in &self.whitelist {
+            let name = ref_counts.add(name, RefCount::once(false));
+            graph.add_reference(None, name);
+        }
 
-        for (defs, refs, secrets, danger, quality) in results {
-            all_defs.extend(defs);
-            all_refs.extend(refs);
-            all_secrets.extend(secrets);
-            all_danger.extend(danger);
-            all_quality.extend(quality);
+        let per_file = results
+            .into_iter()
+            .zip(resolved)
+            .zip(global_symbols)
+            .zip(star_aliases);
+        for (((file, references), global), mut aliases) in per_file {
+            // Offset of this file's definitions in `all_defs`.
+            let offset = all_defs.len();
+            for (i, def) in file.definitions.iter().enumerate() {
+                if def.def_type == "import" && def.simple_name == "*" {
+                    if let Some(star) = file.star_imports.iter().position(|s| s.line == def.line) {
+                        star_defs.insert(i + offset, aliases[star].take());
+                    }
+                }
+            }
+            type_checking_imports.extend(file.type_checking_imports.iter().map(|i| i + offset));
+            for (scope, name) in references.edges {
+                if let Some(name) = global[name.index()] {
+                    graph.add_reference(scope.map(|i| i as usize + offset), name);
+                }
+            }
+            for root in file.roots {
+                graph.add_root(root + offset);
+            }
+            suppressed.extend(
+                file.definitions
+                    .iter()
+                    .map(|def| file.suppressions.find(def.line, None)),
+            );
+            if !file.suppressions.is_empty() {
+                suppressions.insert(file.path, file.suppressions);
+            }
+            all_defs.extend(file.definitions);
+            all_secrets.extend(file.secrets);
+            all_danger.extend(file.danger);
+            all_quality.extend(file.quality);
+            unreachable_code.extend(file.unreachable);
+            redefinitions.extend(file.redefinitions);
+            file_errors.extend(file.error);
         }
 
-        // Count references globally.
-        // We map the full name of a definition to the number of times it is referenced.
-        let mut ref_counts: HashMap<String, usize> = HashMap::new();
-        for (name, _) in &all_refs {
-            *ref_counts.entry(name.clone()).or_insert(0) += 1;
+        // A method overriding a base class method runs whenever the base method is called
+        // (e.g. `self.step()` in a template method, or `shape.area()` on an ABC), so the
+        // base method keeps its overrides alive and its references count for them.
+        // The same goes for attributes overriding a base class attribute that the base
+        // class reads (e.g. `template_name = ...` in a subclass).
+        let members: HashMap<&str, usize> = all_defs
+            .iter()
+            .enumerate()
+            .filter(|(_, def)| is_member(def))
+            .map(|(i, def)| (def.full_name.as_str(), i))
+            .collect();
+        // Base class members overridden by each member, and members of classes with a
+        // base outside the project, which may override a member we do not know.
+        let mut overridden: Vec<(usize, Vec<usize>)> = Vec::new();
+        let mut external_members = Vec::new();
+        for (i, def) in all_defs.iter().enumerate() {
+            if !is_member(def) {
+                continue;
+            }
+            let Some((class, member)) = def.full_name.rsplit_once('.') else {
+                continue;
+            };
+            let bases: Vec<usize> = hierarchy
+                .overridden(class, member)
+                .iter()
+                .filter_map(|base_member| members.get(base_member.as_str()).copied())
+                .collect();
+            for &base in &bases {
+                graph.add_edge(base, i);
+            }
+            if !bases.is_empty() {
+                overridden.push((i, bases));
+            }
+            let has_external_base = hierarchy
+                .external_ancestors(class)
+                .iter()
+                .any(|base| !MEMBERLESS_BASES.contains(base));
+            if has_external_base {
+                external_members.push(i);
+            }
+        }
+        // Overrides must accept the parameters of the method they override, used or not.
+        let overriding_methods: HashSet<&str> = overridden
+            .iter()
+            .map(|(i, _)| *i)
+            .chain(external_members.iter().copied())
+            .filter(|&i| all_defs[i].def_type == "method")
+            .map(|i| all_defs[i].full_name.as_str())
+            .collect();
+        let override_parameters: Vec<usize> = all_defs
+            .iter()
+            .enumerate()
+            .filter(|(_, def)| {
+                def.def_type == "parameter"
+                    && def
+                        .full_name
+                        .rsplit_once('.')
+                        .is_some_and(|(method, _)| overriding_methods.contains(method))
+            })
+            .map(|(i, _)| i)
+            .collect();
+        for i in override_parameters {
+            let def = &mut all_defs[i];
+            def.confidence = def.confidence.min(OVERRIDE_PARAMETER_CONFIDENCE);
+        }
+        for i in external_members {
+            let def = &mut all_defs[i];
+            def.confidence = def.confidence.min(EXTERNAL_OVERRIDE_CONFIDENCE);
         }
 
+        // Update the reference count of every definition and pick the name under which
+        // it is matched by references.
+        let mut keys = Vec::with_capacity(all_defs.len());
+        for (i, def) in all_defs.iter_mut().enumerate() {
+            // Definitions matching a reflectively accessed prefix may be used dynamically.
+            let is_dynamic = !def.is_local
+                && (qualified_prefixes
+                    .iter()
+                    .any(|p| def.full_name.starts_with(p.as_str()))
+                    || bare_prefixes
+                        .iter()
+                        .any(|p| def.simple_name.starts_with(p.as_str())));
+            if is_dynamic {
+                def.confidence = def.confidence.saturating_sub(DYNAMIC_PREFIX_PENALTY);
+            }
+
+            // A star import is used if any of the names it provides is. It has no outgoing
+            // references, so it is treated as a root like local names.
+            // Local names and star imports are not matched through `ref_counts`, so they
+            // are only whitelisted by their full name.
+            let is_whitelisted = usize::from(whitelist.contains(def.full_name.as_str()));
+
+            if let Some(aliases) = star_defs.get(&i) {
+                match aliases {
+                    Some(aliases) => {
+                        def.references = aliases
+                            .iter()
+                            .filter_map(|alias| ref_counts.total(alias))
+                            .sum::<usize>()
+                            + is_whitelisted;
+                    }
+                    // We cannot tell which names a module outside the project provides.
+                    None => def.confidence = def.confidence.min(30),
+                }
+                keys.push(ref_counts.intern(&def.full_name));
+                graph.add_root(i);
+                continue;
+            }
+
+            // Local names were already resolved within their scope by the visitor.
+            // They have no outgoing references, so they are simply treated as roots.
+            if def.is_local {
+                def.references += is_whitelisted;
+                keys.push(ref_counts.intern(&def.full_name));
+                graph.add_root(i);
+                continue;
+            }
+
+            if let Some(count) = ref_counts.total(&def.full_name) {
+                def.references = count;
+                keys.push(ref_counts.intern(&def.full_name));
+            }
+            // Class members may be used through an object of unknown type (`obj.attr`),
+            // so they fall back to their simple name. Other definitions are only used
+            // through names that resolve to them, unless their simple name is whitelisted.
+            else if is_member(def) || whitelist.contains(def.simple_name.as_str()) {
+                if let Some(count) = ref_counts.total(&def.simple_name) {
+                    def.references = count;
+                }
+                keys.push(ref_counts.intern(&def.simple_name));
+            } else {
+                keys.push(ref_counts.intern(&def.full_name));
+            }
+
+            // Definitions we are not confident about must not make their callees dead,
+            // and the configured public API is always used.
+            let is_public_api = self.public_api.iter().any(|api| {
+                def.full_name == *api || def.full_name.starts_with(&format!("{}.", api))
+            });
+            if def.confidence < self.confidence_threshold || is_public_api {
+                graph.add_root(i);
+            }
+        }
+
+        // References to an overridden member count for its overrides, under the name
+        // the overridden member is matched by.
+        let own_references: Vec<usize> = all_defs.iter().map(|def| def.references).collect();
+        for (i, bases) in overridden {
+            all_defs[i].references += bases
+                .iter()
+                .map(|&base| own_references[base])
+                .sum::<usize>();
+        }
+
+        // Suppressed definitions are used as well. To tell whether their pragmas are
+        // needed, find what is reachable without them first.
+        let unsuppressed_live = (self.report_unused_suppressions
+            && suppressed.iter().any(Option::is_some))
+        .then(|| graph.reachable(&keys));
+        for (i, pragma) in suppressed.iter().enumerate() {
+            if pragma.is_some() {
+                graph.add_root(i);
+            }
+        }
+
+        // Find everything reachable from the roots.
+        let live = graph.reachable(&keys);
+
+        // Export the graphs, marking the modules and definitions that are reported dead.
+        let (module_graph, symbol_graph) = if self.collect_graphs {
+            let mut module_graph = import_graph.to_graph();
+            for node in &mut module_graph.nodes {
+                node.dead = dead_modules.iter().any(|m| m.module_name == node.id);
+            }
+            let symbol_graph =
+                self.symbol_graph(&all_defs, &files, &live, &suppressed, &graph.edges(&keys));
+            (Some(module_graph), Some(symbol_graph))
+        } else {
+            (None, None)
+        };
+
         // Categorize unused definitions.
         let mut unused_functions = Vec::new();
         let mut unused_classes = Vec::new();
         let mut unused_imports = Vec::new();
         let mut unused_variables = Vec::new();
+        let mut unused_parameters = Vec::new();
+        let mut unused_attributes = Vec::new();
+        let mut typing_only_imports = Vec::new();
 
-        for mut def in all_defs {
-            // Update the reference count for the definition.
-            if let Some(count) = ref_counts.get(&def.full_name) {
-                def.references = *count;
-            }
-            // Fallback: check simple name count if full name count is missing (for local vars/imports)
-            else if let Some(count) = ref_counts.get(&def.simple_name) {
-                def.references = *count;
-            }
-
+        for (i, (mut def, is_live)) in all_defs.into_iter().zip(live).enumerate() {
             // Filter out low confidence items based on the threshold.
             if def.confidence < self.confidence_threshold {
                 continue;
             }
+            // A suppressed definition is kept alive by its pragma; its liveness without
+            // the pragma decides whether the pragma is needed.
+            let is_live = match (&unsuppressed_live, suppressed[i]) {
+                (Some(unsuppressed_live), Some(_)) => unsuppressed_live[i],
+                _ => is_live,
+            };
 
-            // If reference count is 0, it is unused.
-            if def.references == 0 {
+            // If reference count is 0, it is unused. If it is only referenced from code
+            // that is itself unreachable, it is dead as well.
+            def.dead_reason = if def.references == 0 {
+                Some("unreferenced".to_string())
+            } else if !is_live {
+                Some("referenced_from_dead_code".to_string())
+            } else {
+                None
+            };
+
+            // Used, but only by annotations that are never evaluated.
+            let is_typing_only = def.dead_reason.is_none()
+                && def.def_type == "import"
+                && def.simple_name != "*"
+                && !type_checking_imports.contains(&i)
+                && ref_counts.runtime(&def.full_name) == 0;
+
+            if let Some(pragma) = suppressed[i] {
+                if def.dead_reason.is_some() || is_typing_only {
+                    if let Some(file) = suppressions.get_mut(&files[def.file_id.index()].path) {
+                        file.mark_used(pragma);
+                    }
+                }
+                continue;
+            }
+
+            if def.dead_reason.is_some() || is_typing_only {
+                def.file = files[def.file_id.index()].path.clone();
+            }
+            if def.dead_reason.is_some() {
                 match def.def_type.as_str() {
                     "function" | "method" => unused_functions.push(def),
                     "class" => unused_classes.push(def),
                     "import" => unused_imports.push(def),
                     "variable" => unused_variables.push(def),
+                    "parameter" => unused_parameters.push(def),
+                    "attribute" => unused_attributes.push(def),
                     _ => {}
                 }
+            } else if is_typing_only {
+                typing_only_imports.push(def);
             }
         }
 
         // Construct and return the final result.
-        Ok(AnalysisResult {
+        let mut result = AnalysisResult {
             unused_functions,
             unused_imports,
             unused_classes,
             unused_variables,
+            unused_parameters,
+            unused_attributes,
+            typing_only_imports,
+            dead_modules,
+            unused_dependencies,
+            undeclared_dependencies,
+            import_cycles,
+            undefined_exports,
+            unreachable_code,
+            redefinitions,
+            unused_suppressions: Vec::new(),
+            stale_baseline: Vec::new(),
             secrets: all_secrets.clone(),
             danger: all_danger.clone(),
             quality: all_quality.clone(),
+            file_errors,
+            module_graph,
+            symbol_graph,
             analysis_summary: AnalysisSummary {
                 total_files,
                 secrets_count: all_secrets.len(),
                 danger_count: all_danger.len(),
                 quality_count: all_quality.len(),
             },
+        };
+        apply_suppressions(&mut result, &mut suppressions);
+        if self.report_unused_suppressions {
+            result.unused_suppressions = self.unused_suppressions(&suppressions);
+        }
+        self.apply_severities(&mut result);
+        Ok(result)
+    }
+
+    /// Gives the findings of every rule with a configured severity that severity.
+    fn apply_severities(&self, result: &mut AnalysisResult) {
+        let apply = |rule_id: &str, severity: &mut String| {
+            if let Some(configured) = self.severities.get(rule_id) {
+                severity.clone_from(configured);
+            }
+        };
+        for f in &mut result.secrets {
+            apply(&f.rule_id, &mut f.severity);
+        }
+        for f in &mut result.danger {
+            apply(&f.rule_id, &mut f.severity);
+        }
+        for f in &mut result.quality {
+            apply(&f.rule_id, &mut f.severity);
+        }
+        for f in &mut result.undefined_exports {
+            apply(&f.rule_id, &mut f.severity);
+        }
+        for f in &mut result.unreachable_code {
+            apply(&f.rule_id, &mut f.severity);
+        }
+        for f in &mut result.redefinitions {
+            apply(&f.rule_id, &mut f.severity);
+        }
+        for f in &mut result.dead_modules {
+            apply(&f.rule_id, &mut f.severity);
+        }
+        for f in result
+            .unused_dependencies
+            .iter_mut()
+            .chain(&mut result.undeclared_dependencies)
+        {
+            apply(&f.rule_id, &mut f.severity);
+        }
+        for f in &mut result.import_cycles {
+            apply(&f.rule_id, &mut f.severity);
+        }
+        for f in &mut result.unused_suppressions {
+            apply(&f.rule_id, &mut f.severity);
+        }
+    }
+
+    /// Lists the pragmas that did not suppress any finding, by file and line.
+    fn unused_suppressions(
+        &self,
+        suppressions: &HashMap<PathBuf, Suppressions>,
+    ) -> Vec<UnusedSuppression> {
+        let mut unused: Vec<UnusedSuppression> = suppressions
+            .iter()
+            .flat_map(|(file, suppressions)| {
+                suppressions.unused().map(move |pragma| (file, pragma))
+            })
+            // A pragma may be needed by a rule that did not run, e.g. `SKY-D002`
+            // without `enable_danger`, or any rule for a pragma without a rule list.
+            .filter(|(_, pragma)| match &pragma.rules {
+                Rules::Only(rules) => rules.iter().all(|rule| self.is_rule_enabled(rule)),
+                Rules::All => self.enable_secrets && self.enable_danger && self.enable_quality,
+            })
+            .map(|(file, pragma)| UnusedSuppression {
+                pragma: pragma.text.clone(),
+                message: format!("Unused suppression '{}'", pragma.text),
+                rule_id: "SKY-I001".to_string(),
+                file: file.clone(),
+                line: pragma.line,
+                severity: "LOW".to_string(),
+            })
+            .collect();
+        unused.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+        unused
+    }
+
+    /// Whether a rule runs in this analysis: the secrets, danger and quality rules only
+    /// run when enabled.
+    fn is_rule_enabled(&self, rule_id: &str) -> bool {
+        if rule_id.starts_with("SKY-S") {
+            self.enable_secrets
+        } else if rule_id.starts_with("SKY-D") {
+            self.enable_danger
+        } else if rule_id.starts_with("SKY-Q") {
+            self.enable_quality
+        } else {
+            true
+        }
+    }
+
+    /// Builds the reference graph between functions, methods and classes, sorted by name.
+    /// A definition is dead if it would be reported as unused.
+    fn symbol_graph(
+        &self,
+        defs: &[Definition],
+        files: &[SourceFile],
+        live: &[bool],
+        suppressed: &[Option<usize>],
+        edges: &[(usize, usize)],
+    ) -> Graph {
+        let mut order: Vec<usize> = (0..defs.len())
+            .filter(|&i| matches!(defs[i].def_type.as_str(), "function" | "method" | "class"))
+            .collect();
+        order.sort_by(|a, b| defs[*a].full_name.cmp(&defs[*b].full_name));
+
+        let mut graph = Graph::default();
+        let mut position = HashMap::new();
+        for i in order {
+            let def = &defs[i];
+            let dead = def.confidence >= self.confidence_threshold
+                && suppressed[i].is_none()
+                && (def.references == 0 || !live[i]);
+            position.insert(
+                i,
+                graph.add_node(
+                    def.full_name.clone(),
+                    Some(files[def.file_id.index()].path.clone()),
+                    dead,
+                ),
+            );
+        }
+        let mut edges: Vec<(usize, usize)> = edges
+            .iter()
+            .filter(|(from, to)| from != to)
+            .filter_map(|(from, to)| Some((*position.get(from)?, *position.get(to)?)))
+            .collect();
+        edges.sort();
+        edges.dedup();
+        for (from, to) in edges {
+            graph.add_edge(from, to, REFERENCE_EDGE);
+        }
+        graph
+    }
+
+    /// Finds the modules that no other module imports.
+    ///
+    /// A module counts as imported if another module imports it or one of its members
+    /// (`from pkg import mod`, `import pkg.mod.sub`), loads it with a literal
+    /// `importlib.import_module` name, or names it in a dotted string (`"pkg.mod:app"`).
+    /// Names are also tried relative to the importing module's package, as for scripts
+    /// importing their siblings. Package `__init__.py` files, scripts (`__main__.py`,
+    /// modules with a main guard, `pyproject.toml` scripts and entry points, and files
+    /// run by tools such as `setup.py`), tests,
+    /// whitelisted modules and the public API are never reported.
+    fn find_dead_modules(&self, results: &[FileResult], root: &Path) -> Vec<DeadModule> {
+        // Dotted name -> modules importing it or one of its submodules or members.
+        let mut importers: HashMap<String, HashSet<&str>> = HashMap::new();
+        for file in results {
+            let package = if file.path.ends_with("__init__.py") {
+                file.module_name.as_str()
+            } else {
+                file.module_name.rsplit_once('.').map_or("", |(p, _)| p)
+            };
+            for imported in &file.imported_modules {
+                for name in [imported.clone(), qualify(package, imported)] {
+                    let mut prefix = name.as_str();
+                    loop {
+                        importers
+                            .entry(prefix.to_string())
+                            .or_default()
+                            .insert(&file.module_name);
+                        match prefix.rsplit_once('.') {
+                            Some((parent, _)) => prefix = parent,
+                            None => break,
+                        }
+                    }
+                }
+            }
+        }
+
+        let scripts = crate::entry_point::script_modules(root);
+        let dynamic_prefixes: Vec<&str> = results
+            .iter()
+            .flat_map(|f| f.dynamic_prefixes.iter().map(String::as_str))
+            .collect();
+
+        let mut dead_modules: Vec<DeadModule> = results
+            .iter()
+            .filter(|file| {
+                !file.module_name.is_empty()
+                    && !file.path.ends_with("__init__.py")
+                    && !file.is_script
+                    && !file.is_test_file
+                    && !scripts.contains(&file.module_name)
+                    && !self.whitelist.contains(&file.module_name)
+                    && !self.public_api.iter().any(|api| {
+                        file.module_name == *api
+                            || file.module_name.starts_with(&format!("{}.", api))
+                    })
+                    && !importers
+                        .get(&file.module_name)
+                        .is_some_and(|by| by.iter().any(|m| *m != file.module_name))
+            })
+            .map(|file| {
+                // `import_module(f"plugins.{name}")` may load the module.
+                let mut confidence = DEAD_MODULE_CONFIDENCE;
+                if dynamic_prefixes
+                    .iter()
+                    .any(|prefix| file.module_name.starts_with(prefix))
+                {
+                    confidence = confidence.saturating_sub(DYNAMIC_PREFIX_PENALTY);
+                }
+                DeadModule {
+                    module_name: file.module_name.clone(),
+                    message: format!(
+                        "Module '{}' is never imported ({} lines)",
+                        file.module_name, file.line_count
+                    ),
+                    rule_id: "SKY-M001".to_string(),
+                    file: file.path.clone(),
+                    line_count: file.line_count,
+                    confidence,
+                    severity: "MEDIUM".to_string(),
+                }
+            })
+            .filter(|module| module.confidence >= self.confidence_threshold)
+            .collect();
+
+        dead_modules.sort_by(|a, b| {
+            b.line_count
+                .cmp(&a.line_count)
+                .then_with(|| a.file.cmp(&b.file))
+        });
+        dead_modules
+    }
+}
+
+/// Reports the declared dependencies that are never imported and the imported
+/// third-party packages that are not declared, if the project has any manifest.
+///
+/// Every component of the project's own module names is first-party, since scripts and
+/// namespace packages are imported relative to their own directory rather than the root.
+fn check_dependencies(
+    results: &[FileResult],
+    root: &Path,
+) -> (Vec<DependencyFinding>, Vec<DependencyFinding>) {
+    let Some(dependencies) = crate::dependencies::load_dependencies(root) else {
+        return (Vec::new(), Vec::new());
+    };
+    let top_level = |name: &str| name.split('.').next().unwrap_or(name).to_string();
+
+    let first_party: HashSet<String> = results
+        .iter()
+        .flat_map(|f| f.module_name.split('.').map(str::to_string))
+        .collect();
+    let used: HashSet<String> = results
+        .iter()
+        .flat_map(|f| &f.imported_modules)
+        .map(|name| top_level(name))
+        .collect();
+    let mut imports: Vec<(String, PathBuf, usize)> = results
+        .iter()
+        .flat_map(|f| {
+            f.imports
+                .iter()
+                .chain(&f.star_imports)
+                .map(|import| (top_level(&import.target), f.path.clone(), import.line))
         })
+        .collect();
+    imports.sort_by(|a, b| (&a.1, a.2).cmp(&(&b.1, b.2)));
+
+    crate::dependencies::check_dependencies(&dependencies, &used, &imports, &first_party)
+}
+
+/// Resolves the references of a file, taking them out of it.
+///
+/// A reference counts for the definitions it resolves to through the imports of the
+/// module (including re-exports), and for the base class member it inherits
+/// (`self.helper()` where `helper` is defined in a base class). A bare name the module
+/// binds itself (`helper()` next to `def helper():`) counts for its qualified name.
+/// Only a name that does not resolve counts as written, so a call of a module's own
+/// `helper` does not count for a same-named function of another module. Attributes of
+/// objects of unknown type (`attr` in `obj.attr`) are never resolved.
+/// Each distinct name is only resolved once per file.
+fn resolve_references(
+    file: &mut FileResult,
+    symbols: &SymbolTable,
+    hierarchy: &ClassHierarchy,
+) -> ResolvedReferences {
+    let references = std::mem::take(&mut file.references);
+    let local_names = std::mem::take(&mut file.symbols);
+    let mut names = Interner::new();
+    let mut counts = Vec::new();
+    let mut edges = HashSet::new();
+    let module_bindings: HashSet<&str> = file.module_bindings.iter().map(String::as_str).collect();
+    // Resolved names of each local name, filled on first use.
+    let mut targets: Vec<Option<Vec<Symbol>>> = vec![None; local_names.len()];
+    let mut attribute_targets: Vec<Option<Vec<Symbol>>> = vec![None; local_names.len()];
+    for reference in references {
+        let name = local_names.resolve(reference.name);
+        let resolved = if reference.is_attribute {
+            attribute_targets[reference.name.index()]
+                .get_or_insert_with(|| vec![names.intern(name)])
+        } else {
+            targets[reference.name.index()].get_or_insert_with(|| {
+                let mut resolved = symbols.resolve_reference(&file.module_name, name);
+                if resolved.is_empty()
+                    && !file.module_name.is_empty()
+                    && module_bindings.contains(name)
+                {
+                    resolved.push(qualify(&file.module_name, name));
+                }
+                if resolved.is_empty() {
+                    resolved.push(name.to_string());
+                }
+                let inherited: Vec<String> = resolved
+                    .iter()
+                    .filter_map(|n| hierarchy.resolve_inherited(n))
+                    .collect();
+                resolved.extend(inherited);
+                resolved.iter().map(|n| names.intern(n)).collect()
+            })
+        };
+        counts.resize(names.len(), RefCount::default());
+        for &name in resolved.iter() {
+            counts[name.index()].add(RefCount::once(reference.typing_only));
+            edges.insert((reference.scope, name));
+        }
     }
+    let mut edges: Vec<_> = edges.into_iter().collect();
+    edges.sort();
+    ResolvedReferences {
+        names,
+        counts,
+        edges,
+    }
+}
+
+/// Builds the import graph between the project's modules.
+fn build_import_graph(results: &[FileResult]) -> ImportGraph {
+    let mut graph = ImportGraph::new();
+    for file in results.iter().filter(|f| !f.module_name.is_empty()) {
+        graph.add_module(&file.module_name, file.path.clone());
+    }
+    for file in results {
+        for (name, line, context) in &file.module_imports {
+            graph.add_import(&file.module_name, name, *line, *context);
+        }
+    }
+    graph
+}
+
+/// Whether a definition is a class member that can override a base class member.
+fn is_member(def: &Definition) -> bool {
+    matches!(def.def_type.as_str(), "method" | "attribute")
+}
+
+/// Computes the names each module provides to `from module import *`.
+///
+/// That is its `__all__` if it defines one, and otherwise its public module-level names,
+/// including the public names it gets from star imports of its own.
+fn star_exports(results: &[FileResult]) -> HashMap<&str, BTreeSet<&str>> {
+    let mut provided: HashMap<&str, BTreeSet<&str>> = HashMap::new();
+    for file in results {
+        let names: BTreeSet<&str> = if file.defines_all() {
+            file.exports.iter().map(|(name, _)| name.as_str()).collect()
+        } else {
+            file.module_bindings
+                .iter()
+                .map(String::as_str)
+                .filter(|name| !name.starts_with('_'))
+                .collect()
+        };
+        provided.insert(&file.module_name, names);
+    }
+
+    // Star imports can be chained, so propagate names until nothing changes.
+    // Sets only grow and are bounded by the names in the project, so this terminates.
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for file in results.iter().filter(|f| !f.defines_all()) {
+            for star in &file.star_imports {
+                let Some(imported) = provided.get(star.target.as_str()).cloned() else {
+                    continue;
+                };
+                if let Some(names) = provided.get_mut(file.module_name.as_str()) {
+                    for name in imported.into_iter().filter(|n| !n.starts_with('_')) {
+                        changed |= names.insert(name);
+                    }
+                }
+            }
+        }
+    }
+    provided
+}
+
+/// Resolves a base class name as written in a module (e.g. `Base` or `ast.NodeVisitor`)
+/// to its fully qualified name: through the module's imports if it is imported, as a
+/// class defined in the module itself if the module binds it, and otherwise as a
+/// builtin (e.g. `object` or `dict`), keeping the name as written.
+fn resolve_class_name(symbols: &SymbolTable, file: &FileResult, name: &str) -> String {
+    if let Some(resolved) = symbols.resolve_reference(&file.module_name, name).pop() {
+        return resolved;
+    }
+    let head = name.split('.').next().unwrap_or(name);
+    if file.module_bindings.iter().any(|binding| binding == head) {
+        qualify(&file.module_name, name)
+    } else {
+        name.to_string()
+    }
+}
+
+/// Removes the findings suppressed by a pragma in their file, marking the pragmas used.
+///
+/// Findings are matched at their reported line: the first line of an unreachable block,
+/// line 1 of a dead module, and any import statement of an import cycle.
+/// Unused definitions are suppressed earlier, as they also keep their callees alive.
+fn apply_suppressions(
+    result: &mut AnalysisResult,
+    suppressions: &mut HashMap<PathBuf, Suppressions>,
+) {
+    let mut is_suppressed = |file: &Path, line: usize, rule_id: &str| {
+        suppressions
+            .get_mut(file)
+            .is_some_and(|s| s.suppress(line, Some(rule_id)))
+    };
+    result
+        .secrets
+        .retain(|f| !is_suppressed(&f.file, f.line, &f.rule_id));
+    result
+        .danger
+        .retain(|f| !is_suppressed(&f.file, f.line, &f.rule_id));
+    result
+        .quality
+        .retain(|f| !is_suppressed(&f.file, f.line, &f.rule_id));
+    result
+        .undefined_exports
+        .retain(|f| !is_suppressed(&f.file, f.line, &f.rule_id));
+    result
+        .unreachable_code
+        .retain(|f| !is_suppressed(&f.file, f.line, &f.rule_id));
+    result
+        .redefinitions
+        .retain(|f| !is_suppressed(&f.file, f.line, &f.rule_id));
+    result
+        .dead_modules
+        .retain(|f| !is_suppressed(&f.file, 1, &f.rule_id));
+    result
+        .unused_dependencies
+        .retain(|f| !is_suppressed(&f.file, f.line, &f.rule_id));
+    result
+        .undeclared_dependencies
+        .retain(|f| !is_suppressed(&f.file, f.line, &f.rule_id));
+    result.import_cycles.retain(|cycle| {
+        !cycle
+            .path
+            .iter()
+            .any(|step| is_suppressed(&step.file, step.line, &cycle.rule_id))
+    });
+
+    let summary = &mut result.analysis_summary;
+    summary.secrets_count = result.secrets.len();
+    summary.danger_count = result.danger.len();
+    summary.quality_count = result.quality.len();
 }
 
 /// Applies penalties to the confidence score of a definition.
 ///
 /// This adjusts confidence based on:
-/// - "no skylos" pragmas (ignores the line).
 /// - Test files (ignores definitions in tests).
 /// - Framework decorations (lowers confidence for framework-managed code).
 /// - Private naming conventions (lowers confidence for internal helpers).
 /// - Dunder methods (ignores magic methods).
-fn apply_penalties(
-    def: &mut Definition,
-    fv: &FrameworkAwareVisitor,
-    tv: &TestAwareVisitor,
-    ignored_lines: &std::collections::HashSet<usize>,
-) {
-    // Pragma: no skylos (highest priority - always skip)
-    // If the line is marked to be ignored, set confidence to 0.
-    if ignored_lines.contains(&def.line) {
-        def.confidence = 0;
-        return;
-    }
-
+fn apply_penalties(def: &mut Definition, fv: &FrameworkAwareVisitor, tv: &TestAwareVisitor) {
     // Test files: confidence 0 (ignore)
     // We don't want to report unused code in test files usually.
     if tv.is_test_file || tv.test_decorated_lines.contains(&def.line) {
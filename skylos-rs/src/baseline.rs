@@ -0,0 +1,327 @@
+use crate::analyzer::AnalysisResult;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Version of the baseline file format.
+pub const BASELINE_VERSION: u32 = 1;
+
+/// Findings accepted as they are, so that only new findings are reported.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Baseline {
+    /// Version of the file format.
+    pub version: u32,
+    /// The accepted findings, in report order.
+    pub findings: Vec<BaselineEntry>,
+}
+
+/// A finding recorded in a baseline.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BaselineEntry {
+    /// Hash of the rule, the file and the symbol name or normalized code of the finding.
+    /// It does not depend on the line, so the finding still matches after code above
+    /// it changed.
+    pub fingerprint: String,
+    /// Rule ID, or the category of an unused definition (e.g. `unused_functions`).
+    pub rule: String,
+    /// File of the finding, relative to the baseline file.
+    pub file: PathBuf,
+    /// Line of the finding when the baseline was written, for reference only.
+    pub line: usize,
+    /// Name of the symbol, module or package the finding is about, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// A finding of any category, as needed to fingerprint it.
+struct Finding<'a> {
+    /// Rule ID, or the category of an unused definition.
+    rule: &'a str,
+    /// File of the finding.
+    file: &'a Path,
+    /// Line of the finding.
+    line: usize,
+    /// Name identifying the finding in its file. Findings without one (secrets, danger,
+    /// quality, unreachable code) are identified by the code at their line instead.
+    name: Option<String>,
+}
+
+impl Baseline {
+    /// Records every finding of an analysis. File paths are made relative to the
+    /// directory of `path`, where the baseline is going to be written.
+    pub fn from_result(result: &AnalysisResult, path: &Path) -> Self {
+        let mut fingerprinter = Fingerprinter::new(path);
+        let findings = findings(result)
+            .iter()
+            .map(|finding| fingerprinter.entry(finding))
+            .collect();
+        Baseline {
+            version: BASELINE_VERSION,
+            findings,
+        }
+    }
+
+    /// Reads a baseline file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("failed to read baseline {}", path.display()))?;
+        let baseline: Baseline = serde_json::from_str(&content)
+            .with_context(|| format!("invalid baseline {}", path.display()))?;
+        if baseline.version != BASELINE_VERSION {
+            bail!(
+                "unsupported baseline version {} in {}, expected {}",
+                baseline.version,
+                path.display(),
+                BASELINE_VERSION
+            );
+        }
+        Ok(baseline)
+    }
+
+    /// Writes the baseline as JSON.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)? + "\n")
+            .with_context(|| format!("failed to write baseline {}", path.display()))
+    }
+
+    /// Removes the findings of `result` that are in the baseline, where `path` is the
+    /// baseline file. Returns the baseline entries that matched no finding.
+    ///
+    /// Each entry matches a single finding, so a second identical finding is new.
+    pub fn apply(&self, result: &mut AnalysisResult, path: &Path) -> Vec<BaselineEntry> {
+        let mut remaining: HashMap<&str, usize> = HashMap::new();
+        for entry in &self.findings {
+            *remaining.entry(entry.fingerprint.as_str()).or_default() += 1;
+        }
+
+        let mut fingerprinter = Fingerprinter::new(path);
+        let keep: Vec<bool> = findings(result)
+            .iter()
+            .map(|finding| {
+                let fingerprint = fingerprinter.entry(finding).fingerprint;
+                match remaining.get_mut(fingerprint.as_str()) {
+                    Some(count) if *count > 0 => {
+                        *count -= 1;
+                        false
+                    }
+                    _ => true,
+                }
+            })
+            .collect();
+        retain(result, &keep);
+
+        // Entries left over after matching are stale.
+        let mut stale = Vec::new();
+        for entry in &self.findings {
+            if let Some(count) = remaining.get_mut(entry.fingerprint.as_str()) {
+                if *count > 0 {
+                    *count -= 1;
+                    stale.push(entry.clone());
+                }
+            }
+        }
+        stale
+    }
+}
+
+/// Computes the fingerprints of findings, reading each source file once.
+struct Fingerprinter {
+    /// Directory file paths are made relative to.
+    base: PathBuf,
+    /// Lines of the files read so far.
+    sources: HashMap<PathBuf, Vec<String>>,
+}
+
+impl Fingerprinter {
+    /// Creates a fingerprinter for the baseline file at `path`.
+    fn new(path: &Path) -> Self {
+        let dir = path.parent().unwrap_or(Path::new(""));
+        let dir = if dir.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            dir
+        };
+        Self {
+            base: fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf()),
+            sources: HashMap::new(),
+        }
+    }
+
+    /// Builds the baseline entry of a finding.
+    fn entry(&mut self, finding: &Finding) -> BaselineEntry {
+        let file = self.relative(finding.file);
+        let key = match &finding.name {
+            Some(name) => name.clone(),
+            None => self.code(finding.file, finding.line),
+        };
+        let file_key = file.to_string_lossy().replace('\\', "/");
+        BaselineEntry {
+            fingerprint: fingerprint(&[finding.rule, &file_key, &key]),
+            rule: finding.rule.to_string(),
+            file: PathBuf::from(file_key),
+            line: finding.line,
+            name: finding.name.clone(),
+        }
+    }
+
+    /// Makes a path relative to the baseline directory, if it is inside of it.
+    fn relative(&self, file: &Path) -> PathBuf {
+        let absolute = fs::canonicalize(file).unwrap_or_else(|_| file.to_path_buf());
+        match absolute.strip_prefix(&self.base) {
+            Ok(relative) => relative.to_path_buf(),
+            // Outside of the baseline directory: keep the path as given, without `.`.
+            Err(_) => file
+                .components()
+                .filter(|c| !matches!(c, Component::CurDir))
+                .collect(),
+        }
+    }
+
+    /// Returns the code at a line with its whitespace normalized, so that reindenting or
+    /// reformatting the line keeps its fingerprint.
+    fn code(&mut self, file: &Path, line: usize) -> String {
+        let lines = self.sources.entry(file.to_path_buf()).or_insert_with(|| {
+            fs::read_to_string(file)
+                .unwrap_or_default()
+                .lines()
+                .map(str::to_string)
+                .collect()
+        });
+        lines
+            .get(line.wrapping_sub(1))
+            .map(|code| code.split_whitespace().collect::<Vec<_>>().join(" "))
+            .unwrap_or_default()
+    }
+}
+
+/// Hashes the parts of a fingerprint with 64-bit FNV-1a, which unlike the standard
+/// library's hasher is stable across Rust versions and platforms.
+fn fingerprint(parts: &[&str]) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for part in parts {
+        for byte in part.bytes().chain([0]) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    format!("{:016x}", hash)
+}
+
+/// Lists the findings of every category, in the order of `retain`.
+fn findings(result: &AnalysisResult) -> Vec<Finding<'_>> {
+    let mut findings = Vec::new();
+    let definitions = [
+        ("unused_functions", &result.unused_functions),
+        ("unused_imports", &result.unused_imports),
+        ("unused_classes", &result.unused_classes),
+        ("unused_variables", &result.unused_variables),
+        ("unused_parameters", &result.unused_parameters),
+        ("unused_attributes", &result.unused_attributes),
+        ("typing_only_imports", &result.typing_only_imports),
+    ];
+    for (category, defs) in definitions {
+        findings.extend(defs.iter().map(|d| Finding {
+            rule: category,
+            file: &d.file,
+            line: d.line,
+            name: Some(d.full_name.clone()),
+        }));
+    }
+    findings.extend(result.dead_modules.iter().map(|m| Finding {
+        rule: &m.rule_id,
+        file: &m.file,
+        line: 1,
+        name: Some(m.module_name.clone()),
+    }));
+    let dependencies = result
+        .unused_dependencies
+        .iter()
+        .chain(&result.undeclared_dependencies);
+    findings.extend(dependencies.map(|d| Finding {
+        rule: &d.rule_id,
+        file: &d.file,
+        line: d.line,
+        name: Some(d.name.clone()),
+    }));
+    findings.extend(result.import_cycles.iter().map(|c| Finding {
+        rule: &c.rule_id,
+        file: &c.file,
+        line: c.line,
+        name: Some(c.modules.join(", ")),
+    }));
+    findings.extend(result.undefined_exports.iter().map(|e| Finding {
+        rule: &e.rule_id,
+        file: &e.file,
+        line: e.line,
+        name: Some(e.name.clone()),
+    }));
+    findings.extend(result.unreachable_code.iter().map(|u| Finding {
+        rule: &u.rule_id,
+        file: &u.file,
+        line: u.line,
+        name: None,
+    }));
+    findings.extend(result.redefinitions.iter().map(|r| Finding {
+        rule: &r.rule_id,
+        file: &r.file,
+        line: r.line,
+        name: Some(r.name.clone()),
+    }));
+    findings.extend(result.unused_suppressions.iter().map(|s| Finding {
+        rule: &s.rule_id,
+        file: &s.file,
+        line: s.line,
+        name: Some(s.pragma.clone()),
+    }));
+    findings.extend(result.secrets.iter().map(|s| Finding {
+        rule: &s.rule_id,
+        file: &s.file,
+        line: s.line,
+        name: None,
+    }));
+    findings.extend(result.danger.iter().map(|d| Finding {
+        rule: &d.rule_id,
+        file: &d.file,
+        line: d.line,
+        name: None,
+    }));
+    findings.extend(result.quality.iter().map(|q| Finding {
+        rule: &q.rule_id,
+        file: &q.file,
+        line: q.line,
+        name: None,
+    }));
+    findings
+}
+
+/// Keeps the findings whose entry in `keep` (in the order of `findings`) is set, and
+/// updates the summary counts.
+fn retain(result: &mut AnalysisResult, keep: &[bool]) {
+    let mut keep = keep.iter().copied();
+    let mut next = || keep.next().unwrap_or(true);
+    result.unused_functions.retain(|_| next());
+    result.unused_imports.retain(|_| next());
+    result.unused_classes.retain(|_| next());
+    result.unused_variables.retain(|_| next());
+    result.unused_parameters.retain(|_| next());
+    result.unused_attributes.retain(|_| next());
+    result.typing_only_imports.retain(|_| next());
+    result.dead_modules.retain(|_| next());
+    result.unused_dependencies.retain(|_| next());
+    result.undeclared_dependencies.retain(|_| next());
+    result.import_cycles.retain(|_| next());
+    result.undefined_exports.retain(|_| next());
+    result.unreachable_code.retain(|_| next());
+    result.redefinitions.retain(|_| next());
+    result.unused_suppressions.retain(|_| next());
+    result.secrets.retain(|_| next());
+    result.danger.retain(|_| next());
+    result.quality.retain(|_| next());
+
+    let summary = &mut result.analysis_summary;
+    summary.secrets_count = result.secrets.len();
+    summary.danger_count = result.danger.len();
+    summary.quality_count = result.quality.len();
+}
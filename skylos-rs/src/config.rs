@@ -0,0 +1,212 @@
+use crate::gate::CATEGORIES;
+use crate::rules::RULE_IDS;
+use anyhow::{bail, Context, Result};
+use ignore::overrides::{Override, OverrideBuilder};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name of the standalone configuration file.
+pub const CONFIG_FILE: &str = ".skylos.toml";
+
+/// Severity levels that can be given to a rule, from lowest to highest.
+pub const SEVERITIES: &[&str] = &["LOW", "MEDIUM", "HIGH", "CRITICAL"];
+
+/// Settings read from `.skylos.toml`, or from the `[tool.skylos]` table of `pyproject.toml`.
+///
+/// Every setting is optional. Command line flags take precedence over the file, and a
+/// list given on the command line replaces the one from the file.
+/// Unknown keys are rejected, so typos do not silently fall back to the defaults.
+#[derive(Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Config {
+    /// Confidence threshold (0-100).
+    pub confidence: Option<u8>,
+    /// Whether to scan for secrets.
+    pub secrets: Option<bool>,
+    /// Whether to scan for dangerous code.
+    pub danger: Option<bool>,
+    /// Whether to scan for code quality issues.
+    pub quality: Option<bool>,
+    /// Glob patterns (gitignore syntax) of files and directories to skip, relative to the
+    /// analyzed directory.
+    pub exclude: Vec<String>,
+    /// Glob patterns of the files to analyze, relative to the analyzed directory.
+    /// If set, other files are skipped.
+    pub include: Vec<String>,
+    /// Folders to analyze even though they are excluded by default, e.g. `build`.
+    pub include_folders: Vec<String>,
+    /// Whitelist files, relative to the configuration file.
+    pub whitelist: Vec<PathBuf>,
+    /// Qualified names (or name prefixes) of the public API.
+    pub public_api: Vec<String>,
+    /// Severity of individual rules, e.g. `SKY-Q001 = "HIGH"`.
+    pub severity: BTreeMap<String, String>,
+    /// Thresholds of the quality rules.
+    pub thresholds: Thresholds,
+    /// Severity at or above which any finding fails the run, e.g. `"HIGH"`.
+    pub fail_on: Option<String>,
+    /// Number of dead code findings the run may have before it fails.
+    pub max_dead_code: Option<usize>,
+    /// Number of findings each category may have before the run fails, e.g.
+    /// `unused_imports = 0`.
+    pub max_findings: BTreeMap<String, usize>,
+}
+
+/// Thresholds of the quality rules.
+#[derive(Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Thresholds {
+    /// Nesting depth above which code is reported as deeply nested (`SKY-Q001`).
+    pub max_nesting_depth: Option<usize>,
+}
+
+/// The parts of `pyproject.toml` we read; other tables are ignored.
+#[derive(Deserialize, Default)]
+struct PyProject {
+    #[serde(default)]
+    tool: Tool,
+}
+
+/// The `[tool]` table of `pyproject.toml`.
+#[derive(Deserialize, Default)]
+struct Tool {
+    skylos: Option<Config>,
+}
+
+impl Config {
+    /// Finds the configuration of the project containing `path`, walking up from it.
+    ///
+    /// In each directory, a `.skylos.toml` takes precedence over `pyproject.toml`, and a
+    /// `pyproject.toml` without a `[tool.skylos]` table is skipped.
+    /// Returns the configuration and the file it was read from.
+    pub fn discover(path: &Path) -> Result<Option<(Config, PathBuf)>> {
+        let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        let start = if path.is_file() {
+            path.parent().unwrap_or(&path)
+        } else {
+            &path
+        };
+        for dir in start.ancestors() {
+            let file = dir.join(CONFIG_FILE);
+            if file.is_file() {
+                return Ok(Some((Self::load(&file)?, file)));
+            }
+            let file = dir.join("pyproject.toml");
+            if file.is_file() {
+                if let Some(config) = Self::read(&file)? {
+                    return Ok(Some((config, file)));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Reads a configuration file: the `[tool.skylos]` table of a `pyproject.toml`, or
+    /// a standalone file like `.skylos.toml`.
+    pub fn load(path: &Path) -> Result<Config> {
+        Ok(Self::read(path)?.unwrap_or_default())
+    }
+
+    /// Reads a configuration file, returning `None` for a `pyproject.toml` without
+    /// `[tool.skylos]`. Whitelist paths are made relative to the current directory.
+    fn read(path: &Path) -> Result<Option<Config>> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("failed to read configuration {}", path.display()))?;
+        let config = if path
+            .file_name()
+            .is_some_and(|name| name == "pyproject.toml")
+        {
+            toml::from_str::<PyProject>(&content)
+                .with_context(|| format!("invalid configuration in {}", path.display()))?
+                .tool
+                .skylos
+        } else {
+            Some(
+                toml::from_str::<Config>(&content)
+                    .with_context(|| format!("invalid configuration in {}", path.display()))?,
+            )
+        };
+        let Some(mut config) = config else {
+            return Ok(None);
+        };
+        config
+            .validate()
+            .with_context(|| format!("invalid configuration in {}", path.display()))?;
+
+        let base = path.parent().unwrap_or(Path::new(""));
+        for whitelist in &mut config.whitelist {
+            *whitelist = base.join(&*whitelist);
+        }
+        Ok(Some(config))
+    }
+
+    /// Checks the values the deserializer cannot check, and normalizes severities to
+    /// upper case.
+    fn validate(&mut self) -> Result<()> {
+        if let Some(confidence) = self.confidence {
+            if confidence > 100 {
+                bail!("`confidence` must be between 0 and 100, got {}", confidence);
+            }
+        }
+        for (rule, severity) in &mut self.severity {
+            if !RULE_IDS.contains(&rule.as_str()) {
+                bail!(
+                    "unknown rule `{}` in `severity`, expected one of {}",
+                    rule,
+                    RULE_IDS.join(", ")
+                );
+            }
+            let upper = severity.to_uppercase();
+            if !SEVERITIES.contains(&upper.as_str()) {
+                bail!(
+                    "unknown severity `{}` for `{}`, expected one of {}",
+                    severity,
+                    rule,
+                    SEVERITIES.join(", ")
+                );
+            }
+            *severity = upper;
+        }
+        if let Some(fail_on) = &mut self.fail_on {
+            let upper = fail_on.to_uppercase();
+            if !SEVERITIES.contains(&upper.as_str()) {
+                bail!(
+                    "unknown severity `{}` in `fail-on`, expected one of {}",
+                    fail_on,
+                    SEVERITIES.join(", ")
+                );
+            }
+            *fail_on = upper;
+        }
+        for category in self.max_findings.keys() {
+            if !CATEGORIES.contains(&category.as_str()) {
+                bail!(
+                    "unknown category `{}` in `max-findings`, expected one of {}",
+                    category,
+                    CATEGORIES.join(", ")
+                );
+            }
+        }
+        file_overrides(Path::new(""), &self.include, &self.exclude)?;
+        Ok(())
+    }
+}
+
+/// Builds the file filter of the `include` and `exclude` glob patterns, relative to `root`.
+/// Excludes win over includes.
+pub fn file_overrides(root: &Path, include: &[String], exclude: &[String]) -> Result<Override> {
+    let mut builder = OverrideBuilder::new(root);
+    for pattern in include {
+        builder
+            .add(pattern)
+            .with_context(|| format!("invalid include pattern `{}`", pattern))?;
+    }
+    for pattern in exclude {
+        builder
+            .add(&format!("!{}", pattern))
+            .with_context(|| format!("invalid exclude pattern `{}`", pattern))?;
+    }
+    Ok(builder.build()?)
+}
@@ -0,0 +1,347 @@
+use crate::graph::Graph;
+use crate::visitor::ImportContext;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::path::PathBuf;
+
+/// An import cycle between first-party modules.
+#[derive(Serialize, Clone, Debug)]
+pub struct ImportCycle {
+    /// Modules of the strongly connected component, sorted by name.
+    pub modules: Vec<String>,
+    /// One concrete cycle through the component: each step is an import statement, and
+    /// the last step imports the module of the first one.
+    pub path: Vec<CycleStep>,
+    /// `module-level` if the cycle runs on import, `function-local` if it goes through
+    /// imports inside functions, or `type-checking` if only through `TYPE_CHECKING` imports.
+    pub kind: String,
+    /// Description of the issue.
+    pub message: String,
+    /// Unique rule identifier.
+    pub rule_id: String,
+    /// File of the first import statement of the cycle.
+    pub file: PathBuf,
+    /// Line number of the first import statement of the cycle.
+    pub line: usize,
+    /// Severity level.
+    pub severity: String,
+}
+
+/// An import statement on an import cycle.
+#[derive(Serialize, Clone, Debug)]
+pub struct CycleStep {
+    /// The importing module.
+    pub module: String,
+    /// The imported module.
+    pub imports: String,
+    /// File of the importing module.
+    pub file: PathBuf,
+    /// Line number of the import statement.
+    pub line: usize,
+}
+
+/// An import of one module by another, keeping the import statement that runs earliest.
+#[derive(Clone, Copy)]
+struct Edge {
+    to: usize,
+    line: usize,
+    context: ImportContext,
+}
+
+/// The import graph between first-party modules.
+///
+/// Imports of `pkg.mod.name` are resolved to the longest module prefix in the project
+/// (`pkg.mod.name` if it is a submodule, otherwise `pkg.mod`); imports of modules outside
+/// the project are ignored.
+#[derive(Default)]
+pub struct ImportGraph {
+    /// Module names, indexed by module.
+    modules: Vec<String>,
+    /// File of each module.
+    files: Vec<PathBuf>,
+    /// Module name -> index.
+    index: HashMap<String, usize>,
+    /// Imports of each module, keyed by imported module (sorted for stable output).
+    edges: Vec<BTreeMap<usize, Edge>>,
+}
+
+impl ImportGraph {
+    /// Creates an empty import graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a module. All modules must be added before their imports.
+    pub fn add_module(&mut self, name: &str, file: PathBuf) {
+        if self.index.contains_key(name) {
+            return;
+        }
+        self.index.insert(name.to_string(), self.modules.len());
+        self.modules.push(name.to_string());
+        self.files.push(file);
+        self.edges.push(BTreeMap::new());
+    }
+
+    /// Adds an import of the dotted name `imported` made by the module `from`.
+    pub fn add_import(&mut self, from: &str, imported: &str, line: usize, context: ImportContext) {
+        let Some(&from) = self.index.get(from) else {
+            return;
+        };
+        let Some(to) = self.resolve(imported) else {
+            return;
+        };
+        if to == from {
+            return;
+        }
+        let edge = Edge { to, line, context };
+        self.edges[from]
+            .entry(to)
+            .and_modify(|existing| {
+                if (context, line) < (existing.context, existing.line) {
+                    *existing = edge;
+                }
+            })
+            .or_insert(edge);
+    }
+
+    /// Finds the import cycles of the graph.
+    ///
+    /// Every strongly connected component of the module-level imports is reported as a
+    /// `module-level` cycle (`SKY-C001`): importing any of its modules runs into a module
+    /// that is only partially initialized. Components that only exist through imports
+    /// inside functions or `TYPE_CHECKING` blocks are reported as `function-local`
+    /// (`SKY-C002`) or `type-checking` (`SKY-C003`) cycles, which do not fail on import.
+    pub fn find_cycles(&self) -> Vec<ImportCycle> {
+        let mut cycles = Vec::new();
+
+        let runtime = |edge: &Edge| edge.context == ImportContext::Module;
+        let runtime_components = self.components(runtime);
+        for component in &runtime_components {
+            let start = component[0];
+            let path = self.shortest_path(component, start, start, runtime);
+            cycles.push(self.cycle(component, path));
+        }
+
+        for component in self.components(|_| true) {
+            if runtime_components.contains(&component) {
+                continue;
+            }
+            // Close the cycle through the first deferred import of the component.
+            let Some((from, edge)) = component.iter().find_map(|&from| {
+                self.edges[from]
+                    .values()
+                    .find(|edge| !runtime(edge) && component.contains(&edge.to))
+                    .map(|edge| (from, *edge))
+            }) else {
+                continue;
+            };
+            let mut path = vec![(from, edge)];
+            path.extend(self.shortest_path(&component, edge.to, from, |_| true));
+            cycles.push(self.cycle(&component, path));
+        }
+
+        cycles
+    }
+
+    /// Exports the import graph for rendering, with modules sorted by name.
+    /// Each edge is labeled with the context of its earliest import statement.
+    pub fn to_graph(&self) -> Graph {
+        let mut order: Vec<usize> = (0..self.modules.len()).collect();
+        order.sort_by(|a, b| self.modules[*a].cmp(&self.modules[*b]));
+        let mut position = vec![0; order.len()];
+        let mut graph = Graph::default();
+        for &module in &order {
+            position[module] = graph.add_node(
+                self.modules[module].clone(),
+                Some(self.files[module].clone()),
+                false,
+            );
+        }
+        for &module in &order {
+            let mut edges: Vec<&Edge> = self.edges[module].values().collect();
+            edges.sort_by_key(|edge| position[edge.to]);
+            for edge in edges {
+                graph.add_edge(position[module], position[edge.to], edge.context.as_str());
+            }
+        }
+        graph
+    }
+
+    /// Resolves a dotted name to the module that defines it.
+    fn resolve(&self, name: &str) -> Option<usize> {
+        let mut prefix = name;
+        loop {
+            if let Some(&module) = self.index.get(prefix) {
+                return Some(module);
+            }
+            prefix = prefix.rsplit_once('.')?.0;
+        }
+    }
+
+    /// Computes the strongly connected components with more than one module, using only
+    /// the edges accepted by `follow` (iterative Tarjan's algorithm).
+    /// Each component is sorted by module name, and components are ordered by their
+    /// first module.
+    fn components(&self, follow: impl Fn(&Edge) -> bool) -> Vec<Vec<usize>> {
+        let n = self.modules.len();
+        let mut index = vec![usize::MAX; n];
+        let mut lowlink = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut stack = Vec::new();
+        let mut counter = 0;
+        let mut components = Vec::new();
+
+        for root in 0..n {
+            if index[root] != usize::MAX {
+                continue;
+            }
+            // (module, successors not yet visited)
+            let mut work: Vec<(usize, Vec<usize>)> = Vec::new();
+            let successors = |module: usize| -> Vec<usize> {
+                self.edges[module]
+                    .values()
+                    .filter(|edge| follow(edge))
+                    .map(|edge| edge.to)
+                    .rev()
+                    .collect()
+            };
+            index[root] = counter;
+            lowlink[root] = counter;
+            counter += 1;
+            stack.push(root);
+            on_stack[root] = true;
+            work.push((root, successors(root)));
+
+            while let Some((module, pending)) = work.last_mut() {
+                let module = *module;
+                if let Some(next) = pending.pop() {
+                    if index[next] == usize::MAX {
+                        index[next] = counter;
+                        lowlink[next] = counter;
+                        counter += 1;
+                        stack.push(next);
+                        on_stack[next] = true;
+                        work.push((next, successors(next)));
+                    } else if on_stack[next] {
+                        lowlink[module] = lowlink[module].min(index[next]);
+                    }
+                    continue;
+                }
+
+                work.pop();
+                if let Some((parent, _)) = work.last() {
+                    lowlink[*parent] = lowlink[*parent].min(lowlink[module]);
+                }
+                if lowlink[module] == index[module] {
+                    let mut component = Vec::new();
+                    while let Some(member) = stack.pop() {
+                        on_stack[member] = false;
+                        component.push(member);
+                        if member == module {
+                            break;
+                        }
+                    }
+                    if component.len() > 1 {
+                        component.sort_by(|a, b| self.modules[*a].cmp(&self.modules[*b]));
+                        components.push(component);
+                    }
+                }
+            }
+        }
+
+        components.sort_by(|a, b| self.modules[a[0]].cmp(&self.modules[b[0]]));
+        components
+    }
+
+    /// Finds the shortest import path from `from` to `to` within a component, following
+    /// the edges accepted by `follow`. When `from == to`, the path is a cycle.
+    fn shortest_path(
+        &self,
+        component: &[usize],
+        from: usize,
+        to: usize,
+        follow: impl Fn(&Edge) -> bool,
+    ) -> Vec<(usize, Edge)> {
+        // Module -> the import it was first reached through.
+        let mut reached: HashMap<usize, (usize, Edge)> = HashMap::new();
+        let mut queue = VecDeque::from([from]);
+        while let Some(module) = queue.pop_front() {
+            for edge in self.edges[module].values() {
+                if !follow(edge) || !component.contains(&edge.to) || reached.contains_key(&edge.to)
+                {
+                    continue;
+                }
+                reached.insert(edge.to, (module, *edge));
+                if edge.to == to {
+                    queue.clear();
+                    break;
+                }
+                queue.push_back(edge.to);
+            }
+        }
+
+        let mut path = Vec::new();
+        let mut current = to;
+        while let Some(&(module, edge)) = reached.get(&current) {
+            path.push((module, edge));
+            current = module;
+            if current == from {
+                break;
+            }
+        }
+        path.reverse();
+        path
+    }
+
+    /// Builds the finding for a component and a cycle through it.
+    fn cycle(&self, component: &[usize], path: Vec<(usize, Edge)>) -> ImportCycle {
+        let has_context = |context| path.iter().any(|(_, edge)| edge.context == context);
+        let context = if has_context(ImportContext::Function) {
+            ImportContext::Function
+        } else if has_context(ImportContext::TypeChecking) {
+            ImportContext::TypeChecking
+        } else {
+            ImportContext::Module
+        };
+        let (rule_id, severity) = match context {
+            ImportContext::Module => ("SKY-C001", "MEDIUM"),
+            ImportContext::Function => ("SKY-C002", "LOW"),
+            ImportContext::TypeChecking => ("SKY-C003", "LOW"),
+        };
+        let kind = context.as_str();
+
+        let steps: Vec<CycleStep> = path
+            .iter()
+            .map(|(module, edge)| CycleStep {
+                module: self.modules[*module].clone(),
+                imports: self.modules[edge.to].clone(),
+                file: self.files[*module].clone(),
+                line: edge.line,
+            })
+            .collect();
+        let mut names: Vec<&str> = steps.iter().map(|step| step.module.as_str()).collect();
+        names.extend(steps.first().map(|step| step.module.as_str()));
+        let message = match context {
+            ImportContext::Module => format!("Import cycle: {}", names.join(" -> ")),
+            _ => format!(
+                "Import cycle through {} imports: {}",
+                kind,
+                names.join(" -> ")
+            ),
+        };
+
+        ImportCycle {
+            modules: component
+                .iter()
+                .map(|&module| self.modules[module].clone())
+                .collect(),
+            kind: kind.to_string(),
+            message,
+            rule_id: rule_id.to_string(),
+            file: steps.first().map(|s| s.file.clone()).unwrap_or_default(),
+            line: steps.first().map_or(0, |s| s.line),
+            severity: severity.to_string(),
+            path: steps,
+        }
+    }
+}
@@ -0,0 +1,802 @@
+use rustpython_ast::{self as ast, Constant, Expr, Stmt};
+use rustpython_parser::{parse, Mode};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Group name of the build requirements declared in `[build-system]`.
+const BUILD_SYSTEM_GROUP: &str = "build-system";
+
+/// Top-level modules of the Python standard library (`sys.stdlib_module_names`, plus
+/// modules removed in recent versions that older code still imports).
+const STDLIB_MODULES: &[&str] = &[
+    "__future__",
+    "__main__",
+    "abc",
+    "aifc",
+    "antigravity",
+    "argparse",
+    "array",
+    "ast",
+    "asynchat",
+    "asyncio",
+    "asyncore",
+    "atexit",
+    "audioop",
+    "base64",
+    "bdb",
+    "binascii",
+    "bisect",
+    "builtins",
+    "bz2",
+    "cProfile",
+    "calendar",
+    "cgi",
+    "cgitb",
+    "chunk",
+    "cmath",
+    "cmd",
+    "code",
+    "codecs",
+    "codeop",
+    "collections",
+    "colorsys",
+    "compileall",
+    "concurrent",
+    "configparser",
+    "contextlib",
+    "contextvars",
+    "copy",
+    "copyreg",
+    "crypt",
+    "csv",
+    "ctypes",
+    "curses",
+    "dataclasses",
+    "datetime",
+    "dbm",
+    "decimal",
+    "difflib",
+    "dis",
+    "distutils",
+    "doctest",
+    "email",
+    "encodings",
+    "ensurepip",
+    "enum",
+    "errno",
+    "faulthandler",
+    "fcntl",
+    "filecmp",
+    "fileinput",
+    "fnmatch",
+    "fractions",
+    "ftplib",
+    "functools",
+    "gc",
+    "genericpath",
+    "getopt",
+    "getpass",
+    "gettext",
+    "glob",
+    "graphlib",
+    "grp",
+    "gzip",
+    "hashlib",
+    "heapq",
+    "hmac",
+    "html",
+    "http",
+    "idlelib",
+    "imaplib",
+    "imghdr",
+    "imp",
+    "importlib",
+    "inspect",
+    "io",
+    "ipaddress",
+    "itertools",
+    "json",
+    "keyword",
+    "lib2to3",
+    "linecache",
+    "locale",
+    "logging",
+    "lzma",
+    "mailbox",
+    "mailcap",
+    "marshal",
+    "math",
+    "mimetypes",
+    "mmap",
+    "modulefinder",
+    "msilib",
+    "msvcrt",
+    "multiprocessing",
+    "netrc",
+    "nis",
+    "nntplib",
+    "ntpath",
+    "nturl2path",
+    "numbers",
+    "opcode",
+    "operator",
+    "optparse",
+    "os",
+    "ossaudiodev",
+    "pathlib",
+    "pdb",
+    "pickle",
+    "pickletools",
+    "pipes",
+    "pkgutil",
+    "platform",
+    "plistlib",
+    "poplib",
+    "posix",
+    "posixpath",
+    "pprint",
+    "profile",
+    "pstats",
+    "pty",
+    "pwd",
+    "py_compile",
+    "pyclbr",
+    "pydoc",
+    "pydoc_data",
+    "pyexpat",
+    "queue",
+    "quopri",
+    "random",
+    "re",
+    "readline",
+    "reprlib",
+    "resource",
+    "rlcompleter",
+    "runpy",
+    "sched",
+    "secrets",
+    "select",
+    "selectors",
+    "shelve",
+    "shlex",
+    "shutil",
+    "signal",
+    "site",
+    "smtpd",
+    "smtplib",
+    "sndhdr",
+    "socket",
+    "socketserver",
+    "spwd",
+    "sqlite3",
+    "sre_compile",
+    "sre_constants",
+    "sre_parse",
+    "ssl",
+    "stat",
+    "statistics",
+    "string",
+    "stringprep",
+    "struct",
+    "subprocess",
+    "sunau",
+    "symtable",
+    "sys",
+    "sysconfig",
+    "syslog",
+    "tabnanny",
+    "tarfile",
+    "telnetlib",
+    "tempfile",
+    "termios",
+    "textwrap",
+    "this",
+    "threading",
+    "time",
+    "timeit",
+    "tkinter",
+    "token",
+    "tokenize",
+    "tomllib",
+    "trace",
+    "traceback",
+    "tracemalloc",
+    "tty",
+    "turtle",
+    "turtledemo",
+    "types",
+    "typing",
+    "unicodedata",
+    "unittest",
+    "urllib",
+    "uu",
+    "uuid",
+    "venv",
+    "warnings",
+    "wave",
+    "weakref",
+    "webbrowser",
+    "winreg",
+    "winsound",
+    "wsgiref",
+    "xdrlib",
+    "xml",
+    "xmlrpc",
+    "zipapp",
+    "zipfile",
+    "zipimport",
+    "zlib",
+    "zoneinfo",
+];
+
+/// Distributions whose import names differ from their normalized distribution name,
+/// keyed by normalized distribution name.
+const IMPORT_NAMES: &[(&str, &[&str])] = &[
+    ("attrs", &["attr", "attrs"]),
+    ("beautifulsoup4", &["bs4"]),
+    ("discord-py", &["discord"]),
+    ("django-cors-headers", &["corsheaders"]),
+    ("djangorestframework", &["rest_framework"]),
+    ("faiss-cpu", &["faiss"]),
+    ("faiss-gpu", &["faiss"]),
+    ("google-api-python-client", &["googleapiclient"]),
+    ("grpcio", &["grpc"]),
+    ("mysqlclient", &["MySQLdb"]),
+    ("opencv-contrib-python", &["cv2"]),
+    ("opencv-python", &["cv2"]),
+    ("opencv-python-headless", &["cv2"]),
+    ("pillow", &["PIL"]),
+    ("protobuf", &["google"]),
+    ("psycopg-binary", &["psycopg"]),
+    ("psycopg2-binary", &["psycopg2"]),
+    ("pycryptodome", &["Crypto"]),
+    ("pycryptodomex", &["Cryptodome"]),
+    ("pygithub", &["github"]),
+    ("pyjwt", &["jwt"]),
+    ("pymongo", &["pymongo", "bson", "gridfs"]),
+    ("pymupdf", &["fitz"]),
+    ("pyopenssl", &["OpenSSL"]),
+    ("pyserial", &["serial"]),
+    ("python-dateutil", &["dateutil"]),
+    ("python-dotenv", &["dotenv"]),
+    ("python-jose", &["jose"]),
+    ("python-magic", &["magic"]),
+    ("python-multipart", &["multipart"]),
+    ("python-slugify", &["slugify"]),
+    ("pyusb", &["usb"]),
+    (
+        "pywin32",
+        &["win32api", "win32con", "win32com", "pywintypes"],
+    ),
+    ("pyyaml", &["yaml"]),
+    ("pyzmq", &["zmq"]),
+    ("ruamel-yaml", &["ruamel"]),
+    ("scikit-image", &["skimage"]),
+    ("scikit-learn", &["sklearn"]),
+    ("setuptools", &["setuptools", "pkg_resources"]),
+];
+
+/// Distributions that are run as tools or loaded as plugins rather than imported.
+/// They are never reported as unused.
+const TOOL_DISTRIBUTIONS: &[&str] = &[
+    "bandit",
+    "black",
+    "build",
+    "coverage",
+    "flake8",
+    "gunicorn",
+    "hatchling",
+    "ipython",
+    "isort",
+    "jupyter",
+    "mkdocs",
+    "mypy",
+    "nox",
+    "pip",
+    "pip-tools",
+    "pre-commit",
+    "pylint",
+    "pyright",
+    "pytest",
+    "ruff",
+    "sphinx",
+    "tox",
+    "twine",
+    "uvicorn",
+    "wheel",
+];
+
+/// Prefixes of plugin and stub distributions that are never imported directly.
+const TOOL_PREFIXES: &[&str] = &["types-", "pytest-", "flake8-", "sphinx-", "mkdocs-"];
+
+/// A third-party distribution declared as a dependency of the project.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dependency {
+    /// Distribution name as written (e.g. `PyYAML`).
+    pub name: String,
+    /// Optional dependency group or extra (`dev`, `test`), `None` for runtime dependencies.
+    pub group: Option<String>,
+    /// Manifest file declaring the dependency.
+    pub file: PathBuf,
+    /// Line number of the declaration.
+    pub line: usize,
+}
+
+/// A declared dependency that is never imported, or an imported package that is
+/// not declared.
+#[derive(Serialize, Clone, Debug)]
+pub struct DependencyFinding {
+    /// Distribution name (unused) or top-level import name (undeclared).
+    pub name: String,
+    /// Description of the issue.
+    pub message: String,
+    /// Unique rule identifier.
+    pub rule_id: String,
+    /// Manifest declaring the dependency, or file of the first import.
+    pub file: PathBuf,
+    /// Line number of the declaration or import.
+    pub line: usize,
+    /// Severity level.
+    pub severity: String,
+}
+
+/// Loads the dependencies declared by the project at `root`.
+///
+/// Manifests are read from the closest directory (starting at `root`) that has any:
+/// `pyproject.toml` (PEP 621 dependencies, optional dependencies and dependency groups,
+/// Poetry dependencies and groups, build requirements), `requirements*.txt` files (also
+/// in a `requirements/` directory), `setup.py` and `setup.cfg`.
+/// Returns `None` if the project declares its dependencies nowhere.
+pub fn load_dependencies(root: &Path) -> Option<Vec<Dependency>> {
+    let dir = root.ancestors().find(|dir| !manifests(dir).is_empty())?;
+
+    let mut dependencies = Vec::new();
+    for path in manifests(dir) {
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        match file_name.as_ref() {
+            "pyproject.toml" => dependencies.extend(parse_pyproject(&path, &content)),
+            "setup.py" => dependencies.extend(parse_setup_py(&path, &content)),
+            "setup.cfg" => dependencies.extend(parse_setup_cfg(&path, &content)),
+            _ => dependencies.extend(parse_requirements(&path, &content)),
+        }
+    }
+    Some(dependencies)
+}
+
+/// Compares declared dependencies to the top-level names imported by the project.
+///
+/// `used` holds every imported top-level name (including dynamic imports), and
+/// `imports` the import statements with their location, in report order.
+/// `first_party` holds the top-level names of the project's own modules.
+/// Returns the unused declared dependencies (`SKY-P001`) and the undeclared imported
+/// packages (`SKY-P002`), each reported once.
+pub fn check_dependencies(
+    dependencies: &[Dependency],
+    used: &HashSet<String>,
+    imports: &[(String, PathBuf, usize)],
+    first_party: &HashSet<String>,
+) -> (Vec<DependencyFinding>, Vec<DependencyFinding>) {
+    let used: HashSet<String> = used.iter().map(|name| name.to_lowercase()).collect();
+
+    // Import name -> declared, and the first declaration of every distribution.
+    let mut provided = HashSet::new();
+    let mut declarations: HashMap<String, &Dependency> = HashMap::new();
+    for dependency in dependencies {
+        let names = import_names(&dependency.name);
+        provided.extend(names.iter().map(|name| name.to_lowercase()));
+        declarations
+            .entry(normalize(&dependency.name))
+            .or_insert(dependency);
+    }
+
+    let mut unused = Vec::new();
+    for dependency in dependencies {
+        let normalized = normalize(&dependency.name);
+        let is_first = declarations
+            .get(&normalized)
+            .is_some_and(|first| std::ptr::eq(*first, dependency));
+        if !is_first
+            || dependency.group.as_deref() == Some(BUILD_SYSTEM_GROUP)
+            || is_tool(&normalized)
+            || import_names(&dependency.name)
+                .iter()
+                .any(|name| used.contains(&name.to_lowercase()))
+        {
+            continue;
+        }
+        let message = match &dependency.group {
+            Some(group) => format!(
+                "Dependency '{}' (group '{}') is declared but never imported",
+                dependency.name, group
+            ),
+            None => format!(
+                "Dependency '{}' is declared but never imported",
+                dependency.name
+            ),
+        };
+        unused.push(DependencyFinding {
+            name: dependency.name.clone(),
+            message,
+            rule_id: "SKY-P001".to_string(),
+            file: dependency.file.clone(),
+            line: dependency.line,
+            severity: "LOW".to_string(),
+        });
+    }
+
+    let mut reported = HashSet::new();
+    let mut undeclared = Vec::new();
+    for (name, file, line) in imports {
+        if name.is_empty()
+            || name.starts_with('_')
+            || is_stdlib_module(name)
+            || first_party.contains(name)
+            || provided.contains(&name.to_lowercase())
+            || !reported.insert(name.as_str())
+        {
+            continue;
+        }
+        undeclared.push(DependencyFinding {
+            name: name.clone(),
+            message: format!(
+                "Package '{}' is imported but not declared as a dependency",
+                name
+            ),
+            rule_id: "SKY-P002".to_string(),
+            file: file.clone(),
+            line: *line,
+            severity: "MEDIUM".to_string(),
+        });
+    }
+
+    (unused, undeclared)
+}
+
+/// Returns the top-level import names provided by a distribution.
+///
+/// Well-known mismatches (`PyYAML` provides `yaml`) come from a built-in table.
+/// Otherwise the normalized name is used with `_` for `-` (`typing-extensions` provides
+/// `typing_extensions`); namespace distributions (`zope.interface`, `google-cloud-storage`)
+/// also provide their namespace package.
+pub fn import_names(distribution: &str) -> Vec<String> {
+    let normalized = normalize(distribution);
+    if let Some((_, names)) = IMPORT_NAMES.iter().find(|(name, _)| *name == normalized) {
+        return names.iter().map(|name| name.to_string()).collect();
+    }
+
+    let mut names = vec![normalized.replace('-', "_")];
+    if let Some((namespace, _)) = distribution.split_once('.') {
+        names.push(namespace.to_lowercase());
+    }
+    if normalized.starts_with("google-") {
+        names.push("google".to_string());
+    }
+    names
+}
+
+/// Whether a top-level module name belongs to the standard library.
+pub fn is_stdlib_module(name: &str) -> bool {
+    STDLIB_MODULES.contains(&name)
+}
+
+/// Normalizes a distribution name as in PEP 503: lowercase, with runs of `-`, `_` and
+/// `.` replaced by a single `-`.
+fn normalize(name: &str) -> String {
+    let mut normalized = String::with_capacity(name.len());
+    for c in name.chars() {
+        if matches!(c, '-' | '_' | '.') {
+            if !normalized.ends_with('-') {
+                normalized.push('-');
+            }
+        } else {
+            normalized.push(c.to_ascii_lowercase());
+        }
+    }
+    normalized
+}
+
+/// Whether a (normalized) distribution is a tool, plugin or stub package.
+fn is_tool(normalized: &str) -> bool {
+    TOOL_DISTRIBUTIONS.contains(&normalized)
+        || TOOL_PREFIXES
+            .iter()
+            .any(|prefix| normalized.starts_with(prefix))
+        || normalized.ends_with("-stubs")
+}
+
+/// Lists the dependency manifests in a directory, in reading order.
+fn manifests(dir: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = ["pyproject.toml", "setup.py", "setup.cfg"]
+        .iter()
+        .map(|name| dir.join(name))
+        .filter(|path| path.is_file())
+        .collect();
+
+    let mut requirements: Vec<PathBuf> = fs::read_dir(dir)
+        .into_iter()
+        .chain(fs::read_dir(dir.join("requirements")))
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            let in_directory = path.parent().is_some_and(|p| p.ends_with("requirements"));
+            path.is_file()
+                && name.ends_with(".txt")
+                && (in_directory || name.starts_with("requirements"))
+        })
+        .collect();
+    requirements.sort();
+    paths.extend(requirements);
+    paths
+}
+
+/// Extracts the distribution name from a PEP 508 requirement (`flask[async]>=2.0`).
+fn requirement_name(requirement: &str) -> Option<&str> {
+    let requirement = requirement.trim();
+    let end = requirement
+        .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+        .unwrap_or(requirement.len());
+    let name = &requirement[..end];
+    name.starts_with(|c: char| c.is_ascii_alphanumeric())
+        .then_some(name)
+}
+
+/// Reads the dependencies declared in a `pyproject.toml`.
+fn parse_pyproject(path: &Path, content: &str) -> Vec<Dependency> {
+    let Ok(pyproject) = content.parse::<toml::Table>() else {
+        return Vec::new();
+    };
+
+    // (group, requirement strings) and (group, Poetry dependency tables).
+    let mut requirements: Vec<(Option<String>, &toml::Value)> = Vec::new();
+    let mut poetry_tables: Vec<(Option<String>, &toml::Value)> = Vec::new();
+
+    let project = pyproject.get("project");
+    if let Some(dependencies) = project.and_then(|p| p.get("dependencies")) {
+        requirements.push((None, dependencies));
+    }
+    if let Some(extras) = project
+        .and_then(|p| p.get("optional-dependencies"))
+        .and_then(|g| g.as_table())
+    {
+        requirements.extend(extras.iter().map(|(group, v)| (Some(group.clone()), v)));
+    }
+    if let Some(groups) = pyproject
+        .get("dependency-groups")
+        .and_then(|g| g.as_table())
+    {
+        requirements.extend(groups.iter().map(|(group, v)| (Some(group.clone()), v)));
+    }
+    if let Some(requires) = pyproject
+        .get("build-system")
+        .and_then(|b| b.get("requires"))
+    {
+        requirements.push((Some(BUILD_SYSTEM_GROUP.to_string()), requires));
+    }
+
+    if let Some(poetry) = pyproject.get("tool").and_then(|t| t.get("poetry")) {
+        if let Some(dependencies) = poetry.get("dependencies") {
+            poetry_tables.push((None, dependencies));
+        }
+        if let Some(dependencies) = poetry.get("dev-dependencies") {
+            poetry_tables.push((Some("dev".to_string()), dependencies));
+        }
+        if let Some(groups) = poetry.get("group").and_then(|g| g.as_table()) {
+            for (group, table) in groups {
+                if let Some(dependencies) = table.get("dependencies") {
+                    poetry_tables.push((Some(group.clone()), dependencies));
+                }
+            }
+        }
+    }
+
+    let mut names: Vec<(Option<String>, &str)> = Vec::new();
+    for (group, value) in requirements {
+        let entries = value.as_array().into_iter().flatten();
+        for requirement in entries.filter_map(toml::Value::as_str) {
+            if let Some(name) = requirement_name(requirement) {
+                names.push((group.clone(), name));
+            }
+        }
+    }
+    for (group, value) in poetry_tables {
+        let keys = value.as_table().into_iter().flat_map(|t| t.keys());
+        for name in keys.filter(|name| *name != "python") {
+            names.push((group.clone(), name.as_str()));
+        }
+    }
+
+    names
+        .into_iter()
+        .map(|(group, name)| Dependency {
+            name: name.to_string(),
+            group,
+            file: path.to_path_buf(),
+            line: declaration_line(content, name),
+        })
+        .collect()
+}
+
+/// Finds the line declaring a dependency in a TOML file: the first line that lists it
+/// as a string (`"flask>=2"`) or as a key (`flask = "^2"`). Defaults to 1.
+fn declaration_line(content: &str, name: &str) -> usize {
+    content
+        .lines()
+        .position(|line| {
+            let line = line.trim_start();
+            line.contains(&format!("\"{}", name))
+                || line.contains(&format!("'{}", name))
+                || line
+                    .strip_prefix(name)
+                    .is_some_and(|rest| rest.trim_start().starts_with('='))
+        })
+        .map_or(1, |i| i + 1)
+}
+
+/// Reads the dependencies listed in a requirements file.
+/// Options (`-r`, `-e`, `--hash`) and bare URLs are skipped.
+fn parse_requirements(path: &Path, content: &str) -> Vec<Dependency> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let line = line.split(" #").next().unwrap_or_default().trim();
+            if line.starts_with('#')
+                || line.starts_with('-')
+                || (line.contains("://") && !line.contains(" @ "))
+            {
+                return None;
+            }
+            Some(Dependency {
+                name: requirement_name(line)?.to_string(),
+                group: None,
+                file: path.to_path_buf(),
+                line: i + 1,
+            })
+        })
+        .collect()
+}
+
+/// Reads `install_requires` and `extras_require` from the `setup()` call of a `setup.py`.
+///
+/// Lists may be given inline or through a module-level variable
+/// (`install_requires=REQUIREMENTS`).
+fn parse_setup_py(path: &Path, content: &str) -> Vec<Dependency> {
+    let Ok(ast::Mod::Module(module)) = parse(content, Mode::Module, &path.to_string_lossy()) else {
+        return Vec::new();
+    };
+    let line_index = crate::utils::LineIndex::new(content);
+
+    // Module-level assignments, to resolve `install_requires=REQUIREMENTS`.
+    let mut variables: HashMap<&str, &Expr> = HashMap::new();
+    for stmt in &module.body {
+        if let Stmt::Assign(node) = stmt {
+            for target in &node.targets {
+                if let Expr::Name(name) = target {
+                    variables.insert(name.id.as_str(), &node.value);
+                }
+            }
+        }
+    }
+    let resolve = |expr: &'_ Expr| -> Option<&Expr> {
+        match expr {
+            Expr::Name(name) => variables.get(name.id.as_str()).copied(),
+            _ => None,
+        }
+    };
+
+    let mut dependencies = Vec::new();
+    let mut add_list = |expr: &Expr, group: Option<String>| {
+        let expr = resolve(expr).unwrap_or(expr);
+        let elements = match expr {
+            Expr::List(list) => &list.elts,
+            Expr::Tuple(tuple) => &tuple.elts,
+            _ => return,
+        };
+        for element in elements {
+            if let Expr::Constant(constant) = element {
+                if let Constant::Str(requirement) = &constant.value {
+                    if let Some(name) = requirement_name(requirement) {
+                        dependencies.push(Dependency {
+                            name: name.to_string(),
+                            group: group.clone(),
+                            file: path.to_path_buf(),
+                            line: line_index.line_index(constant.range.start()),
+                        });
+                    }
+                }
+            }
+        }
+    };
+
+    for call in module.body.iter().filter_map(setup_call) {
+        for keyword in &call.keywords {
+            match keyword.arg.as_deref() {
+                Some("install_requires") => add_list(&keyword.value, None),
+                Some("extras_require") => {
+                    let value = resolve(&keyword.value).unwrap_or(&keyword.value);
+                    if let Expr::Dict(dict) = value {
+                        for (key, list) in dict.keys.iter().zip(&dict.values) {
+                            let group = match key {
+                                Some(Expr::Constant(c)) => match &c.value {
+                                    Constant::Str(s) => Some(s.clone()),
+                                    _ => None,
+                                },
+                                _ => None,
+                            };
+                            add_list(list, group.or_else(|| Some("extras".to_string())));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    dependencies
+}
+
+/// Returns the `setup(...)` call made by a top-level statement, if any.
+fn setup_call(stmt: &Stmt) -> Option<&ast::ExprCall> {
+    let Stmt::Expr(node) = stmt else {
+        return None;
+    };
+    let Expr::Call(call) = &*node.value else {
+        return None;
+    };
+    let name = crate::visitor::dotted_name(&call.func)?;
+    (name == "setup" || name.ends_with(".setup")).then_some(call)
+}
+
+/// Reads `install_requires` from the `[options]` section and the extras from the
+/// `[options.extras_require]` section of a `setup.cfg`.
+fn parse_setup_cfg(path: &Path, content: &str) -> Vec<Dependency> {
+    let mut dependencies = Vec::new();
+    let mut section = String::new();
+    // Group of the key whose values are being read, if it lists requirements.
+    let mut group: Option<Option<String>> = None;
+
+    for (i, line) in content.lines().enumerate() {
+        let trimmed = line.split('#').next().unwrap_or_default().trim();
+        if let Some(name) = trimmed.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+            section = name.trim().to_string();
+            group = None;
+            continue;
+        }
+
+        // Keys start at the beginning of a line; values may continue on indented lines.
+        let value = if line.starts_with(char::is_whitespace) {
+            trimmed
+        } else {
+            let Some((key, value)) = trimmed.split_once('=') else {
+                group = None;
+                continue;
+            };
+            let key = key.trim();
+            group = match section.as_str() {
+                "options" if key == "install_requires" => Some(None),
+                "options.extras_require" => Some(Some(key.to_string())),
+                _ => None,
+            };
+            value.trim()
+        };
+
+        let Some(group) = &group else {
+            continue;
+        };
+        if let Some(name) = requirement_name(value) {
+            dependencies.push(Dependency {
+                name: name.to_string(),
+                group: group.clone(),
+                file: path.to_path_buf(),
+                line: i + 1,
+            });
+        }
+    }
+    dependencies
+}
@@ -0,0 +1,197 @@
+use crate::config::file_overrides;
+use anyhow::{bail, Context, Result};
+use ignore::overrides::{Override, OverrideBuilder};
+use ignore::{DirEntry, WalkBuilder};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Folders that are skipped unless named in `include_folders`: caches, virtual
+/// environments and build output (the Python version's `DEFAULT_EXCLUDE_FOLDERS`, plus
+/// other folders of installed or generated code).
+pub const DEFAULT_EXCLUDE_FOLDERS: &[&str] = &[
+    "__pycache__",
+    ".git",
+    ".pytest_cache",
+    ".mypy_cache",
+    ".ruff_cache",
+    ".tox",
+    ".nox",
+    "htmlcov",
+    ".coverage",
+    "build",
+    "dist",
+    "*.egg-info",
+    ".eggs",
+    "venv",
+    ".venv",
+    "node_modules",
+    "site-packages",
+];
+
+/// A Python file found under one of the analyzed paths.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceFile {
+    /// Path of the file, as reached from the analyzed path.
+    pub path: PathBuf,
+    /// Directory its module name is computed relative to: the analyzed directory, or
+    /// the directory of an analyzed file.
+    pub root: PathBuf,
+}
+
+/// Which files to analyze under the given paths.
+#[derive(Debug, Clone, Default)]
+pub struct Discovery {
+    /// Glob patterns (gitignore syntax) of files and directories to skip.
+    pub exclude: Vec<String>,
+    /// Glob patterns of the files to analyze. If not empty, other files are skipped.
+    pub include: Vec<String>,
+    /// Folders to analyze even though they are excluded by default or by `exclude`,
+    /// e.g. `build`.
+    pub include_folders: Vec<String>,
+}
+
+impl Discovery {
+    /// Finds the Python files under `paths`.
+    ///
+    /// Files ignored by `.gitignore` (also outside a git repository) or `.ignore` are
+    /// skipped, as are the default excluded folders. Paths given explicitly are always
+    /// analyzed. Symbolic links are followed; a file reached several times (through
+    /// links or overlapping paths) is analyzed once, and link loops are skipped.
+    ///
+    /// Fails if one of `paths` does not exist or cannot be read; unreadable entries
+    /// below them are skipped.
+    pub fn find_files(&self, paths: &[PathBuf]) -> Result<Vec<SourceFile>> {
+        let exclude: Vec<String> = self
+            .exclude
+            .iter()
+            .filter(|pattern| !self.include_folders.contains(pattern))
+            .cloned()
+            .collect();
+        let defaults = self.default_excludes()?;
+
+        // Canonical path -> index in `files` and rank of the kept path.
+        let mut seen: HashMap<PathBuf, (usize, (bool, usize, usize))> = HashMap::new();
+        let mut files = Vec::new();
+        for path in paths {
+            if !path.exists() {
+                bail!("path `{}` does not exist", path.display());
+            }
+            let root = if path.is_file() {
+                path.parent().unwrap_or(path)
+            } else {
+                path
+            };
+            let root_depth = fs::canonicalize(root)
+                .unwrap_or_else(|_| root.to_path_buf())
+                .components()
+                .count();
+            let overrides = file_overrides(path, &self.include, &exclude)?;
+            let defaults = defaults.clone();
+            let walker = WalkBuilder::new(path)
+                .hidden(false)
+                .require_git(false)
+                .follow_links(true)
+                .sort_by_file_path(|a, b| a.cmp(b))
+                .filter_entry(move |entry| {
+                    entry.depth() == 0 || !is_excluded(entry, &overrides, &defaults)
+                })
+                .build();
+            for entry in walker {
+                let entry = match entry {
+                    Ok(entry) => entry,
+                    // The analyzed path itself is unreadable.
+                    Err(err) if is_error_at(&err, path) => {
+                        return Err(err)
+                            .with_context(|| format!("cannot read `{}`", path.display()));
+                    }
+                    // Unreadable entries below it and symbolic link loops are skipped.
+                    Err(_) => continue,
+                };
+                let is_file = entry.file_type().is_some_and(|kind| kind.is_file());
+                if !is_file || entry.path().extension().is_none_or(|ext| ext != "py") {
+                    continue;
+                }
+                let canonical =
+                    fs::canonicalize(entry.path()).unwrap_or_else(|_| entry.path().to_path_buf());
+                // Of the paths reaching the same file, a path that is not a link itself
+                // is preferred, then one under the outermost analyzed path (`. pkg`
+                // keeps `pkg/a.py` as module `pkg.a`), then the shortest one, so a link
+                // back into the tree does not rename the modules it contains.
+                let rank = (
+                    entry.path_is_symlink(),
+                    root_depth,
+                    entry.path().components().count(),
+                );
+                let file = SourceFile {
+                    path: entry.into_path(),
+                    root: root.to_path_buf(),
+                };
+                match seen.get_mut(&canonical) {
+                    Some((i, best)) => {
+                        if rank < *best {
+                            *best = rank;
+                            files[*i] = file;
+                        }
+                    }
+                    None => {
+                        seen.insert(canonical, (files.len(), rank));
+                        files.push(file);
+                    }
+                }
+            }
+        }
+        Ok(files)
+    }
+
+    /// Builds the filter of the default excluded folders that are not included.
+    fn default_excludes(&self) -> Result<Override> {
+        let mut builder = OverrideBuilder::new("");
+        for folder in DEFAULT_EXCLUDE_FOLDERS {
+            if !self.include_folders.iter().any(|name| name == folder) {
+                builder
+                    .add(&format!("!{}", folder))
+                    .with_context(|| format!("invalid default exclude `{}`", folder))?;
+            }
+        }
+        Ok(builder.build()?)
+    }
+}
+
+/// Whether a walk error is about `path`.
+fn is_error_at(err: &ignore::Error, path: &Path) -> bool {
+    match err {
+        ignore::Error::WithPath { path: at, .. } => at == path,
+        ignore::Error::WithDepth { err, .. } => is_error_at(err, path),
+        _ => false,
+    }
+}
+
+/// Whether an entry below an analyzed path is skipped by the user patterns or, for a
+/// directory, by the default excludes.
+fn is_excluded(entry: &DirEntry, overrides: &Override, defaults: &Override) -> bool {
+    let is_dir = entry.file_type().is_some_and(|kind| kind.is_dir());
+    overrides.matched(entry.path(), is_dir).is_ignore()
+        || (is_dir && defaults.matched(entry.path(), true).is_ignore())
+}
+
+/// Returns the deepest directory containing all `roots`, or the current directory if
+/// they have none in common.
+pub fn common_root(roots: &[&Path]) -> PathBuf {
+    let Some((first, rest)) = roots.split_first() else {
+        return PathBuf::from(".");
+    };
+    let mut common = first.to_path_buf();
+    for root in rest {
+        while !root.starts_with(&common) {
+            if !common.pop() {
+                return PathBuf::from(".");
+            }
+        }
+    }
+    if common.as_os_str().is_empty() {
+        PathBuf::from(".")
+    } else {
+        common
+    }
+}
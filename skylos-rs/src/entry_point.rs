@@ -1,5 +1,7 @@
-use rustpython_ast::{Constant, Expr, ExprContext, Stmt};
+use rustpython_ast::{Constant, Expr, Stmt};
 use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
 
 /// Detects if `__name__ == "__main__"` blocks exist and extracts function calls from them.
 ///
@@ -26,6 +28,71 @@ pub fn detect_entry_point_calls(stmts: &[Stmt]) -> HashSet<String> {
     entry_point_calls
 }
 
+/// Whether a module has an `if __name__ == "__main__"` block, i.e. is meant to be run
+/// as a script.
+pub fn has_main_guard(stmts: &[Stmt]) -> bool {
+    stmts.iter().any(is_main_guard)
+}
+
+/// Returns the modules that provide the scripts and entry points declared in the
+/// `pyproject.toml` of the project at `root` (or of the closest parent directory that
+/// has one).
+///
+/// This reads `[project.scripts]`, `[project.gui-scripts]`, `[project.entry-points.*]`
+/// and `[tool.poetry.scripts]`. An entry such as `cli = "pkg.cli:main"` yields `pkg.cli`.
+pub fn script_modules(root: &Path) -> Vec<String> {
+    root.ancestors()
+        .map(|dir| dir.join("pyproject.toml"))
+        .find(|path| path.is_file())
+        .and_then(|path| fs::read_to_string(path).ok())
+        .map(|content| pyproject_script_modules(&content))
+        .unwrap_or_default()
+}
+
+/// Collects the modules referenced by the script tables of a `pyproject.toml`.
+fn pyproject_script_modules(content: &str) -> Vec<String> {
+    let Ok(pyproject) = content.parse::<toml::Table>() else {
+        return Vec::new();
+    };
+
+    let project = pyproject.get("project");
+    let mut tables: Vec<&toml::Value> = ["scripts", "gui-scripts"]
+        .iter()
+        .filter_map(|key| project.and_then(|p| p.get(key)))
+        .collect();
+    if let Some(groups) = project
+        .and_then(|p| p.get("entry-points"))
+        .and_then(toml::Value::as_table)
+    {
+        tables.extend(groups.values());
+    }
+    if let Some(scripts) = pyproject
+        .get("tool")
+        .and_then(|t| t.get("poetry"))
+        .and_then(|p| p.get("scripts"))
+    {
+        tables.push(scripts);
+    }
+
+    tables
+        .into_iter()
+        .filter_map(toml::Value::as_table)
+        .flat_map(|table| table.values())
+        .filter_map(toml::Value::as_str)
+        .map(|reference| {
+            // `module:attr [extra]` names an attribute of `module`.
+            let module = reference.split(':').next().unwrap_or(reference);
+            module
+                .split('[')
+                .next()
+                .unwrap_or(module)
+                .trim()
+                .to_string()
+        })
+        .filter(|module| !module.is_empty())
+        .collect()
+}
+
 /// Checks if this statement is an `if __name__ == "__main__"` guard.
 ///
 /// This looks for a specific AST pattern: an If statement where the test is a comparison.
@@ -197,6 +264,7 @@ def my_function():
         if let rustpython_ast::Mod::Module(module) = tree {
             let calls = detect_entry_point_calls(&module.body);
             assert_eq!(calls.len(), 0, "Should detect no entry point calls");
+            assert!(!has_main_guard(&module.body), "Should detect no main guard");
         }
     }
 
@@ -214,6 +282,35 @@ if "__main__" == __name__:
         if let rustpython_ast::Mod::Module(module) = tree {
             let calls = detect_entry_point_calls(&module.body);
             assert!(calls.contains("func"), "Should handle reversed comparison");
+            assert!(has_main_guard(&module.body), "Should detect main guard");
         }
     }
+
+    #[test]
+    fn test_pyproject_script_modules() {
+        let content = r#"
+[project]
+name = "app"
+
+[project.scripts]
+app = "app.cli:main"
+
+[project.gui-scripts]
+app-gui = "app.gui:run [gui]"
+
+[project.entry-points."app.plugins"]
+csv = "app.plugins.csv"
+
+[tool.poetry.scripts]
+legacy = "app.legacy:main"
+"#;
+
+        let mut modules = pyproject_script_modules(content);
+        modules.sort();
+        assert_eq!(
+            modules,
+            vec!["app.cli", "app.gui", "app.legacy", "app.plugins.csv"]
+        );
+        assert!(pyproject_script_modules("not [valid toml").is_empty());
+    }
 }
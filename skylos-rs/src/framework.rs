@@ -1,11 +1,11 @@
 use crate::utils::LineIndex;
-use rustpython_ast::{self as ast, Expr, Stmt};
+use rustpython_ast::{Expr, Stmt};
 use std::collections::HashSet;
 
-/// Lazy static initialization for known framework modules.
-/// These libraries are commonly used in Python web development and data processing.
-/// Code using these frameworks often has implicit usage patterns (e.g., dependency injection).
 lazy_static::lazy_static! {
+    /// Lazy static initialization for known framework modules.
+    /// These libraries are commonly used in Python web development and data processing.
+    /// Code using these frameworks often has implicit usage patterns (e.g., dependency injection).
     static ref FRAMEWORK_IMPORTS: HashSet<&'static str> = {
         let mut s = HashSet::new();
         s.insert("flask");
@@ -0,0 +1,202 @@
+use crate::analyzer::AnalysisResult;
+use crate::config::SEVERITIES;
+use std::collections::BTreeMap;
+
+/// Exit code of a run whose findings are within every threshold.
+pub const EXIT_SUCCESS: u8 = 0;
+/// Exit code of a run with findings over a threshold.
+pub const EXIT_FINDINGS: u8 = 1;
+/// Exit code of a run that failed: invalid options or configuration, unreadable or
+/// unparsable files, unreadable baseline, ...
+pub const EXIT_ERROR: u8 = 2;
+
+/// Finding categories, named as in the JSON output.
+pub const CATEGORIES: &[&str] = &[
+    "unused_functions",
+    "unused_imports",
+    "unused_classes",
+    "unused_variables",
+    "unused_parameters",
+    "unused_attributes",
+    "typing_only_imports",
+    "dead_modules",
+    "unused_dependencies",
+    "undeclared_dependencies",
+    "import_cycles",
+    "undefined_exports",
+    "unreachable_code",
+    "redefinitions",
+    "unused_suppressions",
+    "secrets",
+    "danger",
+    "quality",
+];
+
+/// Categories counted as dead code by `max_dead_code`.
+pub const DEAD_CODE_CATEGORIES: &[&str] = &[
+    "unused_functions",
+    "unused_imports",
+    "unused_classes",
+    "unused_variables",
+    "unused_parameters",
+    "unused_attributes",
+    "dead_modules",
+    "unreachable_code",
+];
+
+/// Thresholds above which the findings of a run fail it, e.g. in CI.
+/// With none set, every run passes.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Gate {
+    /// Severity (`LOW`, `MEDIUM`, `HIGH` or `CRITICAL`) at or above which any finding
+    /// fails the run.
+    pub fail_on: Option<String>,
+    /// Number of dead code findings (see `DEAD_CODE_CATEGORIES`) the run may have.
+    pub max_dead_code: Option<usize>,
+    /// Number of findings each category may have (category -> maximum).
+    pub max_findings: BTreeMap<String, usize>,
+}
+
+impl Gate {
+    /// Returns why the findings of `result` fail the run, or nothing if they pass.
+    pub fn check(&self, result: &AnalysisResult) -> Vec<String> {
+        let mut failures = Vec::new();
+        let counts = category_counts(result);
+
+        if let Some(fail_on) = &self.fail_on {
+            let min_rank = severity_rank(fail_on);
+            let count = severities(result)
+                .filter(|severity| severity_rank(severity) >= min_rank)
+                .count();
+            if count > 0 {
+                failures.push(format!(
+                    "{} finding(s) with severity {} or higher",
+                    count, fail_on
+                ));
+            }
+        }
+
+        if let Some(max) = self.max_dead_code {
+            let count: usize = counts
+                .iter()
+                .filter(|(category, _)| DEAD_CODE_CATEGORIES.contains(category))
+                .map(|(_, count)| count)
+                .sum();
+            if count > max {
+                failures.push(format!(
+                    "{} dead code finding(s), more than the maximum of {}",
+                    count, max
+                ));
+            }
+        }
+
+        for (category, count) in &counts {
+            match self.max_findings.get(*category) {
+                Some(&max) if *count > max => failures.push(format!(
+                    "{} {} finding(s), more than the maximum of {}",
+                    count, category, max
+                )),
+                _ => {}
+            }
+        }
+        failures
+    }
+}
+
+/// Returns the number of findings of every category, in the order of `CATEGORIES`.
+pub fn category_counts(result: &AnalysisResult) -> Vec<(&'static str, usize)> {
+    vec![
+        ("unused_functions", result.unused_functions.len()),
+        ("unused_imports", result.unused_imports.len()),
+        ("unused_classes", result.unused_classes.len()),
+        ("unused_variables", result.unused_variables.len()),
+        ("unused_parameters", result.unused_parameters.len()),
+        ("unused_attributes", result.unused_attributes.len()),
+        ("typing_only_imports", result.typing_only_imports.len()),
+        ("dead_modules", result.dead_modules.len()),
+        ("unused_dependencies", result.unused_dependencies.len()),
+        (
+            "undeclared_dependencies",
+            result.undeclared_dependencies.len(),
+        ),
+        ("import_cycles", result.import_cycles.len()),
+        ("undefined_exports", result.undefined_exports.len()),
+        ("unreachable_code", result.unreachable_code.len()),
+        ("redefinitions", result.redefinitions.len()),
+        ("unused_suppressions", result.unused_suppressions.len()),
+        ("secrets", result.secrets.len()),
+        ("danger", result.danger.len()),
+        ("quality", result.quality.len()),
+    ]
+}
+
+/// Parses a `CATEGORY=N` threshold given on the command line.
+pub fn parse_max_findings(value: &str) -> Result<(String, usize), String> {
+    let (category, max) = value
+        .split_once('=')
+        .ok_or_else(|| format!("expected CATEGORY=N, got `{}`", value))?;
+    let category = category.trim();
+    if !CATEGORIES.contains(&category) {
+        return Err(format!(
+            "unknown category `{}`, expected one of {}",
+            category,
+            CATEGORIES.join(", ")
+        ));
+    }
+    let max = max
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid maximum `{}` for `{}`", max, category))?;
+    Ok((category.to_string(), max))
+}
+
+/// Parses a severity given on the command line, in any case.
+pub fn parse_severity(value: &str) -> Result<String, String> {
+    let upper = value.to_uppercase();
+    if SEVERITIES.contains(&upper.as_str()) {
+        Ok(upper)
+    } else {
+        Err(format!(
+            "unknown severity `{}`, expected one of {}",
+            value,
+            SEVERITIES.join(", ")
+        ))
+    }
+}
+
+/// Position of a severity in `SEVERITIES`; unknown severities rank lowest.
+fn severity_rank(severity: &str) -> usize {
+    SEVERITIES
+        .iter()
+        .position(|s| s.eq_ignore_ascii_case(severity))
+        .map_or(0, |rank| rank + 1)
+}
+
+/// Severities of every finding that has one. Unused definitions have none.
+fn severities(result: &AnalysisResult) -> impl Iterator<Item = &str> {
+    let modules = result.dead_modules.iter().map(|m| &m.severity);
+    let dependencies = result
+        .unused_dependencies
+        .iter()
+        .chain(&result.undeclared_dependencies)
+        .map(|d| &d.severity);
+    let cycles = result.import_cycles.iter().map(|c| &c.severity);
+    let exports = result.undefined_exports.iter().map(|e| &e.severity);
+    let unreachable = result.unreachable_code.iter().map(|u| &u.severity);
+    let redefinitions = result.redefinitions.iter().map(|r| &r.severity);
+    let suppressions = result.unused_suppressions.iter().map(|s| &s.severity);
+    let secrets = result.secrets.iter().map(|s| &s.severity);
+    let danger = result.danger.iter().map(|d| &d.severity);
+    let quality = result.quality.iter().map(|q| &q.severity);
+    modules
+        .chain(dependencies)
+        .chain(cycles)
+        .chain(exports)
+        .chain(unreachable)
+        .chain(redefinitions)
+        .chain(suppressions)
+        .chain(secrets)
+        .chain(danger)
+        .chain(quality)
+        .map(String::as_str)
+}
@@ -0,0 +1,239 @@
+use crate::visitor::ImportContext;
+use serde::Serialize;
+use serde_json::json;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Edge kind of a reference between definitions.
+pub const REFERENCE_EDGE: &str = "reference";
+
+/// A graph of modules or definitions, ready to be rendered.
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct Graph {
+    /// The nodes, in output order.
+    pub nodes: Vec<GraphNode>,
+    /// The edges, as indices into `nodes`.
+    pub edges: Vec<GraphEdge>,
+}
+
+/// A module or definition in a graph.
+#[derive(Serialize, Clone, Debug)]
+pub struct GraphNode {
+    /// Dotted name of the module or definition.
+    pub id: String,
+    /// File of the node, `None` for a node collapsed from several files.
+    pub file: Option<PathBuf>,
+    /// Whether the analyzer found the node dead.
+    pub dead: bool,
+}
+
+/// An edge between two nodes of a graph.
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct GraphEdge {
+    /// Index of the source node.
+    pub from: usize,
+    /// Index of the target node.
+    pub to: usize,
+    /// The import context of an import (`module-level`, `function-local` or
+    /// `type-checking`), or `reference` for a reference between definitions.
+    pub kind: String,
+}
+
+/// Output format of a graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum GraphFormat {
+    /// Graphviz DOT.
+    Dot,
+    /// JSON adjacency lists.
+    Json,
+    /// Mermaid flowchart.
+    Mermaid,
+}
+
+impl Graph {
+    /// Adds a node and returns its index.
+    pub fn add_node(&mut self, id: String, file: Option<PathBuf>, dead: bool) -> usize {
+        self.nodes.push(GraphNode { id, file, dead });
+        self.nodes.len() - 1
+    }
+
+    /// Adds an edge between two nodes.
+    pub fn add_edge(&mut self, from: usize, to: usize, kind: &str) {
+        self.edges.push(GraphEdge {
+            from,
+            to,
+            kind: kind.to_string(),
+        });
+    }
+
+    /// Collapses nodes to their first `depth` name components (e.g. `app.models` for
+    /// `app.models.user` at depth 2).
+    ///
+    /// A collapsed node is dead only if all of its nodes are. Edges inside a collapsed
+    /// node are dropped, and parallel edges are merged, keeping the strongest kind.
+    pub fn collapse(&self, depth: usize) -> Graph {
+        let depth = depth.max(1);
+        let mut collapsed = Graph::default();
+        let mut index: HashMap<String, usize> = HashMap::new();
+        let mut mapping = Vec::with_capacity(self.nodes.len());
+        for node in &self.nodes {
+            let id: Vec<&str> = node.id.split('.').take(depth).collect();
+            let id = id.join(".");
+            let i = match index.get(&id) {
+                Some(&i) => {
+                    let existing = &mut collapsed.nodes[i];
+                    existing.dead &= node.dead;
+                    if existing.file != node.file {
+                        existing.file = None;
+                    }
+                    i
+                }
+                None => {
+                    let i = collapsed.add_node(id.clone(), node.file.clone(), node.dead);
+                    index.insert(id, i);
+                    i
+                }
+            };
+            mapping.push(i);
+        }
+
+        let mut edges: BTreeMap<(usize, usize), &str> = BTreeMap::new();
+        for edge in &self.edges {
+            let (from, to) = (mapping[edge.from], mapping[edge.to]);
+            if from == to {
+                continue;
+            }
+            let kind = edges.entry((from, to)).or_insert(&edge.kind);
+            if edge_rank(&edge.kind) < edge_rank(kind) {
+                *kind = &edge.kind;
+            }
+        }
+        for ((from, to), kind) in edges {
+            collapsed.add_edge(from, to, kind);
+        }
+        collapsed
+    }
+
+    /// Keeps only the nodes whose file is under `prefix`, and the edges between them.
+    /// Collapsed nodes without a single file are kept.
+    pub fn filter(&self, prefix: &Path) -> Graph {
+        let mut filtered = Graph::default();
+        let mapping: Vec<Option<usize>> = self
+            .nodes
+            .iter()
+            .map(|node| {
+                let keep = node
+                    .file
+                    .as_ref()
+                    .is_none_or(|file| file.starts_with(prefix));
+                keep.then(|| filtered.add_node(node.id.clone(), node.file.clone(), node.dead))
+            })
+            .collect();
+        for edge in &self.edges {
+            if let (Some(from), Some(to)) = (mapping[edge.from], mapping[edge.to]) {
+                filtered.add_edge(from, to, &edge.kind);
+            }
+        }
+        filtered
+    }
+
+    /// Renders the graph in the given format. Dead nodes are highlighted if `highlight_dead`
+    /// is set (the JSON format always includes the `dead` flag).
+    pub fn render(&self, format: GraphFormat, highlight_dead: bool) -> String {
+        match format {
+            GraphFormat::Dot => self.to_dot(highlight_dead),
+            GraphFormat::Json => self.to_json(),
+            GraphFormat::Mermaid => self.to_mermaid(highlight_dead),
+        }
+    }
+
+    /// Renders the graph as Graphviz DOT. Deferred imports are dashed.
+    fn to_dot(&self, highlight_dead: bool) -> String {
+        let mut out = String::from("digraph skylos {\n    rankdir=LR;\n    node [shape=box];\n");
+        for node in &self.nodes {
+            if highlight_dead && node.dead {
+                out.push_str(&format!(
+                    "    \"{}\" [style=filled, fillcolor=\"#ffcccc\", color=\"#cc0000\"];\n",
+                    node.id
+                ));
+            } else {
+                out.push_str(&format!("    \"{}\";\n", node.id));
+            }
+        }
+        for edge in &self.edges {
+            let style = if is_deferred(&edge.kind) {
+                " [style=dashed]"
+            } else {
+                ""
+            };
+            out.push_str(&format!(
+                "    \"{}\" -> \"{}\"{};\n",
+                self.nodes[edge.from].id, self.nodes[edge.to].id, style
+            ));
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Renders the graph as JSON: one entry per node with its outgoing edges.
+    fn to_json(&self) -> String {
+        let mut adjacency: Vec<Vec<serde_json::Value>> = vec![Vec::new(); self.nodes.len()];
+        for edge in &self.edges {
+            adjacency[edge.from].push(json!({
+                "to": self.nodes[edge.to].id,
+                "kind": edge.kind,
+            }));
+        }
+        let nodes: Vec<serde_json::Value> = self
+            .nodes
+            .iter()
+            .zip(adjacency)
+            .map(|(node, edges)| {
+                json!({
+                    "id": node.id,
+                    "file": node.file,
+                    "dead": node.dead,
+                    "edges": edges,
+                })
+            })
+            .collect();
+        serde_json::to_string_pretty(&json!({ "nodes": nodes })).unwrap_or_default()
+    }
+
+    /// Renders the graph as a Mermaid flowchart. Deferred imports are dotted.
+    fn to_mermaid(&self, highlight_dead: bool) -> String {
+        let mut out = String::from("graph LR\n");
+        for (i, node) in self.nodes.iter().enumerate() {
+            out.push_str(&format!("    n{}[\"{}\"]\n", i, node.id));
+        }
+        for edge in &self.edges {
+            let arrow = if is_deferred(&edge.kind) {
+                "-.->"
+            } else {
+                "-->"
+            };
+            out.push_str(&format!("    n{} {} n{}\n", edge.from, arrow, edge.to));
+        }
+        if highlight_dead && self.nodes.iter().any(|node| node.dead) {
+            out.push_str("    classDef dead fill:#ffcccc,stroke:#cc0000\n");
+            for (i, _) in self.nodes.iter().enumerate().filter(|(_, node)| node.dead) {
+                out.push_str(&format!("    class n{} dead\n", i));
+            }
+        }
+        out
+    }
+}
+
+/// Whether an edge is an import that does not run on import.
+fn is_deferred(kind: &str) -> bool {
+    edge_rank(kind) > 0
+}
+
+/// Orders edge kinds by strength: references and imports that run on import first,
+/// then function-local and `TYPE_CHECKING` imports.
+fn edge_rank(kind: &str) -> u8 {
+    [ImportContext::Function, ImportContext::TypeChecking]
+        .iter()
+        .position(|context| context.as_str() == kind)
+        .map_or(0, |i| i as u8 + 1)
+}
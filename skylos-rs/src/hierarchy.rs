@@ -0,0 +1,112 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Project-wide class hierarchy built after all files have been visited.
+///
+/// Classes are keyed by their fully qualified name, and their bases are resolved to
+/// fully qualified names as well (through imports where needed). Bases defined outside
+/// the project are kept, but have no members of their own.
+///
+/// This lets us relate a method or attribute to the base class members it overrides, and
+/// resolve `Child.method` to `Base.method` when the method is only inherited.
+#[derive(Default)]
+pub struct ClassHierarchy {
+    /// Class name -> resolved base class names, in declaration order.
+    bases: HashMap<String, Vec<String>>,
+    /// Class name -> names of the methods and attributes it defines.
+    members: HashMap<String, HashSet<String>>,
+}
+
+impl ClassHierarchy {
+    /// Creates an empty hierarchy.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a class with its resolved base classes.
+    pub fn add_class(&mut self, class: &str, bases: Vec<String>) {
+        self.bases.insert(class.to_string(), bases);
+    }
+
+    /// Registers a method or attribute defined directly in a class.
+    pub fn add_member(&mut self, class: &str, member: &str) {
+        self.members
+            .entry(class.to_string())
+            .or_default()
+            .insert(member.to_string());
+    }
+
+    /// Whether the name is a known project class.
+    pub fn is_class(&self, name: &str) -> bool {
+        self.bases.contains_key(name)
+    }
+
+    /// Returns all ancestors of a class, nearest first.
+    ///
+    /// Bases are walked breadth-first, left to right, which matches the method
+    /// resolution order for the common single and simple multiple inheritance cases.
+    /// Inheritance cycles (which Python rejects) are ignored.
+    pub fn ancestors(&self, class: &str) -> Vec<&str> {
+        let mut ancestors = Vec::new();
+        let mut seen = HashSet::from([class]);
+        let mut queue: VecDeque<&str> = self
+            .bases
+            .get(class)
+            .into_iter()
+            .flatten()
+            .map(String::as_str)
+            .collect();
+
+        while let Some(base) = queue.pop_front() {
+            if !seen.insert(base) {
+                continue;
+            }
+            ancestors.push(base);
+            queue.extend(
+                self.bases
+                    .get(base)
+                    .into_iter()
+                    .flatten()
+                    .map(String::as_str),
+            );
+        }
+        ancestors
+    }
+
+    /// Returns the ancestors of a class that are defined outside the project (e.g.
+    /// `ast.NodeVisitor` or `dict`), whose members we do not know.
+    pub fn external_ancestors(&self, class: &str) -> Vec<&str> {
+        self.ancestors(class)
+            .into_iter()
+            .filter(|ancestor| !self.is_class(ancestor))
+            .collect()
+    }
+
+    /// Returns the qualified names of the base class members overridden by
+    /// `class.member`, nearest first.
+    pub fn overridden(&self, class: &str, member: &str) -> Vec<String> {
+        self.ancestors(class)
+            .into_iter()
+            .filter(|ancestor| self.defines(ancestor, member))
+            .map(|ancestor| format!("{}.{}", ancestor, member))
+            .collect()
+    }
+
+    /// Resolves a reference to an inherited method or attribute.
+    ///
+    /// For `pkg.Child.method`, where `pkg.Child` is a project class that does not define
+    /// `method` itself, returns the member of the nearest ancestor that does.
+    pub fn resolve_inherited(&self, name: &str) -> Option<String> {
+        let (class, member) = name.rsplit_once('.')?;
+        if !self.is_class(class) || self.defines(class, member) {
+            return None;
+        }
+        self.overridden(class, member).into_iter().next()
+    }
+
+    /// Whether a class defines a member itself.
+    fn defines(&self, class: &str, member: &str) -> bool {
+        self.members
+            .get(class)
+            .is_some_and(|members| members.contains(member))
+    }
+}
@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// An interned string: a small index that is cheap to copy, hash and compare.
+/// Symbols are only meaningful for the `Interner` that created them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Symbol(u32);
+
+impl Symbol {
+    /// Returns the index of the symbol in its interner.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Identifies an analyzed file: its index in the list of files of the analysis.
+/// Definitions refer to their file by ID rather than each holding a copy of its path.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FileId(u32);
+
+impl FileId {
+    /// Returns the ID of the file at `index` in the file list.
+    pub fn new(index: usize) -> Self {
+        FileId(index as u32)
+    }
+
+    /// Returns the index of the file in the file list.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Stores each distinct string once and identifies it by a `Symbol`.
+///
+/// Names are referenced many times per file (every `obj.attr` records three names),
+/// so interning them keeps a single allocation per distinct name.
+#[derive(Default, Clone, Debug)]
+pub struct Interner {
+    /// String -> symbol. The keys share their allocation with `names`.
+    symbols: HashMap<Arc<str>, Symbol>,
+    /// Strings, indexed by symbol.
+    names: Vec<Arc<str>>,
+}
+
+impl Interner {
+    /// Creates an empty interner.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the symbol of a string, interning it if it is new.
+    pub fn intern(&mut self, name: &str) -> Symbol {
+        if let Some(&symbol) = self.symbols.get(name) {
+            return symbol;
+        }
+        let symbol = Symbol(self.names.len() as u32);
+        let name: Arc<str> = Arc::from(name);
+        self.names.push(name.clone());
+        self.symbols.insert(name, symbol);
+        symbol
+    }
+
+    /// Returns the symbol of a string, if it was interned.
+    pub fn get(&self, name: &str) -> Option<Symbol> {
+        self.symbols.get(name).copied()
+    }
+
+    /// Returns the string of a symbol.
+    pub fn resolve(&self, symbol: Symbol) -> &str {
+        &self.names[symbol.index()]
+    }
+
+    /// Returns the number of distinct strings.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Whether no string was interned.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Iterates over the symbols and their strings, in interning order.
+    pub fn iter(&self) -> impl Iterator<Item = (Symbol, &str)> {
+        self.names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (Symbol(i as u32), &**name))
+    }
+}
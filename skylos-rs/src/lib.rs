@@ -28,3 +28,63 @@ pub mod utils;
 /// Module defining the entry point logic.
 /// This handles the integration with Python's setuptools/entry_points ecosystem if needed.
 pub mod entry_point;
+
+/// Module containing the project-wide symbol table.
+/// This resolves import aliases across files to the definitions they refer to.
+pub mod symbols;
+
+/// Module containing the reachability analysis.
+/// This walks the reference graph from entry roots to find dead subgraphs.
+pub mod reachability;
+
+/// Module containing the project-wide class hierarchy.
+/// This relates methods to the base class methods they override.
+pub mod hierarchy;
+
+/// Module containing the control-flow pass over function bodies.
+/// This finds statements and `except` clauses that can never run.
+pub mod unreachable;
+
+/// Module containing whitelist support.
+/// This loads names declared as used and generates whitelists from findings.
+pub mod whitelist;
+
+/// Module containing the third-party dependency report.
+/// This compares the dependencies declared in project manifests to the imported packages.
+pub mod dependencies;
+
+/// Module containing the import cycle detection.
+/// This finds cycles in the import graph between first-party modules.
+pub mod cycles;
+
+/// Module containing graph export.
+/// This renders module and symbol graphs as Graphviz DOT, JSON or Mermaid.
+pub mod graph;
+
+/// Module containing configuration file support.
+/// This reads `.skylos.toml` and the `[tool.skylos]` table of `pyproject.toml`.
+pub mod config;
+
+/// Module containing file discovery.
+/// This finds the Python files to analyze, honoring ignore files and excludes.
+pub mod discovery;
+
+/// Module containing suppression pragmas.
+/// This reads `# skylos: ignore` comments and the lines and rules they apply to.
+pub mod suppression;
+
+/// Module containing baseline files.
+/// This records accepted findings so that only new ones are reported.
+pub mod baseline;
+
+/// Module containing failure thresholds.
+/// This decides whether the findings of a run fail it, and with which exit code.
+pub mod gate;
+
+/// Module containing string interning and file IDs.
+/// This stores each distinct name once and refers to it by a compact symbol.
+pub mod interner;
+
+/// Module containing lexical scope tracking.
+/// This is used to resolve local names and detect unused variables.
+pub mod scope;
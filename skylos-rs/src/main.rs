@@ -1,48 +1,140 @@
 pub mod analyzer;
+pub mod baseline;
+pub mod config;
+pub mod cycles;
+pub mod dependencies;
+pub mod discovery;
 pub mod entry_point;
 pub mod framework;
+pub mod gate;
+pub mod graph;
+pub mod hierarchy;
+pub mod interner;
+pub mod reachability;
 pub mod rules;
+pub mod scope;
+pub mod suppression;
+pub mod symbols;
 pub mod test_utils;
+pub mod unreachable;
 pub mod utils;
 pub mod visitor;
+pub mod whitelist;
 
-use crate::analyzer::Skylos;
+use crate::analyzer::{AnalysisResult, Skylos};
+use crate::baseline::Baseline;
+use crate::config::Config;
+use crate::gate::{Gate, EXIT_ERROR, EXIT_FINDINGS, EXIT_SUCCESS};
+use crate::graph::GraphFormat;
+use crate::visitor::Definition;
 use anyhow::Result;
-use clap::Parser;
+use clap::{Args, Parser, Subcommand};
 use colored::*;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+/// Default confidence threshold.
+const DEFAULT_CONFIDENCE: u8 = 60;
 
 /// Command line interface configuration using `clap`.
 /// This struct defines the arguments and flags accepted by the program.
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+#[command(
+    after_help = "Exit status: 0 if the findings are within every threshold, 1 if they exceed one, 2 if a file could not be read or parsed or the run failed."
+)]
 struct Cli {
-    /// Path to the Python project to analyze.
-    /// This is the root directory where the scan will begin.
-    /// We need this to know which files to read and parse.
-    path: PathBuf,
+    /// Subcommand to run instead of the analysis report.
+    #[command(subcommand)]
+    command: Option<Command>,
 
-    /// Confidence threshold (0-100).
-    /// Only findings with confidence higher than this value will be reported.
-    /// This helps in filtering out false positives.
-    #[arg(short, long, default_value_t = 60)]
-    confidence: u8,
+    /// Paths of the Python project to analyze: directories or files.
+    /// These are the roots where the scan will begin.
+    /// We need them to know which files to read and parse.
+    #[arg(required = true)]
+    paths: Vec<PathBuf>,
+
+    /// Options shared with the subcommands.
+    #[command(flatten)]
+    analysis: AnalysisArgs,
 
     /// Scan for API keys/secrets.
     /// If true, the analyzer will look for hardcoded secrets like API keys and passwords.
-    #[arg(long)]
+    #[arg(long, overrides_with = "no_secrets")]
     secrets: bool,
 
+    /// Do not scan for secrets, even if the configuration file enables it.
+    #[arg(long = "no-secrets", overrides_with = "secrets")]
+    no_secrets: bool,
+
     /// Scan for dangerous code.
     /// If true, the analyzer will look for dangerous patterns like eval(), exec(), etc.
-    #[arg(long)]
+    #[arg(long, overrides_with = "no_danger")]
     danger: bool,
 
+    /// Do not scan for dangerous code, even if the configuration file enables it.
+    #[arg(long = "no-danger", overrides_with = "danger")]
+    no_danger: bool,
+
     /// Scan for code quality issues.
     /// If true, the analyzer will check for code quality problems such as complex functions.
-    #[arg(long)]
+    #[arg(long, overrides_with = "no_quality")]
     quality: bool,
 
+    /// Do not scan for code quality issues, even if the configuration file enables it.
+    #[arg(long = "no-quality", overrides_with = "quality")]
+    no_quality: bool,
+
+    /// Print a whitelist covering every reported unused definition instead of the report.
+    /// Save it to a file and pass it back with `--whitelist` to accept the current state
+    /// of a legacy codebase.
+    #[arg(long = "make-whitelist")]
+    make_whitelist: bool,
+
+    /// Report the suppression pragmas (`# skylos: ignore`, `# pragma: no skylos`, ...)
+    /// that did not suppress any finding. Pragmas that may apply to a rule which did
+    /// not run (e.g. `# skylos: ignore[SKY-D002]` without `--danger`) are left out.
+    #[arg(long = "unused-suppressions")]
+    unused_suppressions: bool,
+
+    /// Delete the unused suppression pragmas from the files.
+    #[arg(long, requires = "unused_suppressions")]
+    fix: bool,
+
+    /// Record every current finding in a baseline file instead of printing the report.
+    #[arg(
+        long = "write-baseline",
+        value_name = "FILE",
+        conflicts_with = "baseline"
+    )]
+    write_baseline: Option<PathBuf>,
+
+    /// Only report findings that are not in this baseline file, and list the baseline
+    /// entries that no longer occur.
+    #[arg(long, value_name = "FILE")]
+    baseline: Option<PathBuf>,
+
+    /// Exit with status 1 if any finding has this severity or a higher one
+    /// (LOW, MEDIUM, HIGH or CRITICAL).
+    #[arg(long = "fail-on", value_name = "SEVERITY", value_parser = gate::parse_severity)]
+    fail_on: Option<String>,
+
+    /// Exit with status 1 if there are more than N dead code findings: unused
+    /// definitions, dead modules and unreachable code.
+    #[arg(long = "max-dead-code", value_name = "N")]
+    max_dead_code: Option<usize>,
+
+    /// Exit with status 1 if a category has more than N findings, e.g.
+    /// `unused_imports=0`. Categories are named as in the JSON output.
+    /// Can be given multiple times.
+    #[arg(
+        long = "max-findings",
+        value_name = "CATEGORY=N",
+        value_parser = gate::parse_max_findings
+    )]
+    max_findings: Vec<(String, usize)>,
+
     /// Output raw JSON.
     /// If true, the output will be in JSON format for machine parsing.
     /// This is useful for integrating with other tools or CI/CD pipelines.
@@ -50,34 +142,174 @@ struct Cli {
     json: bool,
 }
 
+/// Options of the analysis, shared by the report and the subcommands.
+/// They take precedence over the configuration file.
+#[derive(Args)]
+struct AnalysisArgs {
+    /// Configuration file to use instead of the discovered one.
+    /// By default, `.skylos.toml` or the `[tool.skylos]` table of `pyproject.toml` is
+    /// looked up from the first analyzed path upwards.
+    #[arg(long, value_name = "FILE")]
+    config: Option<PathBuf>,
+
+    /// Confidence threshold (0-100), 60 by default.
+    /// Only findings with confidence higher than this value will be reported.
+    /// This helps in filtering out false positives.
+    #[arg(short, long)]
+    confidence: Option<u8>,
+
+    /// Qualified name (or prefix) of a public API definition, e.g. `mypkg.api`.
+    /// Public API definitions are treated as used and as roots of the reachability
+    /// analysis. Can be given multiple times.
+    #[arg(long = "public-api", value_name = "NAME")]
+    public_api: Vec<String>,
+
+    /// Whitelist file declaring names that are used, e.g. by a framework.
+    /// Either a Python file (`whitelist.py`, vulture style) or a plain list with one
+    /// simple or qualified name per line. Can be given multiple times.
+    #[arg(long, value_name = "FILE")]
+    whitelist: Vec<PathBuf>,
+
+    /// Glob pattern (gitignore syntax) of files or directories to skip, e.g. `migrations`.
+    /// Can be given multiple times.
+    #[arg(long, value_name = "PATTERN")]
+    exclude: Vec<String>,
+
+    /// Glob pattern of the files to analyze, e.g. `src/**`; other files are skipped.
+    /// Can be given multiple times.
+    #[arg(long, value_name = "PATTERN")]
+    include: Vec<String>,
+
+    /// Folder to analyze even though it is excluded by default, e.g. `build` or `venv`.
+    /// Can be given multiple times.
+    #[arg(long = "include-folder", value_name = "NAME")]
+    include_folder: Vec<String>,
+}
+
+/// Subcommands of the program.
+#[derive(Subcommand)]
+enum Command {
+    /// Print the module import graph, or the symbol reference graph, of a project.
+    Graph(GraphArgs),
+}
+
+/// Arguments of the `graph` subcommand.
+#[derive(Args)]
+struct GraphArgs {
+    /// Paths of the Python project to analyze: directories or files.
+    #[arg(required = true)]
+    paths: Vec<PathBuf>,
+
+    /// Output format.
+    #[arg(long, value_enum, default_value_t = GraphFormat::Dot)]
+    format: GraphFormat,
+
+    /// Print the reference graph between functions, methods and classes instead of
+    /// the import graph between modules.
+    #[arg(long)]
+    symbols: bool,
+
+    /// Collapse nodes to their first N dotted name components, e.g. `--depth 1` shows
+    /// one node per top-level package.
+    #[arg(long, value_name = "N")]
+    depth: Option<usize>,
+
+    /// Only keep nodes whose file is under this path.
+    #[arg(long = "path-prefix", value_name = "PATH")]
+    path_prefix: Option<PathBuf>,
+
+    /// Highlight the modules and definitions the analyzer reports as dead.
+    #[arg(long = "highlight-dead")]
+    highlight_dead: bool,
+
+    /// Options of the analysis that finds dead nodes.
+    #[command(flatten)]
+    analysis: AnalysisArgs,
+}
+
 /// Main entry point of the application.
 ///
+/// Runs the program and turns its outcome into the exit status: errors are printed
+/// and exit with `EXIT_ERROR`.
+fn main() -> ExitCode {
+    match run() {
+        Ok(code) => ExitCode::from(code),
+        Err(error) => {
+            eprintln!("Error: {:?}", error);
+            ExitCode::from(EXIT_ERROR)
+        }
+    }
+}
+
+/// Runs the program and returns its exit code.
+///
 /// This function handles argument parsing, initialization of the analyzer,
 /// execution of the analysis, and output formatting.
-fn main() -> Result<()> {
+fn run() -> Result<u8> {
     // Parse command line arguments using the Cli struct definition.
     // This allows users to configure the analysis via CLI flags.
     let cli = Cli::parse();
+    if let Some(Command::Graph(args)) = &cli.command {
+        print_graph(args)?;
+        return Ok(EXIT_SUCCESS);
+    }
+    // Clap requires a path when no subcommand is given.
+    let paths = &cli.paths;
+
+    // Initialize the Skylos analyzer with the configuration file and the CLI flags.
+    // The flags for the different types of checks override the file in both directions.
+    // This sets up the analyzer state before running on files.
+    let (mut skylos, mut gate, config_file) = configure(&paths[0], &cli.analysis)?;
+    skylos.enable_secrets =
+        family_flag(cli.secrets, cli.no_secrets).unwrap_or(skylos.enable_secrets);
+    skylos.enable_danger = family_flag(cli.danger, cli.no_danger).unwrap_or(skylos.enable_danger);
+    skylos.enable_quality =
+        family_flag(cli.quality, cli.no_quality).unwrap_or(skylos.enable_quality);
+    skylos.report_unused_suppressions = cli.unused_suppressions;
+    gate.fail_on = cli.fail_on.clone().or(gate.fail_on);
+    gate.max_dead_code = cli.max_dead_code.or(gate.max_dead_code);
+    gate.max_findings.extend(cli.max_findings.iter().cloned());
 
     // If JSON output is not requested, print a friendly message indicating the start of analysis.
     // This gives immediate feedback to the user that the process is running.
-    if !cli.json {
-        println!("Analyzing path: {:?}", cli.path);
+    if !cli.json && !cli.make_whitelist {
+        for path in paths {
+            println!("Analyzing path: {:?}", path);
+        }
+        if let Some(file) = &config_file {
+            println!("Using configuration: {}", file.display());
+        }
     }
 
-    // Initialize the Skylos analyzer with the configuration from CLI.
-    // We pass the confidence threshold and boolean flags for different types of checks.
-    // This sets up the analyzer state before running on files.
-    let skylos = Skylos::new(cli.confidence, cli.secrets, cli.danger, cli.quality);
-
-    // Run the analysis on the provided path.
-    // This traverses the directory, parses Python files, and applies rules.
+    // Run the analysis on the provided paths.
+    // This traverses the directories, parses Python files, and applies rules.
     // It returns a Result containing the AnalysisResult struct or an error.
     // We propagate any error with `?`.
-    let result = skylos.analyze(&cli.path)?;
+    let mut result = skylos.analyze_paths(paths)?;
+    if cli.fix {
+        let removed = suppression::remove_unused_suppressions(&result.unused_suppressions)?;
+        eprintln!("Removed {} unused suppression(s)", removed);
+    }
 
-    // Check if JSON output was requested.
-    if cli.json {
+    // Accept the current findings, or drop the accepted ones.
+    if let Some(file) = &cli.write_baseline {
+        let baseline = Baseline::from_result(&result, file);
+        baseline.save(file)?;
+        eprintln!(
+            "Wrote {} finding(s) to baseline {}",
+            baseline.findings.len(),
+            file.display()
+        );
+        return Ok(exit_code(&result, &Gate::default()));
+    }
+    if let Some(file) = &cli.baseline {
+        result.stale_baseline = Baseline::load(file)?.apply(&mut result, file);
+    }
+
+    // Check if a whitelist or JSON output was requested.
+    if cli.make_whitelist {
+        print!("{}", whitelist::make_whitelist(&result));
+    } else if cli.json {
         // Serialize the result struct to a pretty-printed JSON string.
         // This uses `serde_json` to convert the Rust struct to JSON.
         // This is useful for integrating with other tools or pipelines.
@@ -107,13 +339,72 @@ fn main() -> Result<()> {
         if !result.unused_variables.is_empty() {
             println!(" * Unused variables: {}", result.unused_variables.len());
         }
-        if cli.danger {
+        if !result.unused_parameters.is_empty() {
+            println!(" * Unused parameters: {}", result.unused_parameters.len());
+        }
+        if !result.unused_attributes.is_empty() {
+            println!(" * Unused attributes: {}", result.unused_attributes.len());
+        }
+        if !result.typing_only_imports.is_empty() {
+            println!(
+                " * Imports only needed for typing: {}",
+                result.typing_only_imports.len()
+            );
+        }
+        if !result.dead_modules.is_empty() {
+            println!(
+                " * Dead modules: {} ({} lines)",
+                result.dead_modules.len(),
+                result
+                    .dead_modules
+                    .iter()
+                    .map(|m| m.line_count)
+                    .sum::<usize>()
+            );
+        }
+        if !result.unused_dependencies.is_empty() {
+            println!(
+                " * Unused dependencies: {}",
+                result.unused_dependencies.len()
+            );
+        }
+        if !result.undeclared_dependencies.is_empty() {
+            println!(
+                " * Undeclared dependencies: {}",
+                result.undeclared_dependencies.len()
+            );
+        }
+        if !result.import_cycles.is_empty() {
+            println!(" * Import cycles: {}", result.import_cycles.len());
+        }
+        if !result.undefined_exports.is_empty() {
+            println!(" * Undefined exports: {}", result.undefined_exports.len());
+        }
+        if !result.unreachable_code.is_empty() {
+            println!(
+                " * Unreachable code blocks: {}",
+                result.unreachable_code.len()
+            );
+        }
+        if !result.redefinitions.is_empty() {
+            println!(" * Redefinitions: {}", result.redefinitions.len());
+        }
+        if !result.unused_suppressions.is_empty() {
+            println!(
+                " * Unused suppressions: {}",
+                result.unused_suppressions.len()
+            );
+        }
+        if !result.stale_baseline.is_empty() {
+            println!(" * Stale baseline entries: {}", result.stale_baseline.len());
+        }
+        if skylos.enable_danger {
             println!(" * Security issues: {}", result.danger.len());
         }
-        if cli.secrets {
+        if skylos.enable_secrets {
             println!(" * Secrets found: {}", result.secrets.len());
         }
-        if cli.quality {
+        if skylos.enable_quality {
             println!(" * Quality issues: {}", result.quality.len());
         }
 
@@ -123,7 +414,7 @@ fn main() -> Result<()> {
             println!("\n - Unreachable Functions");
             println!("=======================");
             for (i, func) in result.unused_functions.iter().enumerate() {
-                println!(" {}. {}", i + 1, func.name);
+                println!(" {}. {}{}", i + 1, func.name, dead_code_note(func));
                 println!("    └─ {}:{}", func.file.display(), func.line);
             }
         }
@@ -134,14 +425,208 @@ fn main() -> Result<()> {
             println!("\n - Unused Imports");
             println!("================");
             for (i, imp) in result.unused_imports.iter().enumerate() {
+                println!(" {}. {}{}", i + 1, imp.simple_name, dead_code_note(imp));
+                println!("    └─ {}:{}", imp.file.display(), imp.line);
+            }
+        }
+
+        // List unused variables if any found.
+        if !result.unused_variables.is_empty() {
+            println!("\n - Unused Variables");
+            println!("==================");
+            for (i, var) in result.unused_variables.iter().enumerate() {
+                println!(" {}. {}{}", i + 1, var.simple_name, dead_code_note(var));
+                println!("    └─ {}:{}", var.file.display(), var.line);
+            }
+        }
+
+        // List unused parameters if any found.
+        if !result.unused_parameters.is_empty() {
+            println!("\n - Unused Parameters");
+            println!("===================");
+            for (i, param) in result.unused_parameters.iter().enumerate() {
+                println!(" {}. {}", i + 1, param.name);
+                println!("    └─ {}:{}", param.file.display(), param.line);
+            }
+        }
+
+        // List unused attributes if any found.
+        if !result.unused_attributes.is_empty() {
+            println!("\n - Unused Attributes");
+            println!("===================");
+            for (i, attr) in result.unused_attributes.iter().enumerate() {
+                println!(" {}. {}", i + 1, attr.name);
+                println!("    └─ {}:{}", attr.file.display(), attr.line);
+            }
+        }
+
+        // List imports that are only used in annotations that are never evaluated.
+        if !result.typing_only_imports.is_empty() {
+            println!("\n - Imports Only Needed For Typing");
+            println!("================================");
+            for (i, imp) in result.typing_only_imports.iter().enumerate() {
                 println!(" {}. {}", i + 1, imp.simple_name);
                 println!("    └─ {}:{}", imp.file.display(), imp.line);
             }
         }
 
+        // List modules that are never imported, largest first.
+        if !result.dead_modules.is_empty() {
+            println!("\n - Dead Modules");
+            println!("==============");
+            for (i, m) in result.dead_modules.iter().enumerate() {
+                println!(" {}. {} ({} lines)", i + 1, m.module_name, m.line_count);
+                println!("    └─ {}", m.file.display());
+            }
+        }
+
+        // List declared dependencies that are never imported.
+        if !result.unused_dependencies.is_empty() {
+            println!("\n - Unused Dependencies");
+            println!("=====================");
+            for (i, d) in result.unused_dependencies.iter().enumerate() {
+                println!(
+                    " {}. {} [{}] ({}:{}) Severity: {}",
+                    i + 1,
+                    d.message,
+                    d.rule_id,
+                    d.file.display(),
+                    d.line,
+                    d.severity
+                );
+            }
+        }
+
+        // List imported packages that are not declared as dependencies.
+        if !result.undeclared_dependencies.is_empty() {
+            println!("\n - Undeclared Dependencies");
+            println!("=========================");
+            for (i, d) in result.undeclared_dependencies.iter().enumerate() {
+                println!(
+                    " {}. {} [{}] ({}:{}) Severity: {}",
+                    i + 1,
+                    d.message,
+                    d.rule_id,
+                    d.file.display(),
+                    d.line,
+                    d.severity
+                );
+            }
+        }
+
+        // List import cycles, with the import statement of every step.
+        if !result.import_cycles.is_empty() {
+            println!("\n - Import Cycles");
+            println!("===============");
+            for (i, cycle) in result.import_cycles.iter().enumerate() {
+                println!(
+                    " {}. {} [{}] Severity: {}",
+                    i + 1,
+                    cycle.message,
+                    cycle.rule_id,
+                    cycle.severity
+                );
+                for step in &cycle.path {
+                    println!(
+                        "    └─ {}:{} imports {}",
+                        step.file.display(),
+                        step.line,
+                        step.imports
+                    );
+                }
+            }
+        }
+
+        // List names exported through `__all__` that are never defined.
+        if !result.undefined_exports.is_empty() {
+            println!("\n - Undefined Exports");
+            println!("===================");
+            for (i, e) in result.undefined_exports.iter().enumerate() {
+                println!(
+                    " {}. {} [{}] ({}:{}) Severity: {}",
+                    i + 1,
+                    e.message,
+                    e.rule_id,
+                    e.file.display(),
+                    e.line,
+                    e.severity
+                );
+            }
+        }
+
+        // List statement blocks that can never run, with the lines they span.
+        if !result.unreachable_code.is_empty() {
+            println!("\n - Unreachable Code");
+            println!("==================");
+            for (i, block) in result.unreachable_code.iter().enumerate() {
+                println!(
+                    " {}. {} [{}] ({}:{}-{}) Severity: {}",
+                    i + 1,
+                    block.message,
+                    block.rule_id,
+                    block.file.display(),
+                    block.line,
+                    block.end_line,
+                    block.severity
+                );
+            }
+        }
+
+        // List definitions replaced before they were ever used.
+        if !result.redefinitions.is_empty() {
+            println!("\n - Redefinitions");
+            println!("===============");
+            for (i, r) in result.redefinitions.iter().enumerate() {
+                println!(
+                    " {}. {} [{}] ({}:{}) Severity: {}",
+                    i + 1,
+                    r.message,
+                    r.rule_id,
+                    r.file.display(),
+                    r.line,
+                    r.severity
+                );
+            }
+        }
+
+        // List unused suppression pragmas if requested.
+        if !result.unused_suppressions.is_empty() {
+            println!("\n - Unused Suppressions");
+            println!("======================");
+            for (i, s) in result.unused_suppressions.iter().enumerate() {
+                println!(
+                    " {}. {} [{}] ({}:{})",
+                    i + 1,
+                    s.message,
+                    s.rule_id,
+                    s.file.display(),
+                    s.line
+                );
+            }
+        }
+
+        // List baseline entries without a matching finding, so they can be removed.
+        if !result.stale_baseline.is_empty() {
+            println!("\n - Stale Baseline Entries (no longer found)");
+            println!("==========================================");
+            for (i, entry) in result.stale_baseline.iter().enumerate() {
+                let label = match &entry.name {
+                    Some(name) => format!("{} {}", entry.rule, name),
+                    None => entry.rule.clone(),
+                };
+                println!(
+                    " {}. {} ({}:{})",
+                    i + 1,
+                    label,
+                    entry.file.display(),
+                    entry.line
+                );
+            }
+        }
+
         // List security issues if enabled and found.
         // We show the message, rule ID, location, and severity.
-        if cli.danger && !result.danger.is_empty() {
+        if skylos.enable_danger && !result.danger.is_empty() {
             println!("\n - Security Issues");
             println!("================");
             for (i, f) in result.danger.iter().enumerate() {
@@ -159,7 +644,7 @@ fn main() -> Result<()> {
 
         // List secrets if enabled and found.
         // We show the message, rule ID, location, and severity.
-        if cli.secrets && !result.secrets.is_empty() {
+        if skylos.enable_secrets && !result.secrets.is_empty() {
             println!("\n - Secrets");
             println!("==========");
             for (i, s) in result.secrets.iter().enumerate() {
@@ -177,7 +662,7 @@ fn main() -> Result<()> {
 
         // List quality issues if enabled and found.
         // We show the message, rule ID, location, and severity.
-        if cli.quality && !result.quality.is_empty() {
+        if skylos.enable_quality && !result.quality.is_empty() {
             println!("\n - Quality Issues");
             println!("================");
             for (i, q) in result.quality.iter().enumerate() {
@@ -194,6 +679,126 @@ fn main() -> Result<()> {
         }
     }
 
-    // Return Ok(()) to indicate successful execution.
+    // Fail the run on unanalyzed files or findings over a threshold.
+    Ok(exit_code(&result, &gate))
+}
+
+/// Prints the files that could not be analyzed and the exceeded thresholds to stderr,
+/// and returns the exit code of the run. Errors take precedence over findings.
+fn exit_code(result: &AnalysisResult, gate: &Gate) -> u8 {
+    for error in &result.file_errors {
+        match error.line {
+            Some(line) => eprintln!(
+                "error: {}:{}: {}",
+                error.file.display(),
+                line,
+                error.message
+            ),
+            None => eprintln!("error: {}: {}", error.file.display(), error.message),
+        }
+    }
+    if !result.file_errors.is_empty() {
+        return EXIT_ERROR;
+    }
+
+    let failures = gate.check(result);
+    for failure in &failures {
+        eprintln!("{} {}", "failed:".red().bold(), failure);
+    }
+    if failures.is_empty() {
+        EXIT_SUCCESS
+    } else {
+        EXIT_FINDINGS
+    }
+}
+
+/// Creates the analyzer from the configuration file of the project and the command line
+/// options, which take precedence. Also returns the failure thresholds of the file and
+/// the configuration file that was used, if any.
+fn configure(path: &Path, args: &AnalysisArgs) -> Result<(Skylos, Gate, Option<PathBuf>)> {
+    let (config, config_file) = match &args.config {
+        Some(file) => (Config::load(file)?, Some(file.clone())),
+        None => match Config::discover(path)? {
+            Some((config, file)) => (config, Some(file)),
+            None => (Config::default(), None),
+        },
+    };
+
+    let confidence = args
+        .confidence
+        .or(config.confidence)
+        .unwrap_or(DEFAULT_CONFIDENCE);
+    let mut skylos = Skylos::new(
+        confidence,
+        config.secrets.unwrap_or(false),
+        config.danger.unwrap_or(false),
+        config.quality.unwrap_or(false),
+    );
+    skylos.public_api = pick(&args.public_api, config.public_api);
+    for file in pick(&args.whitelist, config.whitelist) {
+        skylos.whitelist.extend(whitelist::load_whitelist(&file)?);
+    }
+    skylos.exclude = pick(&args.exclude, config.exclude);
+    skylos.include = pick(&args.include, config.include);
+    skylos.include_folders = pick(&args.include_folder, config.include_folders);
+    skylos.severities = config.severity.into_iter().collect();
+    if let Some(depth) = config.thresholds.max_nesting_depth {
+        skylos.max_nesting_depth = depth;
+    }
+    let gate = Gate {
+        fail_on: config.fail_on,
+        max_dead_code: config.max_dead_code,
+        max_findings: config.max_findings,
+    };
+    Ok((skylos, gate, config_file))
+}
+
+/// Combines a `--x`/`--no-x` flag pair, returning `None` when neither was given so that
+/// the configuration file decides. Clap keeps only the last flag of the pair.
+fn family_flag(enable: bool, disable: bool) -> Option<bool> {
+    match (enable, disable) {
+        (true, _) => Some(true),
+        (_, true) => Some(false),
+        _ => None,
+    }
+}
+
+/// Returns the values given on the command line, or else those of the configuration file.
+fn pick<T: Clone>(flags: &[T], config: Vec<T>) -> Vec<T> {
+    if flags.is_empty() {
+        config
+    } else {
+        flags.to_vec()
+    }
+}
+
+/// Runs the `graph` subcommand: analyzes the project and prints the requested graph.
+fn print_graph(args: &GraphArgs) -> Result<()> {
+    let (mut skylos, _, _) = configure(&args.paths[0], &args.analysis)?;
+    skylos.collect_graphs = true;
+    let result = skylos.analyze_paths(&args.paths)?;
+
+    let graph = if args.symbols {
+        result.symbol_graph
+    } else {
+        result.module_graph
+    };
+    let mut graph = graph.unwrap_or_default();
+    if let Some(prefix) = &args.path_prefix {
+        graph = graph.filter(prefix);
+    }
+    if let Some(depth) = args.depth {
+        graph = graph.collapse(depth);
+    }
+    print!("{}", graph.render(args.format, args.highlight_dead));
     Ok(())
 }
+
+/// Returns a note for definitions that are referenced, but only from dead code.
+/// Unreferenced definitions need no explanation.
+fn dead_code_note(def: &Definition) -> &'static str {
+    match def.dead_reason.as_deref() {
+        Some("referenced_from_dead_code") => " (only referenced from dead code)",
+        _ => "",
+    }
+}
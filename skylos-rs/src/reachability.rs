@@ -0,0 +1,119 @@
+use crate::interner::Symbol;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+/// A reference graph between definitions.
+///
+/// Each definition has outgoing edges to the names referenced from its body, and
+/// module-level code (which runs on import) references names directly from the roots.
+/// Walking the graph from the roots finds every definition that can actually run;
+/// anything else is dead, even if it is referenced by other dead code.
+pub struct ReferenceGraph {
+    /// Names referenced from the body of each definition (indexed like the definitions).
+    edges: Vec<Vec<Symbol>>,
+    /// Definitions kept alive by each definition directly, regardless of names
+    /// (e.g. the overrides of a method, which run whenever the method is called).
+    implied: Vec<Vec<usize>>,
+    /// Names referenced from module-level code.
+    root_refs: Vec<Symbol>,
+    /// Definitions that are used implicitly (entry points, tests, framework handlers,
+    /// exports, public API).
+    root_defs: Vec<usize>,
+}
+
+impl ReferenceGraph {
+    /// Creates an empty graph for `def_count` definitions.
+    pub fn new(def_count: usize) -> Self {
+        Self {
+            edges: vec![Vec::new(); def_count],
+            implied: vec![Vec::new(); def_count],
+            root_refs: Vec::new(),
+            root_defs: Vec::new(),
+        }
+    }
+
+    /// Adds a reference to `name` from the definition `owner` (or from module-level code).
+    pub fn add_reference(&mut self, owner: Option<usize>, name: Symbol) {
+        match owner {
+            Some(owner) => self.edges[owner].push(name),
+            None => self.root_refs.push(name),
+        }
+    }
+
+    /// Adds an edge from one definition to another: whenever `from` is live, so is `to`.
+    pub fn add_edge(&mut self, from: usize, to: usize) {
+        self.implied[from].push(to);
+    }
+
+    /// Marks a definition as a root.
+    pub fn add_root(&mut self, def: usize) {
+        self.root_defs.push(def);
+    }
+
+    /// Computes which definitions are reachable from the roots.
+    ///
+    /// `keys[i]` is the interned name under which definition `i` is matched by references
+    /// (its full name, or its simple name for loosely tracked definitions).
+    /// Returns one flag per definition.
+    pub fn reachable(&self, keys: &[Symbol]) -> Vec<bool> {
+        let defs_by_key = index_keys(keys);
+
+        let mut live = vec![false; keys.len()];
+        let mut queue = VecDeque::new();
+
+        for &def in &self.root_defs {
+            mark(def, &mut live, &mut queue);
+        }
+        for name in &self.root_refs {
+            for &def in defs_by_key.get(name).into_iter().flatten() {
+                mark(def, &mut live, &mut queue);
+            }
+        }
+
+        // Breadth-first walk along the references of every live definition.
+        while let Some(def) = queue.pop_front() {
+            for name in &self.edges[def] {
+                for &target in defs_by_key.get(name).into_iter().flatten() {
+                    mark(target, &mut live, &mut queue);
+                }
+            }
+            for &target in &self.implied[def] {
+                mark(target, &mut live, &mut queue);
+            }
+        }
+
+        live
+    }
+
+    /// Returns the edges between definitions, with references resolved like in
+    /// `reachable`. Each edge is listed once, sorted.
+    pub fn edges(&self, keys: &[Symbol]) -> Vec<(usize, usize)> {
+        let defs_by_key = index_keys(keys);
+        let mut edges = BTreeSet::new();
+        for (def, names) in self.edges.iter().enumerate() {
+            for name in names {
+                for &target in defs_by_key.get(name).into_iter().flatten() {
+                    edges.insert((def, target));
+                }
+            }
+            edges.extend(self.implied[def].iter().map(|&target| (def, target)));
+        }
+        edges.into_iter().collect()
+    }
+}
+
+/// Groups definitions by the key they are matched under.
+fn index_keys(keys: &[Symbol]) -> HashMap<Symbol, Vec<usize>> {
+    let mut defs_by_key: HashMap<Symbol, Vec<usize>> = HashMap::new();
+    for (i, &key) in keys.iter().enumerate() {
+        defs_by_key.entry(key).or_default().push(i);
+    }
+    defs_by_key
+}
+
+/// Marks a definition as live and queues it for visiting, unless it already is.
+fn mark(def: usize, live: &mut [bool], queue: &mut VecDeque<usize>) {
+    if !live[def] {
+        live[def] = true;
+        queue.push_back(def);
+    }
+}
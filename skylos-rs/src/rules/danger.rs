@@ -61,16 +61,13 @@ impl<'a> DangerVisitor<'a> {
 
     /// Visits expressions to find dangerous function calls.
     pub fn visit_expr(&mut self, expr: &Expr) {
-        match expr {
-            Expr::Call(node) => {
-                self.check_call(node);
-                // Recursively check arguments
-                self.visit_expr(&node.func);
-                for arg in &node.args {
-                    self.visit_expr(arg);
-                }
+        if let Expr::Call(node) = expr {
+            self.check_call(node);
+            // Recursively check arguments
+            self.visit_expr(&node.func);
+            for arg in &node.args {
+                self.visit_expr(arg);
             }
-            _ => {}
         }
     }
 
@@ -9,3 +9,23 @@ pub mod danger;
 
 /// Rules for detecting code quality issues.
 pub mod quality;
+
+/// Identifiers of every rule that reports findings with a severity.
+pub const RULE_IDS: &[&str] = &[
+    "SKY-S101", // Hardcoded secret
+    "SKY-D001", // eval/exec
+    "SKY-D002", // subprocess with shell=True
+    "SKY-Q001", // Deeply nested code
+    "SKY-E001", // Undefined name in __all__
+    "SKY-U001", // Code after return/raise/break/continue
+    "SKY-U002", // Branch guarded by a constant condition
+    "SKY-U003", // Shadowed except clause
+    "SKY-R001", // Redefinition of an unused name
+    "SKY-M001", // Dead module
+    "SKY-P001", // Unused dependency
+    "SKY-P002", // Undeclared dependency
+    "SKY-C001", // Module-level import cycle
+    "SKY-C002", // Function-local import cycle
+    "SKY-C003", // Type-checking import cycle
+    "SKY-I001", // Unused suppression pragma
+];
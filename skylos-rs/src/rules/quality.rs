@@ -1,8 +1,11 @@
 use crate::utils::LineIndex;
-use rustpython_ast::{self as ast, ExceptHandler, Stmt};
+use rustpython_ast::{ExceptHandler, Stmt};
 use serde::Serialize;
 use std::path::PathBuf;
 
+/// Default nesting depth above which code is reported as deeply nested.
+pub const DEFAULT_MAX_DEPTH: usize = 5;
+
 /// Represents a code quality finding.
 #[derive(Debug, Clone, Serialize)]
 pub struct QualityFinding {
@@ -42,7 +45,7 @@ impl<'a> QualityVisitor<'a> {
             file_path,
             line_index,
             current_depth: 0,
-            max_depth: 5, // Default threshold for nesting depth
+            max_depth: DEFAULT_MAX_DEPTH,
         }
     }
 
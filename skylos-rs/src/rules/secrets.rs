@@ -1,6 +1,6 @@
 use regex::Regex;
 use serde::Serialize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Represents a secret finding (e.g., a hardcoded API key).
 #[derive(Debug, Clone, Serialize)]
@@ -37,7 +37,7 @@ lazy_static::lazy_static! {
 /// Scans the content of a file for secrets using regular expressions.
 ///
 /// This function iterates through the file line by line and applies the regex patterns.
-pub fn scan_secrets(content: &str, file_path: &PathBuf) -> Vec<SecretFinding> {
+pub fn scan_secrets(content: &str, file_path: &Path) -> Vec<SecretFinding> {
     let mut findings = Vec::new();
 
     for (line_idx, line) in content.lines().enumerate() {
@@ -53,7 +53,7 @@ pub fn scan_secrets(content: &str, file_path: &PathBuf) -> Vec<SecretFinding> {
                 findings.push(SecretFinding {
                     message: format!("Found potential {}", name),
                     rule_id: "SKY-S101".to_string(),
-                    file: file_path.clone(),
+                    file: file_path.to_path_buf(),
                     line: line_idx + 1,
                     severity: "HIGH".to_string(),
                 });
@@ -0,0 +1,182 @@
+use std::collections::{HashMap, HashSet};
+
+/// The kind of a Python scope.
+///
+/// Python resolves names through function scopes (including lambdas and comprehensions)
+/// but class bodies are skipped when resolving names from nested functions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScopeKind {
+    Module,
+    Class,
+    Function,
+    Lambda,
+    Comprehension,
+}
+
+/// What kind of statement bound a name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BindingKind {
+    /// An assignment target (`x = ...`, `for x in ...`, `with ... as x`, `x := ...`).
+    Variable,
+    /// A function or lambda parameter.
+    Parameter,
+    /// Any other binding (nested `def`/`class`, imports, `except ... as e`).
+    /// These are tracked for name resolution only.
+    Other,
+}
+
+/// A name bound in a scope.
+#[derive(Debug, Clone)]
+pub struct Binding {
+    /// The bound name.
+    pub name: String,
+    /// The line of the first binding.
+    pub line: usize,
+    /// The kind of the first binding.
+    pub kind: BindingKind,
+}
+
+/// A `def`, `class` or `import` binding that has not been read since it was made.
+/// Binding the name again before it is read makes this definition dead.
+#[derive(Debug, Clone)]
+pub struct UnreadDefinition {
+    /// The kind of statement that made the binding ("function", "class" or "import").
+    pub kind: &'static str,
+    /// The line of the binding.
+    pub line: usize,
+    /// For imports, the dotted name of the imported module or member.
+    pub target: Option<String>,
+    /// The `if`/`try`/`match` branches the binding is nested in, outermost first,
+    /// as `(statement id, branch index)` pairs.
+    pub branches: Vec<(usize, usize)>,
+    /// Whether the definition is meant to be redefined (`@overload`) or may be used
+    /// without being read (e.g. registered by a decorator).
+    pub allows_redefinition: bool,
+}
+
+/// A lexical scope, tracking which names it binds and which names it reads.
+#[derive(Debug)]
+pub struct Scope {
+    /// The kind of scope.
+    pub kind: ScopeKind,
+    /// Qualified name of the scope (e.g. `pkg.mod.func`), used to name its locals.
+    pub qualified_name: String,
+    /// Bindings in the order they first appear.
+    bindings: Vec<Binding>,
+    /// Name -> index into `bindings`.
+    binding_index: HashMap<String, usize>,
+    /// Number of reads of each name, including free reads from nested scopes.
+    loads: HashMap<String, usize>,
+    /// Names declared `global` in this scope.
+    globals: HashSet<String>,
+    /// Names declared `nonlocal` in this scope.
+    nonlocals: HashSet<String>,
+    /// Definitions of names that have not been read since they were made.
+    unread_definitions: HashMap<String, UnreadDefinition>,
+    /// Confidence (0-100) with which unused parameters of this scope are reported.
+    /// Lowered when the signature is dictated by something else (overrides, stubs, callbacks).
+    pub parameter_confidence: u8,
+}
+
+impl Scope {
+    /// Creates an empty scope.
+    pub fn new(kind: ScopeKind, qualified_name: String) -> Self {
+        Self {
+            kind,
+            qualified_name,
+            bindings: Vec::new(),
+            binding_index: HashMap::new(),
+            loads: HashMap::new(),
+            globals: HashSet::new(),
+            nonlocals: HashSet::new(),
+            unread_definitions: HashMap::new(),
+            parameter_confidence: 100,
+        }
+    }
+
+    /// Whether names bound here are local to a function (rather than module or class).
+    pub fn is_function_like(&self) -> bool {
+        matches!(
+            self.kind,
+            ScopeKind::Function | ScopeKind::Lambda | ScopeKind::Comprehension
+        )
+    }
+
+    /// Binds a name. Returns `true` if this is the first binding of the name.
+    pub fn bind(&mut self, name: &str, line: usize, kind: BindingKind) -> bool {
+        if self.binding_index.contains_key(name) {
+            return false;
+        }
+        self.binding_index
+            .insert(name.to_string(), self.bindings.len());
+        self.bindings.push(Binding {
+            name: name.to_string(),
+            line,
+            kind,
+        });
+        true
+    }
+
+    /// Whether the name is bound in this scope.
+    pub fn is_bound(&self, name: &str) -> bool {
+        self.binding_index.contains_key(name)
+    }
+
+    /// Records `count` reads of a name.
+    pub fn add_load(&mut self, name: &str, count: usize) {
+        self.unread_definitions.remove(name);
+        *self.loads.entry(name.to_string()).or_insert(0) += count;
+    }
+
+    /// Returns the number of reads of a name.
+    pub fn load_count(&self, name: &str) -> usize {
+        self.loads.get(name).copied().unwrap_or(0)
+    }
+
+    /// Records a definition of a name, which is unread until the name is loaded.
+    pub fn add_unread_definition(&mut self, name: &str, definition: UnreadDefinition) {
+        self.unread_definitions.insert(name.to_string(), definition);
+    }
+
+    /// Removes and returns the unread definition of a name, if there is one.
+    pub fn take_unread_definition(&mut self, name: &str) -> Option<UnreadDefinition> {
+        self.unread_definitions.remove(name)
+    }
+
+    /// Declares a name `global` in this scope.
+    pub fn declare_global(&mut self, name: &str) {
+        self.globals.insert(name.to_string());
+    }
+
+    /// Declares a name `nonlocal` in this scope.
+    pub fn declare_nonlocal(&mut self, name: &str) {
+        self.nonlocals.insert(name.to_string());
+    }
+
+    /// Whether the name was declared `global` in this scope.
+    pub fn is_global(&self, name: &str) -> bool {
+        self.globals.contains(name)
+    }
+
+    /// Whether the name was declared `nonlocal` in this scope.
+    pub fn is_nonlocal(&self, name: &str) -> bool {
+        self.nonlocals.contains(name)
+    }
+
+    /// Returns the bindings of this scope, in order of first appearance.
+    pub fn bindings(&self) -> &[Binding] {
+        &self.bindings
+    }
+
+    /// Returns the reads of names that are not bound in this scope.
+    /// These resolve to an enclosing function scope (closures and `nonlocal`).
+    /// Names declared `global` are excluded, since they resolve to the module.
+    pub fn free_loads(&self) -> impl Iterator<Item = (&str, usize)> + '_ {
+        self.loads
+            .iter()
+            .filter(|(name, _)| {
+                (!self.is_bound(name) || self.is_nonlocal(name)) && !self.is_global(name)
+            })
+            .map(|(name, count)| (name.as_str(), *count))
+    }
+}
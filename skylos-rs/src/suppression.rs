@@ -0,0 +1,402 @@
+use crate::utils::LineIndex;
+use anyhow::{Context, Result};
+use rustpython_parser::lexer::lex;
+use rustpython_parser::{Mode, Tok};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::ops::Range;
+use std::path::PathBuf;
+
+/// The rules a pragma applies to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rules {
+    /// Every rule, and unused definitions (which have no rule ID).
+    All,
+    /// The listed rule IDs, e.g. `SKY-D002`.
+    Only(Vec<String>),
+}
+
+impl Rules {
+    /// Whether a finding of `rule_id` (`None` for an unused definition) is covered.
+    pub fn covers(&self, rule_id: Option<&str>) -> bool {
+        match self {
+            Rules::All => true,
+            Rules::Only(rules) => rule_id.is_some_and(|id| rules.iter().any(|rule| rule == id)),
+        }
+    }
+}
+
+/// Kind of a suppression pragma.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PragmaKind {
+    /// `# skylos: ignore` (or the older `# pragma: no skylos`): findings on the same line.
+    Line,
+    /// `# skylos: ignore-next-line`: findings on the next line.
+    NextLine,
+    /// `# skylos: ignore-file`: findings anywhere in the file.
+    File,
+    /// `# skylos: disable=...`: findings from this line until the rules are enabled again.
+    Disable,
+    /// `# skylos: enable=...`: ends the `disable` regions of the rules.
+    Enable,
+}
+
+/// A suppression pragma in a comment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pragma {
+    /// What the pragma suppresses.
+    pub kind: PragmaKind,
+    /// The rules it applies to. Without a rule list, a pragma applies to every rule.
+    pub rules: Rules,
+    /// Line of the comment.
+    pub line: usize,
+    /// Text of the pragma, from its `#` to the end of its part of the comment.
+    pub text: String,
+    /// Byte range of `text` in the source.
+    pub range: Range<usize>,
+}
+
+/// Lines in which a rule is disabled, from a `disable` pragma to the matching `enable`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Region {
+    /// First line, the line of the `disable` pragma.
+    start: usize,
+    /// Last line, the line of the `enable` pragma (`usize::MAX` if there is none).
+    end: usize,
+    /// The disabled rule, `None` for every rule.
+    rule: Option<String>,
+    /// Index of the `disable` pragma.
+    pragma: usize,
+}
+
+/// The suppression pragmas of a file.
+///
+/// Pragmas are read from comment tokens only, so a pragma-like text in a string literal
+/// does not suppress anything.
+#[derive(Debug, Clone, Default)]
+pub struct Suppressions {
+    /// The pragmas, in source order.
+    pub pragmas: Vec<Pragma>,
+    /// The regions of the `disable` pragmas.
+    regions: Vec<Region>,
+    /// Whether each pragma suppressed a finding.
+    used: Vec<bool>,
+}
+
+/// A suppression pragma that did not suppress any finding.
+#[derive(Serialize, Clone, Debug)]
+pub struct UnusedSuppression {
+    /// Text of the pragma, e.g. `# skylos: ignore[SKY-D002]`.
+    pub pragma: String,
+    /// Description of the issue.
+    pub message: String,
+    /// Unique rule identifier.
+    pub rule_id: String,
+    /// File of the pragma.
+    pub file: PathBuf,
+    /// Line of the pragma.
+    pub line: usize,
+    /// Severity level.
+    pub severity: String,
+}
+
+impl Suppressions {
+    /// Reads the suppression pragmas of a Python source file.
+    pub fn parse(source: &str, line_index: &LineIndex) -> Self {
+        let mut suppressions = Suppressions::default();
+        // Most files have no pragmas; skip lexing them.
+        if !source.contains("skylos") {
+            return suppressions;
+        }
+        for (token, range) in lex(source, Mode::Module).flatten() {
+            let Tok::Comment(comment) = token else {
+                continue;
+            };
+            let line = line_index.line_index(range.start());
+            let offset = range.start().to_usize();
+            // A comment may hold several parts, e.g. `# noqa: E501  # skylos: ignore`.
+            for (start, _) in comment.match_indices('#') {
+                let end = comment[start + 1..]
+                    .find('#')
+                    .map_or(comment.len(), |end| start + 1 + end);
+                let part = comment[start..end].trim_end();
+                if let Some((kind, rules)) = parse_pragma(&part[1..]) {
+                    suppressions.pragmas.push(Pragma {
+                        kind,
+                        rules,
+                        line,
+                        text: part.to_string(),
+                        range: offset + start..offset + start + part.len(),
+                    });
+                }
+            }
+        }
+        suppressions.regions = regions(&suppressions.pragmas);
+        suppressions.used = vec![false; suppressions.pragmas.len()];
+        suppressions
+    }
+
+    /// Whether the file has no pragmas.
+    pub fn is_empty(&self) -> bool {
+        self.pragmas.is_empty()
+    }
+
+    /// Returns the index of the pragma suppressing a finding of `rule_id` (`None` for an
+    /// unused definition) on `line`, if any.
+    pub fn find(&self, line: usize, rule_id: Option<&str>) -> Option<usize> {
+        let matches = |kind: PragmaKind, target: usize| {
+            self.pragmas.iter().position(|pragma| {
+                pragma.kind == kind && pragma.line == target && pragma.rules.covers(rule_id)
+            })
+        };
+        matches(PragmaKind::Line, line)
+            .or_else(|| matches(PragmaKind::NextLine, line.wrapping_sub(1)))
+            .or_else(|| {
+                self.regions
+                    .iter()
+                    .find(|region| {
+                        (region.start..=region.end).contains(&line)
+                            && region
+                                .rule
+                                .as_deref()
+                                .is_none_or(|rule| Some(rule) == rule_id)
+                    })
+                    .map(|region| region.pragma)
+            })
+            .or_else(|| {
+                self.pragmas.iter().position(|pragma| {
+                    pragma.kind == PragmaKind::File && pragma.rules.covers(rule_id)
+                })
+            })
+    }
+
+    /// Whether a finding of `rule_id` (`None` for an unused definition) on `line` is
+    /// suppressed.
+    pub fn is_suppressed(&self, line: usize, rule_id: Option<&str>) -> bool {
+        self.find(line, rule_id).is_some()
+    }
+
+    /// Like `is_suppressed`, and marks the pragma suppressing the finding as used.
+    pub fn suppress(&mut self, line: usize, rule_id: Option<&str>) -> bool {
+        let pragma = self.find(line, rule_id);
+        if let Some(pragma) = pragma {
+            self.mark_used(pragma);
+        }
+        pragma.is_some()
+    }
+
+    /// Marks a pragma as having suppressed a finding.
+    pub fn mark_used(&mut self, pragma: usize) {
+        self.used[pragma] = true;
+    }
+
+    /// Returns the pragmas that did not suppress any finding. `enable` pragmas, which
+    /// never suppress anything themselves, are left out.
+    pub fn unused(&self) -> impl Iterator<Item = &Pragma> {
+        self.pragmas
+            .iter()
+            .zip(&self.used)
+            .filter(|(pragma, used)| !**used && pragma.kind != PragmaKind::Enable)
+            .map(|(pragma, _)| pragma)
+    }
+}
+
+/// Deletes the unused pragmas from their files. Returns the number of deleted pragmas.
+///
+/// A pragma is deleted with the whitespace before it, and a line left empty is deleted
+/// entirely. Other parts of the same comment, such as `# noqa`, are kept.
+pub fn remove_unused_suppressions(unused: &[UnusedSuppression]) -> Result<usize> {
+    let mut by_file: BTreeMap<&PathBuf, Vec<&UnusedSuppression>> = BTreeMap::new();
+    for suppression in unused {
+        by_file
+            .entry(&suppression.file)
+            .or_default()
+            .push(suppression);
+    }
+
+    let mut removed = 0;
+    for (file, unused) in by_file {
+        let source = fs::read_to_string(file)
+            .with_context(|| format!("failed to read {}", file.display()))?;
+        let suppressions = Suppressions::parse(&source, &LineIndex::new(&source));
+        let ranges: Vec<Range<usize>> = suppressions
+            .pragmas
+            .into_iter()
+            .filter(|pragma| {
+                unused
+                    .iter()
+                    .any(|u| u.line == pragma.line && u.pragma == pragma.text)
+            })
+            .map(|pragma| pragma.range)
+            .collect();
+        if ranges.is_empty() {
+            continue;
+        }
+        removed += ranges.len();
+        fs::write(file, remove_ranges(&source, &ranges))
+            .with_context(|| format!("failed to write {}", file.display()))?;
+    }
+    Ok(removed)
+}
+
+/// Removes pragmas (sorted by position) from a source, with the whitespace separating
+/// them from the rest of the line. Lines left empty are removed entirely.
+fn remove_ranges(source: &str, ranges: &[Range<usize>]) -> String {
+    let mut out = source.to_string();
+    for range in ranges.iter().rev() {
+        let line_start = source[..range.start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = source[range.end..]
+            .find('\n')
+            .map_or(source.len(), |i| range.end + i + 1);
+        let before = &source[line_start..range.start];
+        let after = &source[range.end..line_end];
+        let removed = if before.trim().is_empty() && after.trim().is_empty() {
+            line_start..line_end
+        } else if before.trim().is_empty() {
+            range.start..line_end - after.trim_start().len()
+        } else {
+            line_start + before.trim_end().len()..range.end
+        };
+        out.replace_range(removed, "");
+    }
+    out
+}
+
+/// Parses one `#`-separated part of a comment, e.g. ` skylos: ignore[SKY-D002] `.
+/// Text after the pragma, such as an explanation, is ignored.
+fn parse_pragma(text: &str) -> Option<(PragmaKind, Rules)> {
+    let text = text.trim();
+    if text.starts_with("pragma: no skylos") {
+        return Some((PragmaKind::Line, Rules::All));
+    }
+    let text = text.strip_prefix("skylos:")?.trim_start();
+    let end = text
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-'))
+        .unwrap_or(text.len());
+    let (directive, rest) = text.split_at(end);
+    let kind = match directive {
+        "ignore" => PragmaKind::Line,
+        "ignore-next-line" => PragmaKind::NextLine,
+        "ignore-file" => PragmaKind::File,
+        "disable" => PragmaKind::Disable,
+        "enable" => PragmaKind::Enable,
+        _ => return None,
+    };
+    let rules = if let Some(list) = rest.strip_prefix('[') {
+        Rules::Only(rule_list(list.split(']').next().unwrap_or_default()))
+    } else if let Some(list) = rest.strip_prefix('=') {
+        Rules::Only(rule_list(list))
+    } else {
+        Rules::All
+    };
+    Some((kind, rules))
+}
+
+/// Parses a comma-separated list of rule IDs, stopping at the first item followed by
+/// other text.
+fn rule_list(list: &str) -> Vec<String> {
+    let mut rules = Vec::new();
+    for item in list.split(',') {
+        let mut words = item.split_whitespace();
+        if let Some(rule) = words.next() {
+            rules.push(rule.to_uppercase());
+        }
+        if words.next().is_some() {
+            break;
+        }
+    }
+    rules
+}
+
+/// Pairs the `disable` pragmas with the `enable` pragmas ending them.
+///
+/// `enable=RULE` ends the region of `RULE`, and a bare `enable` ends every open region.
+/// Regions that are never ended last until the end of the file.
+fn regions(pragmas: &[Pragma]) -> Vec<Region> {
+    let mut open: Vec<Region> = Vec::new();
+    let mut closed = Vec::new();
+    for (i, pragma) in pragmas.iter().enumerate() {
+        match (pragma.kind, &pragma.rules) {
+            (PragmaKind::Disable, Rules::All) => open.push(Region {
+                start: pragma.line,
+                end: usize::MAX,
+                rule: None,
+                pragma: i,
+            }),
+            (PragmaKind::Disable, Rules::Only(rules)) => {
+                open.extend(rules.iter().map(|rule| Region {
+                    start: pragma.line,
+                    end: usize::MAX,
+                    rule: Some(rule.clone()),
+                    pragma: i,
+                }));
+            }
+            (PragmaKind::Enable, rules) => {
+                let (ended, still_open) = open.into_iter().partition(|region: &Region| {
+                    matches!(rules, Rules::All)
+                        || region
+                            .rule
+                            .as_deref()
+                            .is_some_and(|rule| rules.covers(Some(rule)))
+                });
+                open = still_open;
+                closed.extend(ended.into_iter().map(|region| Region {
+                    end: pragma.line,
+                    ..region
+                }));
+            }
+            _ => {}
+        }
+    }
+    closed.extend(open);
+    closed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(source: &str) -> Suppressions {
+        Suppressions::parse(source, &LineIndex::new(source))
+    }
+
+    #[test]
+    fn test_pragma_detection() {
+        let source = r#"
+def used_function():
+    return 42
+
+def unused_function():  # pragma: no skylos
+    return "ignored"
+
+class MyClass:  # pragma: no skylos
+    pass
+"#;
+        let suppressions = parse(source);
+
+        // Lines 5 and 8 should be ignored (1-indexed)
+        assert!(
+            suppressions.is_suppressed(5, None),
+            "Should detect pragma on line 5"
+        );
+        assert!(
+            suppressions.is_suppressed(8, None),
+            "Should detect pragma on line 8"
+        );
+        assert_eq!(
+            suppressions.pragmas.len(),
+            2,
+            "Should find exactly 2 pragmas"
+        );
+    }
+
+    #[test]
+    fn test_no_pragmas() {
+        let source = r#"
+def regular_function():
+    return 42
+"#;
+        assert!(parse(source).is_empty(), "Should find no pragmas");
+    }
+}
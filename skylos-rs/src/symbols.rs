@@ -0,0 +1,98 @@
+use crate::utils::qualify;
+use crate::visitor::ImportBinding;
+use std::collections::{HashMap, HashSet};
+
+/// Maximum number of alias hops followed when resolving a name.
+/// This guards against import cycles (e.g. two `__init__.py` files re-exporting each other).
+const MAX_ALIAS_DEPTH: usize = 32;
+
+/// Project-wide symbol table built after all files have been visited.
+///
+/// Every import is stored as an alias from its qualified name in the importing module
+/// (e.g. `pkg.helper` for `from .mod import helper` in `pkg/__init__.py`) to the name
+/// it imports (`pkg.mod.helper`). Following these aliases lets us count a use of an
+/// imported name as a use of the original definition, including re-exports.
+#[derive(Default)]
+pub struct SymbolTable {
+    /// Qualified alias name -> imported target name.
+    aliases: HashMap<String, String>,
+    /// Local import names per module, to tell imported names apart from other references.
+    module_imports: HashMap<String, HashSet<String>>,
+}
+
+impl SymbolTable {
+    /// Creates an empty symbol table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers the import bindings of a module.
+    pub fn add_module(&mut self, module_name: &str, imports: &[ImportBinding]) {
+        let locals = self
+            .module_imports
+            .entry(module_name.to_string())
+            .or_default();
+        for import in imports {
+            locals.insert(import.local_name.clone());
+            self.aliases.insert(
+                qualify(module_name, &import.local_name),
+                import.target.clone(),
+            );
+        }
+    }
+
+    /// Follows alias chains starting at a fully qualified name.
+    ///
+    /// Returns every alias passed through (each of them is an import that is being used)
+    /// followed by the final resolved name. The longest aliased prefix is substituted at
+    /// each step, so `pkg.helper.attr` resolves through the alias `pkg.helper`.
+    pub fn resolve(&self, name: &str) -> Vec<String> {
+        let mut visited = Vec::new();
+        let mut seen = HashSet::new();
+        let mut current = name.to_string();
+
+        for _ in 0..MAX_ALIAS_DEPTH {
+            if !seen.insert(current.clone()) {
+                break;
+            }
+            let Some((alias, target, rest)) = self.find_alias(&current) else {
+                break;
+            };
+            visited.push(alias.to_string());
+            current = format!("{}{}", target, rest);
+        }
+
+        visited.push(current);
+        visited
+    }
+
+    /// Resolves a reference made inside `module_name`.
+    ///
+    /// If the reference starts with a name imported by that module (e.g. `helper` or
+    /// `np.array`), returns the names it resolves to, starting with the local import
+    /// itself. Otherwise returns an empty list.
+    pub fn resolve_reference(&self, module_name: &str, name: &str) -> Vec<String> {
+        let head = name.split('.').next().unwrap_or(name);
+        let is_imported = self
+            .module_imports
+            .get(module_name)
+            .is_some_and(|locals| locals.contains(head));
+        if !is_imported {
+            return Vec::new();
+        }
+        self.resolve(&qualify(module_name, name))
+    }
+
+    /// Finds the longest dotted prefix of `name` that is an alias.
+    /// Returns the alias, its target and the remaining suffix (including the leading dot).
+    fn find_alias<'n>(&self, name: &'n str) -> Option<(&'n str, &str, &'n str)> {
+        let mut end = name.len();
+        loop {
+            let prefix = &name[..end];
+            if let Some(target) = self.aliases.get(prefix) {
+                return Some((prefix, target.as_str(), &name[end..]));
+            }
+            end = prefix.rfind('.')?;
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use crate::utils::LineIndex;
 use regex::Regex;
-use rustpython_ast::{self as ast, Expr, Stmt};
+use rustpython_ast::{Expr, Stmt};
 use std::path::Path;
 
 lazy_static::lazy_static! {
@@ -0,0 +1,486 @@
+use crate::utils::LineIndex;
+use crate::visitor::dotted_name;
+use rustpython_ast::{self as ast, ExceptHandler, Expr, Ranged, Stmt};
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Calls that never return.
+const EXIT_CALLS: &[&str] = &["sys.exit", "exit", "quit", "os._exit", "os.abort"];
+
+/// Built-in exception classes and their direct base class.
+/// Used to tell whether an earlier `except` clause already catches everything a later one does.
+const EXCEPTION_BASES: &[(&str, &str)] = &[
+    ("Exception", "BaseException"),
+    ("GeneratorExit", "BaseException"),
+    ("KeyboardInterrupt", "BaseException"),
+    ("SystemExit", "BaseException"),
+    ("ArithmeticError", "Exception"),
+    ("FloatingPointError", "ArithmeticError"),
+    ("OverflowError", "ArithmeticError"),
+    ("ZeroDivisionError", "ArithmeticError"),
+    ("AssertionError", "Exception"),
+    ("AttributeError", "Exception"),
+    ("BufferError", "Exception"),
+    ("EOFError", "Exception"),
+    ("ImportError", "Exception"),
+    ("ModuleNotFoundError", "ImportError"),
+    ("LookupError", "Exception"),
+    ("IndexError", "LookupError"),
+    ("KeyError", "LookupError"),
+    ("MemoryError", "Exception"),
+    ("NameError", "Exception"),
+    ("UnboundLocalError", "NameError"),
+    ("OSError", "Exception"),
+    ("BlockingIOError", "OSError"),
+    ("ChildProcessError", "OSError"),
+    ("ConnectionError", "OSError"),
+    ("BrokenPipeError", "ConnectionError"),
+    ("ConnectionAbortedError", "ConnectionError"),
+    ("ConnectionRefusedError", "ConnectionError"),
+    ("ConnectionResetError", "ConnectionError"),
+    ("FileExistsError", "OSError"),
+    ("FileNotFoundError", "OSError"),
+    ("InterruptedError", "OSError"),
+    ("IsADirectoryError", "OSError"),
+    ("NotADirectoryError", "OSError"),
+    ("PermissionError", "OSError"),
+    ("ProcessLookupError", "OSError"),
+    ("TimeoutError", "OSError"),
+    ("ReferenceError", "Exception"),
+    ("RuntimeError", "Exception"),
+    ("NotImplementedError", "RuntimeError"),
+    ("RecursionError", "RuntimeError"),
+    ("StopAsyncIteration", "Exception"),
+    ("StopIteration", "Exception"),
+    ("SyntaxError", "Exception"),
+    ("IndentationError", "SyntaxError"),
+    ("TabError", "IndentationError"),
+    ("SystemError", "Exception"),
+    ("TypeError", "Exception"),
+    ("ValueError", "Exception"),
+    ("UnicodeError", "ValueError"),
+    ("UnicodeDecodeError", "UnicodeError"),
+    ("UnicodeEncodeError", "UnicodeError"),
+    ("UnicodeTranslateError", "UnicodeError"),
+    ("Warning", "Exception"),
+    ("BytesWarning", "Warning"),
+    ("DeprecationWarning", "Warning"),
+    ("FutureWarning", "Warning"),
+    ("ImportWarning", "Warning"),
+    ("PendingDeprecationWarning", "Warning"),
+    ("ResourceWarning", "Warning"),
+    ("RuntimeWarning", "Warning"),
+    ("SyntaxWarning", "Warning"),
+    ("UnicodeWarning", "Warning"),
+    ("UserWarning", "Warning"),
+];
+
+/// A block of statements that can never run.
+#[derive(Debug, Clone, Serialize)]
+pub struct UnreachableCode {
+    /// Description of why the block is unreachable.
+    pub message: String,
+    /// Unique rule identifier (e.g., "SKY-U001").
+    pub rule_id: String,
+    /// File where the block was found.
+    pub file: PathBuf,
+    /// First line of the dead block.
+    pub line: usize,
+    /// Last line of the dead block.
+    pub end_line: usize,
+    /// Severity level (e.g., "LOW").
+    pub severity: String,
+}
+
+/// Visitor that runs a control-flow pass over every function body.
+///
+/// It reports statements following a `return`, `raise`, `break`, `continue` or exit call
+/// (SKY-U001), branches guarded by a constant condition (SKY-U002), and `except` clauses
+/// shadowed by an earlier, broader handler (SKY-U003).
+/// Module-level and class-level code is only searched for functions, not checked itself.
+pub struct UnreachableVisitor<'a> {
+    /// Collected findings.
+    pub findings: Vec<UnreachableCode>,
+    /// Current file path.
+    pub file_path: PathBuf,
+    /// Helper for line mapping.
+    pub line_index: &'a LineIndex,
+}
+
+impl<'a> UnreachableVisitor<'a> {
+    /// Creates a new `UnreachableVisitor`.
+    pub fn new(file_path: PathBuf, line_index: &'a LineIndex) -> Self {
+        Self {
+            findings: Vec::new(),
+            file_path,
+            line_index,
+        }
+    }
+
+    /// Visits a module-level or class-level statement, checking the functions it contains.
+    pub fn visit_stmt(&mut self, stmt: &Stmt) {
+        match stmt {
+            Stmt::FunctionDef(node) => {
+                self.check_block(&node.body);
+            }
+            Stmt::AsyncFunctionDef(node) => {
+                self.check_block(&node.body);
+            }
+            _ => {
+                for block in child_blocks(stmt) {
+                    for stmt in block {
+                        self.visit_stmt(stmt);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Checks a block of statements inside a function.
+    /// Returns whether control can fall off the end of the block.
+    fn check_block(&mut self, body: &[Stmt]) -> bool {
+        for (i, stmt) in body.iter().enumerate() {
+            if !self.check_stmt(stmt) {
+                let rest = &body[i + 1..];
+                if !rest.is_empty() {
+                    self.report(
+                        rest,
+                        "SKY-U001",
+                        format!("Unreachable code after {}", exit_description(stmt)),
+                    );
+                }
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Checks a single statement inside a function.
+    /// Returns whether control can continue with the next statement.
+    fn check_stmt(&mut self, stmt: &Stmt) -> bool {
+        match stmt {
+            Stmt::Return(_) | Stmt::Raise(_) | Stmt::Break(_) | Stmt::Continue(_) => false,
+            Stmt::Expr(node) => exit_call(&node.value).is_none(),
+            Stmt::If(node) => match constant_truth(&node.test) {
+                Some(false) => {
+                    self.report(
+                        &node.body,
+                        "SKY-U002",
+                        "Unreachable code: condition is always false".to_string(),
+                    );
+                    self.check_block(&node.orelse)
+                }
+                Some(true) => {
+                    let falls_through = self.check_block(&node.body);
+                    if !node.orelse.is_empty() {
+                        self.report(
+                            &node.orelse,
+                            "SKY-U002",
+                            "Unreachable 'else' branch: condition is always true".to_string(),
+                        );
+                    }
+                    falls_through
+                }
+                None => {
+                    let body = self.check_block(&node.body);
+                    let orelse = self.check_block(&node.orelse);
+                    body || orelse
+                }
+            },
+            Stmt::While(node) => match constant_truth(&node.test) {
+                Some(false) => {
+                    self.report(
+                        &node.body,
+                        "SKY-U002",
+                        "Unreachable loop body: condition is always false".to_string(),
+                    );
+                    self.check_block(&node.orelse)
+                }
+                // An infinite loop only ends through a `break`.
+                Some(true) => {
+                    self.check_block(&node.body);
+                    contains_break(&node.body)
+                }
+                None => {
+                    self.check_block(&node.body);
+                    self.check_block(&node.orelse);
+                    true
+                }
+            },
+            Stmt::Try(node) => self.check_try(
+                &node.body,
+                &node.handlers,
+                &node.orelse,
+                &node.finalbody,
+                true,
+            ),
+            Stmt::TryStar(node) => self.check_try(
+                &node.body,
+                &node.handlers,
+                &node.orelse,
+                &node.finalbody,
+                false,
+            ),
+            Stmt::Match(node) => {
+                let mut falls_through = false;
+                let mut exhaustive = false;
+                for case in &node.cases {
+                    falls_through |= self.check_block(&case.body);
+                    exhaustive |= case.guard.is_none() && is_irrefutable(&case.pattern);
+                }
+                // Without an irrefutable case, no case may match at all.
+                falls_through || !exhaustive
+            }
+            Stmt::FunctionDef(_) | Stmt::AsyncFunctionDef(_) | Stmt::ClassDef(_) => {
+                self.visit_stmt(stmt);
+                true
+            }
+            // Loops and `with` blocks (whose context manager may swallow an exception)
+            // can always continue after the block.
+            _ => {
+                for block in child_blocks(stmt) {
+                    self.check_block(block);
+                }
+                true
+            }
+        }
+    }
+
+    /// Checks the blocks of a `try` statement.
+    /// Returns whether control can continue after the statement.
+    fn check_try(
+        &mut self,
+        body: &[Stmt],
+        handlers: &[ExceptHandler],
+        orelse: &[Stmt],
+        finalbody: &[Stmt],
+        check_shadowing: bool,
+    ) -> bool {
+        let body_falls_through = self.check_block(body);
+        let orelse_falls_through = self.check_block(orelse);
+        let mut falls_through = body_falls_through && orelse_falls_through;
+
+        // Exception classes caught by the handlers seen so far (`None` for a bare `except:`).
+        let mut caught: Vec<Option<String>> = Vec::new();
+        for ExceptHandler::ExceptHandler(handler) in handlers {
+            let types = handler.type_.as_deref().map(handler_types);
+            if check_shadowing {
+                if let Some(shadowing) = shadowing_handler(&caught, types.as_ref()) {
+                    self.report(
+                        &handler.body,
+                        "SKY-U003",
+                        format!(
+                            "Unreachable 'except' clause: an earlier handler catches {}",
+                            shadowing
+                        ),
+                    );
+                    continue;
+                }
+            }
+            match &types {
+                None => caught.push(None),
+                Some(types) => caught.extend(types.iter().flatten().cloned().map(Some)),
+            }
+            if self.check_block(&handler.body) {
+                falls_through = true;
+            }
+        }
+
+        // A `finally` block that always exits overrides how the rest of the statement ended.
+        self.check_block(finalbody) && falls_through
+    }
+
+    /// Records a dead block, spanning from its first to its last statement.
+    fn report(&mut self, block: &[Stmt], rule_id: &str, message: String) {
+        let (Some(first), Some(last)) = (block.first(), block.last()) else {
+            return;
+        };
+        self.findings.push(UnreachableCode {
+            message,
+            rule_id: rule_id.to_string(),
+            file: self.file_path.clone(),
+            line: self.line_index.line_index(first.start()),
+            end_line: self.line_index.line_index(last.end()),
+            severity: if rule_id == "SKY-U003" {
+                "MEDIUM"
+            } else {
+                "LOW"
+            }
+            .to_string(),
+        });
+    }
+}
+
+/// Returns the nested statement blocks of a compound statement.
+fn child_blocks(stmt: &Stmt) -> Vec<&[Stmt]> {
+    match stmt {
+        Stmt::FunctionDef(node) => vec![&node.body],
+        Stmt::AsyncFunctionDef(node) => vec![&node.body],
+        Stmt::ClassDef(node) => vec![&node.body],
+        Stmt::If(node) => vec![&node.body, &node.orelse],
+        Stmt::While(node) => vec![&node.body, &node.orelse],
+        Stmt::For(node) => vec![&node.body, &node.orelse],
+        Stmt::AsyncFor(node) => vec![&node.body, &node.orelse],
+        Stmt::With(node) => vec![&node.body],
+        Stmt::AsyncWith(node) => vec![&node.body],
+        Stmt::Try(node) => try_blocks(&node.body, &node.handlers, &node.orelse, &node.finalbody),
+        Stmt::TryStar(node) => {
+            try_blocks(&node.body, &node.handlers, &node.orelse, &node.finalbody)
+        }
+        Stmt::Match(node) => node.cases.iter().map(|case| case.body.as_slice()).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Returns the blocks of a `try` statement, in source order.
+fn try_blocks<'s>(
+    body: &'s [Stmt],
+    handlers: &'s [ExceptHandler],
+    orelse: &'s [Stmt],
+    finalbody: &'s [Stmt],
+) -> Vec<&'s [Stmt]> {
+    let mut blocks = vec![body];
+    for ExceptHandler::ExceptHandler(handler) in handlers {
+        blocks.push(&handler.body);
+    }
+    blocks.push(orelse);
+    blocks.push(finalbody);
+    blocks
+}
+
+/// Returns the name of the exit function called by an expression statement, if any.
+fn exit_call(expr: &Expr) -> Option<String> {
+    let Expr::Call(call) = expr else {
+        return None;
+    };
+    dotted_name(&call.func).filter(|name| EXIT_CALLS.contains(&name.as_str()))
+}
+
+/// Describes the statement that makes the code following it unreachable.
+fn exit_description(stmt: &Stmt) -> String {
+    match stmt {
+        Stmt::Return(_) => "'return'".to_string(),
+        Stmt::Raise(_) => "'raise'".to_string(),
+        Stmt::Break(_) => "'break'".to_string(),
+        Stmt::Continue(_) => "'continue'".to_string(),
+        Stmt::Expr(node) => match exit_call(&node.value) {
+            Some(name) => format!("'{}()'", name),
+            None => "a statement that never completes".to_string(),
+        },
+        Stmt::While(_) => "an infinite loop".to_string(),
+        _ => "a block that always exits".to_string(),
+    }
+}
+
+/// Evaluates a condition that does not depend on any runtime value
+/// (e.g. `False`, `0`, `not True`). Returns `None` if it is not constant.
+fn constant_truth(expr: &Expr) -> Option<bool> {
+    match expr {
+        Expr::Constant(node) => match &node.value {
+            ast::Constant::Bool(value) => Some(*value),
+            ast::Constant::None => Some(false),
+            ast::Constant::Int(value) => Some(*value != ast::bigint::BigInt::from(0)),
+            ast::Constant::Str(value) => Some(!value.is_empty()),
+            ast::Constant::Bytes(value) => Some(!value.is_empty()),
+            _ => None,
+        },
+        Expr::UnaryOp(node) if node.op == ast::UnaryOp::Not => {
+            constant_truth(&node.operand).map(|value| !value)
+        }
+        _ => None,
+    }
+}
+
+/// Whether a loop body contains a `break` that exits this loop
+/// (rather than a nested loop, function or class).
+fn contains_break(body: &[Stmt]) -> bool {
+    body.iter().any(|stmt| match stmt {
+        Stmt::Break(_) => true,
+        // A `break` in the body of a nested loop exits that loop,
+        // but one in its `else` clause exits ours.
+        Stmt::For(node) => contains_break(&node.orelse),
+        Stmt::AsyncFor(node) => contains_break(&node.orelse),
+        Stmt::While(node) => contains_break(&node.orelse),
+        Stmt::FunctionDef(_) | Stmt::AsyncFunctionDef(_) | Stmt::ClassDef(_) => false,
+        _ => child_blocks(stmt).into_iter().any(contains_break),
+    })
+}
+
+/// Whether a `match` pattern matches every subject (`case _:` or `case name:`).
+fn is_irrefutable(pattern: &ast::Pattern) -> bool {
+    match pattern {
+        ast::Pattern::MatchAs(node) => node.pattern.as_deref().is_none_or(is_irrefutable),
+        ast::Pattern::MatchOr(node) => node.patterns.iter().any(is_irrefutable),
+        _ => false,
+    }
+}
+
+/// Returns the exception classes caught by an `except` clause.
+/// Entries that are not plain (dotted) names are `None`.
+fn handler_types(expr: &Expr) -> Vec<Option<String>> {
+    match expr {
+        Expr::Tuple(tuple) => tuple.elts.iter().map(dotted_name).collect(),
+        _ => vec![dotted_name(expr)],
+    }
+    .into_iter()
+    .map(|name| name.map(|name| canonical_exception(&name).to_string()))
+    .collect()
+}
+
+/// Finds an earlier handler that catches everything a later handler does.
+///
+/// `caught` holds the classes caught by the earlier handlers (`None` for a bare `except:`),
+/// and `types` the classes caught by the later one (`None` for a bare `except:`).
+/// Returns a description of the earlier handler.
+fn shadowing_handler(
+    caught: &[Option<String>],
+    types: Option<&Vec<Option<String>>>,
+) -> Option<String> {
+    if caught.iter().any(Option::is_none) {
+        return Some("everything".to_string());
+    }
+    // A later bare `except:` catches more than any earlier named handler.
+    let types = types?;
+    if types.is_empty() {
+        return None;
+    }
+    let mut shadowing = None;
+    for ty in types {
+        // Classes that cannot be resolved to a name are never considered shadowed.
+        let ty = ty.as_deref()?;
+        let base = caught
+            .iter()
+            .flatten()
+            .find(|base| is_exception_subclass(ty, base))?;
+        shadowing.get_or_insert_with(|| format!("'{}'", base));
+    }
+    shadowing
+}
+
+/// Maps aliases of built-in exception classes to the class itself.
+fn canonical_exception(name: &str) -> &str {
+    match name {
+        "IOError" | "EnvironmentError" | "socket.error" | "select.error" => "OSError",
+        _ => name.strip_prefix("builtins.").unwrap_or(name),
+    }
+}
+
+/// Whether catching `base` also catches `class`.
+///
+/// Every exception class derives from `BaseException`. Otherwise only the built-in
+/// hierarchy is known, so project exception classes are only caught by themselves.
+fn is_exception_subclass(class: &str, base: &str) -> bool {
+    if base == "BaseException" {
+        return true;
+    }
+    let mut current = class;
+    loop {
+        if current == base {
+            return true;
+        }
+        match EXCEPTION_BASES.iter().find(|(name, _)| *name == current) {
+            Some((_, parent)) => current = parent,
+            None => return false,
+        }
+    }
+}
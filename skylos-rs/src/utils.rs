@@ -1,5 +1,5 @@
 use rustpython_ast::TextSize;
-use std::collections::HashSet;
+use std::path::{Component, Path};
 
 /// A utility struct to convert byte offsets to line numbers.
 ///
@@ -34,17 +34,104 @@ impl LineIndex {
     }
 }
 
-/// Detects lines with `# pragma: no skylos` comment.
+/// Computes the fully qualified (dotted) module name of a Python file.
 ///
-/// Returns a set of line numbers (1-indexed) that should be ignored by the analyzer.
-/// This allows users to suppress false positives or intentionally ignore specific lines.
-pub fn get_ignored_lines(source: &str) -> HashSet<usize> {
-    source
-        .lines()
-        .enumerate()
-        .filter(|(_, line)| line.contains("pragma: no skylos"))
-        .map(|(i, _)| i + 1)
-        .collect()
+/// Python resolves a module by walking up through directories that contain an
+/// `__init__.py`, so `pkg/a/utils.py` becomes `pkg.a.utils` no matter where the scan
+/// started, and `pkg/a/__init__.py` becomes `pkg.a`.
+/// Files that are not part of a regular package (scripts, namespace packages) are
+/// named by their path relative to `root`, dropping a leading `src/` directory so
+/// that `src/` layouts produce the same names as an installed package.
+pub fn module_name_from_path(root: &Path, path: &Path) -> String {
+    let mut parts: Vec<String> = Vec::new();
+
+    // The file itself. `__init__.py` names its containing package.
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if stem != "__init__" {
+        parts.push(stem);
+    }
+
+    // Walk up through regular packages (directories with an `__init__.py`).
+    let mut in_package = false;
+    let mut dir = path.parent();
+    while let Some(current) = dir {
+        let Some(name) = current.file_name() else {
+            break;
+        };
+        if !current.join("__init__.py").is_file() {
+            break;
+        }
+        in_package = true;
+        parts.push(name.to_string_lossy().to_string());
+        dir = current.parent();
+    }
+
+    // Not inside a package: fall back to the path relative to the project root.
+    if !in_package {
+        if let Some(rel) = path.parent().and_then(|p| p.strip_prefix(root).ok()) {
+            let mut dirs: Vec<String> = rel
+                .components()
+                .filter_map(|c| match c {
+                    Component::Normal(name) => Some(name.to_string_lossy().to_string()),
+                    _ => None,
+                })
+                .collect();
+            if dirs.first().map(String::as_str) == Some("src") {
+                dirs.remove(0);
+            }
+            parts.extend(dirs.into_iter().rev());
+        }
+    }
+
+    parts.reverse();
+    parts.join(".")
+}
+
+/// Resolves the absolute module named by an import statement.
+///
+/// `level` is the number of leading dots of a relative import (`from ..x import y` has
+/// level 2). Relative imports are resolved against the package of `module_name`, which
+/// is the module itself when the importing file is an `__init__.py`.
+/// Returns `None` when the import climbs above the top-level package.
+pub fn resolve_import_module(
+    module_name: &str,
+    is_package: bool,
+    level: usize,
+    module: Option<&str>,
+) -> Option<String> {
+    if level == 0 {
+        return module.map(str::to_string);
+    }
+
+    let mut parts: Vec<&str> = if module_name.is_empty() {
+        Vec::new()
+    } else {
+        module_name.split('.').collect()
+    };
+    // A plain module's package is its parent; a package is its own package.
+    if !is_package {
+        parts.pop()?;
+    }
+    // Every extra dot climbs one package up.
+    for _ in 1..level {
+        parts.pop()?;
+    }
+    if let Some(module) = module {
+        parts.push(module);
+    }
+    Some(parts.join("."))
+}
+
+/// Qualifies a name with its module name (if any).
+pub fn qualify(module_name: &str, name: &str) -> String {
+    if module_name.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", module_name, name)
+    }
 }
 
 #[cfg(test)]
@@ -52,32 +139,37 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_pragma_detection() {
-        let source = r#"
-def used_function():
-    return 42
-
-def unused_function():  # pragma: no skylos
-    return "ignored"
-
-class MyClass:  # pragma: no skylos
-    pass
-"#;
-        let ignored = get_ignored_lines(source);
-
-        // Lines 5 and 8 should be ignored (1-indexed)
-        assert!(ignored.contains(&5), "Should detect pragma on line 5");
-        assert!(ignored.contains(&8), "Should detect pragma on line 8");
-        assert_eq!(ignored.len(), 2, "Should find exactly 2 pragma lines");
+    fn test_module_name_from_path() {
+        let root = Path::new("/nonexistent/project");
+        assert_eq!(
+            module_name_from_path(root, &root.join("scripts").join("tool.py")),
+            "scripts.tool"
+        );
+        assert_eq!(
+            module_name_from_path(root, &root.join("src").join("app").join("main.py")),
+            "app.main"
+        );
+        assert_eq!(module_name_from_path(root, &root.join("setup.py")), "setup");
     }
 
     #[test]
-    fn test_no_pragmas() {
-        let source = r#"
-def regular_function():
-    return 42
-"#;
-        let ignored = get_ignored_lines(source);
-        assert_eq!(ignored.len(), 0, "Should find no pragma lines");
+    fn test_resolve_import_module() {
+        assert_eq!(
+            resolve_import_module("pkg.a.utils", false, 0, Some("os.path")),
+            Some("os.path".to_string())
+        );
+        assert_eq!(
+            resolve_import_module("pkg.a.utils", false, 1, Some("models")),
+            Some("pkg.a.models".to_string())
+        );
+        assert_eq!(
+            resolve_import_module("pkg.a.utils", false, 2, None),
+            Some("pkg".to_string())
+        );
+        assert_eq!(
+            resolve_import_module("pkg.a", true, 1, Some("utils")),
+            Some("pkg.a.utils".to_string())
+        );
+        assert_eq!(resolve_import_module("utils", false, 2, None), None);
     }
 }
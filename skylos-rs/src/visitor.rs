@@ -1,8 +1,88 @@
+use crate::interner::{FileId, Interner, Symbol};
+use crate::scope::{BindingKind, Scope, ScopeKind, UnreadDefinition};
 use crate::utils::LineIndex;
-use rustpython_ast::{self as ast, Expr, Stmt};
+use rustpython_ast::{self as ast, Expr, Ranged, Stmt};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::path::PathBuf;
 
+/// Decorators that wrap a function without registering it as a callback.
+/// Any other decorator may call the function with a signature it does not control.
+const PLAIN_DECORATORS: &[&str] = &[
+    "staticmethod",
+    "classmethod",
+    "property",
+    "cached_property",
+    "setter",
+    "getter",
+    "deleter",
+    "wraps",
+    "cache",
+    "lru_cache",
+    "contextmanager",
+    "asynccontextmanager",
+];
+
+/// Decorators marking a signature that is declared but not implemented.
+const STUB_DECORATORS: &[&str] = &[
+    "abstractmethod",
+    "abstractclassmethod",
+    "abstractstaticmethod",
+    "abstractproperty",
+    "overload",
+];
+
+/// Leading positional parameters of well-known framework callbacks.
+/// Such callbacks must accept every parameter, whether they use it or not.
+const CALLBACK_SIGNATURES: &[&[&str]] = &[
+    // Django signal receivers.
+    &["sender"],
+    // Django/DRF views and pytest fixtures.
+    &["request"],
+    // `signal.signal` handlers.
+    &["signum", "frame"],
+    // Click option callbacks.
+    &["ctx", "param", "value"],
+    // WSGI applications.
+    &["environ", "start_response"],
+    // ASGI applications.
+    &["scope", "receive", "send"],
+    // GUI and event callbacks.
+    &["event"],
+];
+
+/// Class decorators that generate `__init__`, comparison and `__repr__` methods from the
+/// class-level fields (dataclasses and attrs).
+const SCHEMA_DECORATORS: &[&str] = &[
+    "dataclass",
+    "dataclasses.dataclass",
+    "pydantic.dataclasses.dataclass",
+    "attr.s",
+    "attr.attrs",
+    "attr.define",
+    "attr.frozen",
+    "attr.mutable",
+    "attrs.define",
+    "attrs.frozen",
+    "attrs.mutable",
+    "define",
+    "frozen",
+    "mutable",
+];
+
+/// Base classes whose class-level fields are read by generated or library code
+/// (pydantic models, typed tuples and dicts).
+const SCHEMA_BASES: &[&str] = &[
+    "BaseModel",
+    "BaseSettings",
+    "RootModel",
+    "NamedTuple",
+    "TypedDict",
+];
+
+/// Base classes of enumerations.
+const ENUM_BASES: &[&str] = &["Enum", "IntEnum", "StrEnum", "Flag", "IntFlag", "ReprEnum"];
+
 /// Represents a defined entity (function, class, variable, import) in the Python code.
 /// This struct holds metadata about the definition, including its location and confidence.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,10 +93,15 @@ pub struct Definition {
     pub full_name: String,
     /// The simple name (last part of the full name).
     pub simple_name: String,
-    /// The type of definition ("function", "class", "method", "import", "variable").
+    /// The type of definition ("function", "class", "method", "import", "variable",
+    /// "parameter", "attribute").
     pub def_type: String,
-    /// The file path where this definition resides.
+    /// The file path where this definition resides. Only set on the definitions an
+    /// analysis reports; until then, `file_id` identifies the file.
     pub file: PathBuf,
+    /// The file where this definition resides, in the file list of the analysis.
+    #[serde(skip)]
+    pub file_id: FileId,
     /// The line number where this definition starts.
     pub line: usize,
     /// A confidence score (0-100) indicating how certain we are that this is unused.
@@ -28,8 +113,17 @@ pub struct Definition {
     pub is_exported: bool,
     /// Whether this definition is inside an `__init__.py` file.
     pub in_init: bool,
-    /// List of base classes if this is a class definition.
+    /// Whether this is a function-local name (e.g. a local variable).
+    /// Local names are resolved within their scope by the visitor, so references
+    /// elsewhere in the project never count towards them.
+    pub is_local: bool,
+    /// List of base classes if this is a class definition, as written
+    /// (e.g. `Base` or `ast.NodeVisitor`).
     pub base_classes: Vec<String>,
+    /// Why this definition is considered dead, if it is:
+    /// `"unreferenced"` when nothing refers to it, or `"referenced_from_dead_code"` when
+    /// it is only referenced from other dead code.
+    pub dead_reason: Option<String>,
 }
 
 impl Definition {
@@ -64,24 +158,160 @@ impl Definition {
     }
 }
 
+/// A definition replaced by a later binding of the same name before it is ever read,
+/// e.g. a function defined twice or an import shadowed by an assignment.
+#[derive(Debug, Clone, Serialize)]
+pub struct Redefinition {
+    /// The redefined name.
+    pub name: String,
+    /// Description of the issue.
+    pub message: String,
+    /// Unique rule identifier.
+    pub rule_id: String,
+    /// File where the issue was found.
+    pub file: PathBuf,
+    /// Line of the redefinition.
+    pub line: usize,
+    /// Line of the definition that is never read.
+    pub original_line: usize,
+    /// Severity level.
+    pub severity: String,
+}
+
+/// An import statement binding a local name to a module or module member.
+///
+/// For example `from pkg.mod import helper as h` binds `h` to `pkg.mod.helper`,
+/// and `import os.path` binds `os` to `os`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportBinding {
+    /// The name bound in the importing module (the `as` name if present).
+    pub local_name: String,
+    /// The absolute dotted name being imported, with relative imports resolved.
+    pub target: String,
+    /// The line number of the import statement.
+    pub line: usize,
+}
+
+/// When an import statement runs, which decides whether it can take part in an import
+/// cycle at runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ImportContext {
+    /// Runs when the module is imported (module or class level, including `try`/`if` blocks).
+    Module,
+    /// Runs only when the enclosing function is called.
+    Function,
+    /// Never runs: the import is in an `if TYPE_CHECKING:` block.
+    TypeChecking,
+}
+
+impl ImportContext {
+    /// Returns the name of the context as used in reports: `module-level`,
+    /// `function-local` or `type-checking`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ImportContext::Module => "module-level",
+            ImportContext::Function => "function-local",
+            ImportContext::TypeChecking => "type-checking",
+        }
+    }
+}
+
+/// Attributes collected for a class while its body is visited.
+struct ClassAttributes {
+    /// Qualified name of the class.
+    qualified_name: String,
+    /// Confidence (0-100) with which unused class-level fields are reported.
+    field_confidence: u8,
+    /// Attributes declared or assigned outside of plain class-level assignments, with the
+    /// line of their first definition and whether they are class-level (`cls.x`, `x: int`).
+    attributes: Vec<(String, usize, bool)>,
+}
+
+/// A reference to a name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Reference {
+    /// The referenced name, interned in the visitor's `symbols`.
+    pub name: Symbol,
+    /// Index into `definitions` of the enclosing function or class, or `None` for
+    /// module-level code.
+    pub scope: Option<u32>,
+    /// Whether the reference is only needed for typing: it occurs in a string annotation,
+    /// an `if TYPE_CHECKING:` block or an annotation that is never evaluated (local
+    /// variable annotations, or any with `from __future__ import annotations`).
+    pub typing_only: bool,
+    /// Whether the name is the attribute of an object we cannot resolve (`attr` in
+    /// `obj.attr`), so it may be any class member of that name.
+    pub is_attribute: bool,
+}
+
 /// The main visitor for collecting definitions and references from the AST.
 pub struct SkylosVisitor<'a> {
     /// Collected definitions.
     pub definitions: Vec<Definition>,
-    /// Collected references (name usage).
-    pub references: Vec<(String, PathBuf)>,
+    /// Collected references (name usage), in source order.
+    pub references: Vec<Reference>,
+    /// Names of the references, interned per file.
+    pub symbols: Interner,
+    /// Import bindings, used to resolve references across files.
+    pub imports: Vec<ImportBinding>,
+    /// Indices into `definitions` of imports made in `if TYPE_CHECKING:` blocks.
+    pub type_checking_imports: Vec<usize>,
     /// Names explicitly exported via `__all__`.
     pub exports: Vec<String>,
-    /// Dynamic imports detected.
+    /// For each entry of `exports`, the line it is listed on.
+    pub export_lines: Vec<usize>,
+    /// Star imports (`from mod import *`), with `*` as the local name and the
+    /// resolved module as the target. They are expanded once all modules are known.
+    pub star_imports: Vec<ImportBinding>,
+    /// Modules imported dynamically with a literal name
+    /// (`importlib.import_module("pkg.mod")`, `__import__("pkg")`).
     pub dynamic_imports: Vec<String>,
+    /// Full dotted names named by import statements: `a.b.c` for `import a.b.c`, and
+    /// `pkg.mod.name` for `from pkg.mod import name` (which may be a submodule).
+    pub imported_modules: Vec<String>,
+    /// For each entry of `imported_modules`, the line of the import statement.
+    pub import_lines: Vec<usize>,
+    /// For each entry of `imported_modules`, when the import statement runs.
+    pub import_contexts: Vec<ImportContext>,
+    /// String literals that look like dotted paths (`"pkg.mod.Class"`, `"pkg.mod:func"`),
+    /// which frameworks use to load modules by name (e.g. Django settings).
+    pub dotted_strings: Vec<String>,
+    /// Name prefixes accessed reflectively, e.g. `handle_` for `getattr(obj, f"handle_{x}")`.
+    /// Prefixes qualified with an object (`pkg.mod.C.handle_` for `self`, `mod.` for a module)
+    /// are resolved like references; bare prefixes match definitions by simple name.
+    pub dynamic_prefixes: Vec<String>,
+    /// Whether the module accesses names in a way that cannot be bounded
+    /// (`getattr(obj, name)`, `globals()[name]`, `eval`, `exec`).
+    pub has_dynamic_access: bool,
+    /// Definitions replaced before they were ever read.
+    pub redefinitions: Vec<Redefinition>,
     /// The path of the file being visited.
     pub file_path: PathBuf,
+    /// The ID of the file being visited, given to its definitions.
+    pub file_id: FileId,
     /// The module name derived from the file path.
     pub module_name: String,
-    /// Current scope stack (not fully used currently but good for tracking nested scopes).
-    pub current_scope: Vec<String>,
+    /// Stack of lexical scopes (module, class, function, lambda, comprehension).
+    /// Used to resolve local variables and report the ones that are never read.
+    scopes: Vec<Scope>,
     /// Stack of class names to track current class context.
     pub class_stack: Vec<String>,
+    /// Stack of indices into `definitions` for the functions/classes being visited.
+    /// References are attributed to the innermost one.
+    def_stack: Vec<usize>,
+    /// Nesting depth of typing-only contexts (see `Reference::typing_only`).
+    typing_depth: usize,
+    /// Whether an annotation is being visited, so string constants are parsed as types.
+    in_annotation: bool,
+    /// Whether the module has `from __future__ import annotations`.
+    future_annotations: bool,
+    /// Attributes of the classes being visited, innermost last.
+    class_attributes: Vec<ClassAttributes>,
+    /// The `if`/`try`/`match` branches being visited, outermost first, as
+    /// `(statement id, branch index)` pairs.
+    branches: Vec<(usize, usize)>,
+    /// Number of `if`/`try`/`match` statements seen, used to give each one an id.
+    branch_statements: usize,
     /// Helper for line number mapping.
     pub line_index: &'a LineIndex,
 }
@@ -92,12 +322,32 @@ impl<'a> SkylosVisitor<'a> {
         Self {
             definitions: Vec::new(),
             references: Vec::new(),
+            symbols: Interner::new(),
+            imports: Vec::new(),
+            type_checking_imports: Vec::new(),
             exports: Vec::new(),
+            export_lines: Vec::new(),
+            star_imports: Vec::new(),
             dynamic_imports: Vec::new(),
+            imported_modules: Vec::new(),
+            import_lines: Vec::new(),
+            import_contexts: Vec::new(),
+            dotted_strings: Vec::new(),
+            dynamic_prefixes: Vec::new(),
+            has_dynamic_access: false,
+            redefinitions: Vec::new(),
             file_path,
+            file_id: FileId::default(),
+            scopes: vec![Scope::new(ScopeKind::Module, module_name.clone())],
             module_name,
-            current_scope: Vec::new(),
             class_stack: Vec::new(),
+            def_stack: Vec::new(),
+            typing_depth: 0,
+            in_annotation: false,
+            future_annotations: false,
+            class_attributes: Vec::new(),
+            branches: Vec::new(),
+            branch_statements: 0,
             line_index,
         }
     }
@@ -115,7 +365,7 @@ impl<'a> SkylosVisitor<'a> {
         line: usize,
         base_classes: Vec<String>,
     ) {
-        let simple_name = name.split('.').last().unwrap_or(&name).to_string();
+        let simple_name = name.rsplit('.').next().unwrap_or(&name).to_string();
         let in_init = self.file_path.ends_with("__init__.py");
 
         // GENERIC HEURISTICS (No hardcoded project names)
@@ -149,32 +399,503 @@ impl<'a> SkylosVisitor<'a> {
             full_name: name,
             simple_name,
             def_type: def_type.to_string(),
-            file: self.file_path.clone(),
+            file: PathBuf::new(),
+            file_id: self.file_id,
             line,
             confidence: 100,
             references,
             is_exported: is_implicitly_used,
             in_init,
+            is_local: false,
             base_classes,
+            dead_reason: None,
         };
 
         self.definitions.push(definition);
     }
 
+    /// Records the dotted name of an import statement, with its line and context.
+    fn add_imported_module(&mut self, name: String, line: usize) {
+        let context = if self.typing_depth > 0 {
+            ImportContext::TypeChecking
+        } else if self
+            .scopes
+            .iter()
+            .any(|scope| matches!(scope.kind, ScopeKind::Function | ScopeKind::Lambda))
+        {
+            ImportContext::Function
+        } else {
+            ImportContext::Module
+        };
+        self.imported_modules.push(name);
+        self.import_lines.push(line);
+        self.import_contexts.push(context);
+    }
+
+    /// Records an import: a module-level "import" definition plus its binding.
+    ///
+    /// The definition is qualified with the module name (e.g. `pkg.mod.os`) so that
+    /// identical imports in different files are tracked separately. `imported` is the
+    /// full dotted name being imported (`os.path` for `import os.path`, which binds `os`).
+    fn add_import(&mut self, local_name: String, target: String, imported: &str, line: usize) {
+        let qualified_name = if self.module_name.is_empty() {
+            local_name.clone()
+        } else {
+            format!("{}.{}", self.module_name, local_name)
+        };
+        self.add_def(qualified_name, "import", line);
+        if self.typing_depth > 0 {
+            self.type_checking_imports.push(self.definitions.len() - 1);
+        }
+        self.add_imported_module(imported.to_string(), line);
+        self.bind_definition(
+            &local_name,
+            line,
+            "import",
+            Some(imported.to_string()),
+            false,
+        );
+        self.imports.push(ImportBinding {
+            local_name,
+            target,
+            line,
+        });
+    }
+
     /// Records a reference to a name.
-    pub fn add_ref(&mut self, name: String) {
-        self.references.push((name, self.file_path.clone()));
+    pub fn add_ref(&mut self, name: &str) {
+        self.push_ref(name, false);
+    }
+
+    /// Records a reference to the attribute of an object of unknown type (`obj.attr`).
+    pub fn add_attribute_ref(&mut self, name: &str) {
+        self.push_ref(name, true);
+    }
+
+    fn push_ref(&mut self, name: &str, is_attribute: bool) {
+        let name = self.symbols.intern(name);
+        self.references.push(Reference {
+            name,
+            scope: self.def_stack.last().map(|&i| i as u32),
+            typing_only: self.typing_depth > 0,
+            is_attribute,
+        });
+    }
+
+    /// Returns the referenced names, in source order.
+    pub fn reference_names(&self) -> impl Iterator<Item = &str> {
+        self.references
+            .iter()
+            .map(|reference| self.symbols.resolve(reference.name))
+    }
+
+    /// Records a read of a name in the current scope.
+    fn add_load(&mut self, name: &str) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.add_load(name, 1);
+        }
+    }
+
+    /// Binds a name in the current scope.
+    fn bind_name(&mut self, name: &str, line: usize, kind: BindingKind) {
+        self.bind_name_in(self.scopes.len() - 1, name, line, kind, None);
+    }
+
+    /// Binds a name defined by a `def`, `class` or `import` statement in the current
+    /// scope, remembering the definition until the name is read.
+    fn bind_definition(
+        &mut self,
+        name: &str,
+        line: usize,
+        kind: &'static str,
+        target: Option<String>,
+        allows_redefinition: bool,
+    ) {
+        let definition = UnreadDefinition {
+            kind,
+            line,
+            target,
+            branches: self.branches.clone(),
+            allows_redefinition,
+        };
+        self.bind_name_in(
+            self.scopes.len() - 1,
+            name,
+            line,
+            BindingKind::Other,
+            Some(definition),
+        );
+    }
+
+    /// Binds a name in the scope at `index`, honoring `global` and `nonlocal`.
+    ///
+    /// Module-level variables are recorded as definitions right away, since they can be
+    /// used from other modules. Function-local ones are reported when their scope ends.
+    /// Binding a name whose previous definition was never read reports a redefinition.
+    fn bind_name_in(
+        &mut self,
+        index: usize,
+        name: &str,
+        line: usize,
+        kind: BindingKind,
+        definition: Option<UnreadDefinition>,
+    ) {
+        let scope = &self.scopes[index];
+        // `nonlocal x` assigns the enclosing function's variable; nothing new is bound.
+        if scope.is_nonlocal(name) {
+            return;
+        }
+        // `global x` binds the module-level name.
+        let index = if scope.is_global(name) { 0 } else { index };
+
+        let scope = &mut self.scopes[index];
+        let previous = scope.take_unread_definition(name);
+        let is_new = scope.bind(name, line, kind);
+        if is_new && scope.kind == ScopeKind::Module && kind == BindingKind::Variable && name != "_"
+        {
+            let qualified_name = if self.module_name.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}.{}", self.module_name, name)
+            };
+            self.add_def(qualified_name, "variable", line);
+        }
+
+        if let Some(previous) = previous {
+            self.check_redefinition(name, line, previous, definition.as_ref());
+        }
+        if let Some(definition) = definition {
+            self.scopes[index].add_unread_definition(name, definition);
+        }
+    }
+
+    /// Reports a name bound again while its previous definition was never read (F811).
+    ///
+    /// Overloads and definitions registered by decorators are meant to be redefined.
+    /// Bindings in different branches (`try`/`except ImportError` fallbacks, `if`/`else`),
+    /// and redefinitions that only happen conditionally, leave the previous definition in
+    /// use. Importing a submodule (`import os.path` after `import os`) rebinds the same
+    /// package.
+    fn check_redefinition(
+        &mut self,
+        name: &str,
+        line: usize,
+        previous: UnreadDefinition,
+        definition: Option<&UnreadDefinition>,
+    ) {
+        if previous.allows_redefinition
+            || name == "_"
+            || !previous.branches.starts_with(&self.branches)
+        {
+            return;
+        }
+        let new_target = definition.and_then(|d| d.target.as_deref());
+        if let (Some(old), Some(new)) = (previous.target.as_deref(), new_target) {
+            let is_submodule =
+                |module: &str, package: &str| module.starts_with(&format!("{}.", package));
+            if is_submodule(old, new) || is_submodule(new, old) {
+                return;
+            }
+        }
+        self.redefinitions.push(Redefinition {
+            name: name.to_string(),
+            message: format!(
+                "Redefinition of unused {} '{}' from line {}",
+                previous.kind, name, previous.line
+            ),
+            rule_id: "SKY-R001".to_string(),
+            file: self.file_path.clone(),
+            line,
+            original_line: previous.line,
+            severity: "MEDIUM".to_string(),
+        });
+    }
+
+    /// Binds the names in an assignment target (`x`, `a, *b`, `[x, y]`).
+    ///
+    /// Attribute and subscript targets bind nothing, but their base expressions are reads.
+    fn bind_target(&mut self, target: &Expr, line: usize) {
+        match target {
+            Expr::Name(node) => self.bind_name(node.id.as_str(), line, BindingKind::Variable),
+            Expr::Tuple(node) => {
+                for elt in &node.elts {
+                    self.bind_target(elt, line);
+                }
+            }
+            Expr::List(node) => {
+                for elt in &node.elts {
+                    self.bind_target(elt, line);
+                }
+            }
+            Expr::Starred(node) => self.bind_target(&node.value, line),
+            Expr::Attribute(node) => self.visit_expr(&node.value),
+            Expr::Subscript(node) => {
+                self.visit_expr(&node.value);
+                self.visit_expr(&node.slice);
+            }
+            _ => self.visit_expr(target),
+        }
+    }
+
+    /// Binds the parameters of a function or lambda in the current scope.
+    ///
+    /// If `has_receiver` is set, the first positional parameter is the method receiver
+    /// (`self`/`cls`). It is bound for name resolution but never reported as unused.
+    fn bind_arguments(&mut self, args: &ast::Arguments, has_receiver: bool) {
+        let line = |arg: &ast::Arg| self.line_index.line_index(arg.range.start());
+        let mut params: Vec<(String, usize)> = Vec::new();
+        for arg in args
+            .posonlyargs
+            .iter()
+            .chain(&args.args)
+            .chain(&args.kwonlyargs)
+        {
+            params.push((arg.def.arg.to_string(), line(&arg.def)));
+        }
+        for arg in args.vararg.iter().chain(&args.kwarg) {
+            params.push((arg.arg.to_string(), line(arg)));
+        }
+        let receiver_name = if has_receiver {
+            args.posonlyargs
+                .iter()
+                .chain(&args.args)
+                .next()
+                .map(|arg| arg.def.arg.to_string())
+        } else {
+            None
+        };
+        for (name, line) in params {
+            let kind = if receiver_name.as_deref() == Some(name.as_str()) {
+                BindingKind::Other
+            } else {
+                BindingKind::Parameter
+            };
+            self.bind_name(&name, line, kind);
+        }
+    }
+
+    /// Enters a new scope.
+    fn push_scope(&mut self, kind: ScopeKind, qualified_name: String) {
+        self.scopes.push(Scope::new(kind, qualified_name));
+    }
+
+    /// Leaves the current scope.
+    ///
+    /// Local variables of function-like scopes and parameters of functions are recorded
+    /// as definitions, with the number of reads as their reference count. Reads of names
+    /// that are not bound locally are passed on to the enclosing function scope (closures).
+    fn pop_scope(&mut self) {
+        let Some(scope) = self.scopes.pop() else {
+            return;
+        };
+
+        // Free variables resolve to the nearest enclosing function scope; class bodies
+        // are not visible from nested scopes.
+        if let Some(parent) = self
+            .scopes
+            .iter_mut()
+            .rev()
+            .find(|s| s.kind != ScopeKind::Class)
+        {
+            if parent.is_function_like() {
+                for (name, count) in scope.free_loads() {
+                    parent.add_load(name, count);
+                }
+            }
+        }
+
+        if !scope.is_function_like() {
+            return;
+        }
+
+        // `locals()` can read any local, so nothing in this scope is reported.
+        let uses_locals = scope.load_count("locals") > 0;
+
+        for binding in scope.bindings() {
+            // By convention `_` and `_`-prefixed locals are intentionally unused.
+            if binding.name.starts_with('_') {
+                continue;
+            }
+            // Lambda parameters are dictated by whatever calls the lambda.
+            let def_type = match binding.kind {
+                BindingKind::Variable => "variable",
+                BindingKind::Parameter if scope.kind == ScopeKind::Function => "parameter",
+                _ => continue,
+            };
+            let qualified_name = format!("{}.{}", scope.qualified_name, binding.name);
+            self.add_def(qualified_name, def_type, binding.line);
+            if let Some(def) = self.definitions.last_mut() {
+                let loads = scope.load_count(&binding.name);
+                def.references = if uses_locals { loads.max(1) } else { loads };
+                def.is_local = true;
+                if binding.kind == BindingKind::Parameter {
+                    def.confidence = scope.parameter_confidence;
+                }
+            }
+        }
+    }
+
+    /// Returns the names bound at module level (definitions, imports and variables).
+    pub fn module_bindings(&self) -> impl Iterator<Item = &str> + '_ {
+        self.scopes[0].bindings().iter().map(|b| b.name.as_str())
+    }
+
+    /// Whether an assignment target is the module's `__all__`.
+    fn is_module_all(&self, target: &Expr) -> bool {
+        self.scopes.len() == 1
+            && matches!(target, Expr::Name(node) if node.id.as_str() == "__all__")
+    }
+
+    /// Records the string entries of a list or tuple assigned to (or added to) `__all__`.
+    /// Concatenations (`["a"] + ["b"]`) are followed; anything else is not a literal
+    /// list of names and is skipped.
+    fn add_exports(&mut self, value: &Expr) {
+        let elts = match value {
+            Expr::List(node) => &node.elts,
+            Expr::Tuple(node) => &node.elts,
+            Expr::BinOp(node) if matches!(node.op, ast::Operator::Add) => {
+                self.add_exports(&node.left);
+                self.add_exports(&node.right);
+                return;
+            }
+            _ => return,
+        };
+        for elt in elts {
+            self.add_export(elt);
+        }
+    }
+
+    /// Records a single `__all__` entry if it is a string literal.
+    fn add_export(&mut self, elt: &Expr) {
+        if let Expr::Constant(constant) = elt {
+            if let ast::Constant::Str(s) = &constant.value {
+                self.exports.push(s.to_string());
+                self.export_lines
+                    .push(self.line_index.line_index(constant.range.start()));
+            }
+        }
+    }
+
+    /// Whether an assignment target is the `__slots__` of the class being visited.
+    fn is_class_slots(&self, target: &Expr) -> bool {
+        self.scopes
+            .last()
+            .is_some_and(|s| s.kind == ScopeKind::Class)
+            && matches!(target, Expr::Name(node) if node.id.as_str() == "__slots__")
+    }
+
+    /// Records the string entries of `__slots__` as instance attributes.
+    /// Other entries are visited as ordinary expressions.
+    fn add_slots(&mut self, value: &Expr) {
+        let elts = match value {
+            Expr::List(node) => &node.elts,
+            Expr::Tuple(node) => &node.elts,
+            _ => std::slice::from_ref(value),
+        };
+        for elt in elts {
+            match string_literal(elt) {
+                Some(name) => {
+                    let line = self.line_index.line_index(elt.range().start());
+                    self.add_attribute(name, line, false);
+                }
+                None => self.visit_expr(elt),
+            }
+        }
+    }
+
+    /// Records the attributes assigned through `self.x = ...` (instance attributes) and
+    /// `cls.x = ...` (class attributes) in an assignment target.
+    fn add_attribute_targets(&mut self, target: &Expr, line: usize) {
+        match target {
+            Expr::Attribute(node) => {
+                let Expr::Name(base) = &*node.value else {
+                    return;
+                };
+                match base.id.as_str() {
+                    "self" => self.add_attribute(node.attr.as_str(), line, false),
+                    "cls" => self.add_attribute(node.attr.as_str(), line, true),
+                    _ => {}
+                }
+            }
+            Expr::Tuple(node) => {
+                for elt in &node.elts {
+                    self.add_attribute_targets(elt, line);
+                }
+            }
+            Expr::List(node) => {
+                for elt in &node.elts {
+                    self.add_attribute_targets(elt, line);
+                }
+            }
+            Expr::Starred(node) => self.add_attribute_targets(&node.value, line),
+            _ => {}
+        }
+    }
+
+    /// Records an attribute of the innermost class being visited, unless it was
+    /// already recorded.
+    fn add_attribute(&mut self, name: &str, line: usize, is_class_level: bool) {
+        let Some(class) = self.class_attributes.last_mut() else {
+            return;
+        };
+        if !class.attributes.iter().any(|(n, _, _)| n == name) {
+            class
+                .attributes
+                .push((name.to_string(), line, is_class_level));
+        }
+    }
+
+    /// Records the attributes of the class whose body was just visited as definitions.
+    ///
+    /// Class-level assignments come first, followed by the attributes that are only
+    /// declared (annotations, `__slots__`) or assigned through `self`/`cls`. Names bound
+    /// by methods, nested classes or imports in the class body are not attributes, and
+    /// dunder names are part of a protocol.
+    fn add_class_attributes(&mut self) {
+        let (Some(class), Some(scope)) = (self.class_attributes.pop(), self.scopes.last()) else {
+            return;
+        };
+        let mut seen = HashSet::new();
+        let mut attributes = Vec::new();
+        for binding in scope.bindings() {
+            if binding.kind == BindingKind::Variable {
+                attributes.push((binding.name.clone(), binding.line, class.field_confidence));
+            }
+            seen.insert(binding.name.clone());
+        }
+        for (name, line, is_class_level) in class.attributes {
+            if seen.insert(name.clone()) {
+                let confidence = if is_class_level {
+                    class.field_confidence
+                } else {
+                    100
+                };
+                attributes.push((name, line, confidence));
+            }
+        }
+
+        for (name, line, confidence) in attributes {
+            if name == "_" || (name.starts_with("__") && name.ends_with("__")) {
+                continue;
+            }
+            let qualified_name = format!("{}.{}", class.qualified_name, name);
+            self.add_def(qualified_name, "attribute", line);
+            if let Some(def) = self.definitions.last_mut() {
+                def.confidence = confidence;
+            }
+        }
     }
 
     /// Constructs a qualified name based on the current module and class stack.
     fn get_qualified_name(&self, name: &str) -> String {
-        let mut parts = Vec::new();
-        if !self.module_name.is_empty() {
-            parts.push(self.module_name.clone());
+        let mut qualified = self.module_name.clone();
+        for part in self.class_stack.iter().map(String::as_str).chain([name]) {
+            if !qualified.is_empty() {
+                qualified.push('.');
+            }
+            qualified.push_str(part);
         }
-        parts.extend(self.class_stack.clone());
-        parts.push(name.to_string());
-        parts.join(".")
+        qualified
     }
 
     /// Visits a statement node in the AST.
@@ -182,11 +903,35 @@ impl<'a> SkylosVisitor<'a> {
         match stmt {
             // Handle function definitions
             Stmt::FunctionDef(node) => {
-                self.visit_function_def(&node.name, &node.body, node.range.start());
+                self.visit_function_header(
+                    &node.decorator_list,
+                    &node.args,
+                    node.returns.as_deref(),
+                    &node.type_params,
+                );
+                self.visit_function_def(
+                    &node.name,
+                    &node.args,
+                    &node.body,
+                    &node.decorator_list,
+                    node.range.start(),
+                );
             }
             // Handle async function definitions
             Stmt::AsyncFunctionDef(node) => {
-                self.visit_function_def(&node.name, &node.body, node.range.start());
+                self.visit_function_header(
+                    &node.decorator_list,
+                    &node.args,
+                    node.returns.as_deref(),
+                    &node.type_params,
+                );
+                self.visit_function_def(
+                    &node.name,
+                    &node.args,
+                    &node.body,
+                    &node.decorator_list,
+                    node.range.start(),
+                );
             }
             // Handle class definitions
             Stmt::ClassDef(node) => {
@@ -194,50 +939,86 @@ impl<'a> SkylosVisitor<'a> {
                 let qualified_name = self.get_qualified_name(name.as_str());
                 let line = self.line_index.line_index(node.range.start());
 
-                // Extract base class names to check for inheritance patterns later.
-                let mut base_classes = Vec::new();
-                for base in &node.bases {
-                    match base {
-                        Expr::Name(base_name) => {
-                            base_classes.push(base_name.id.to_string());
-                        }
-                        Expr::Attribute(attr) => {
-                            base_classes.push(attr.attr.to_string());
-                        }
-                        _ => {}
-                    }
+                // Extract base class names to build the class hierarchy later.
+                // Generic bases (`Base[T]`) are recorded by their unsubscripted name.
+                let base_classes: Vec<String> = node
+                    .bases
+                    .iter()
+                    .filter_map(|base| match base {
+                        Expr::Subscript(sub) => dotted_name(&sub.value),
+                        _ => dotted_name(base),
+                    })
+                    .collect();
+
+                // Decorators, type parameters and keywords (`metaclass=...`) are evaluated
+                // in the enclosing scope, before the class is bound.
+                for decorator in &node.decorator_list {
+                    self.visit_expr(decorator);
+                }
+                self.visit_type_params(&node.type_params);
+                for keyword in &node.keywords {
+                    self.visit_expr(&keyword.value);
                 }
 
-                self.add_def_with_bases(qualified_name, "class", line, base_classes.clone());
+                let is_registered = node
+                    .decorator_list
+                    .iter()
+                    .filter_map(decorator_name)
+                    .any(|d| !PLAIN_DECORATORS.contains(&d.as_str()));
+                self.bind_definition(name.as_str(), line, "class", None, is_registered);
+                self.add_def_with_bases(
+                    qualified_name.clone(),
+                    "class",
+                    line,
+                    base_classes.clone(),
+                );
+                // Base classes and the class body are attributed to the class itself.
+                self.def_stack.push(self.definitions.len() - 1);
 
                 // Add references for base classes because inheriting uses them.
                 for base in &node.bases {
                     self.visit_expr(base);
                     // Handle simple base class names mapping to module refs
                     if let Expr::Name(base_name) = base {
-                        self.add_ref(base_name.id.to_string()); // Also add simple reference
+                        self.add_ref(base_name.id.as_str()); // Also add simple reference
                         if !self.module_name.is_empty() {
                             let qualified_base = format!("{}.{}", self.module_name, base_name.id);
-                            self.add_ref(qualified_base);
+                            self.add_ref(&qualified_base);
                         }
                     }
                 }
 
                 // Push class name to stack for nested definitions (methods/inner classes).
                 self.class_stack.push(name.to_string());
+                self.class_attributes.push(ClassAttributes {
+                    qualified_name: qualified_name.clone(),
+                    field_confidence: field_confidence(&node.decorator_list, &base_classes),
+                    attributes: Vec::new(),
+                });
+                self.push_scope(ScopeKind::Class, qualified_name);
                 // Visit class body.
                 for stmt in &node.body {
                     self.visit_stmt(stmt);
                 }
+                self.add_class_attributes();
                 // Pop class name after visiting body.
+                self.pop_scope();
                 self.class_stack.pop();
+                self.def_stack.pop();
             }
             // Handle imports
             Stmt::Import(node) => {
+                let line = self.line_index.line_index(node.range.start());
                 for alias in &node.names {
-                    let asname = alias.asname.as_ref().unwrap_or(&alias.name);
-                    let line = self.line_index.line_index(node.range.start());
-                    self.add_def(asname.to_string(), "import", line);
+                    // `import a.b.c` binds `a`; `import a.b as x` binds `x` to `a.b`.
+                    let (local_name, target) = match &alias.asname {
+                        Some(asname) => (asname.to_string(), alias.name.to_string()),
+                        None => {
+                            let head = alias.name.split('.').next().unwrap_or(&alias.name);
+                            (head.to_string(), head.to_string())
+                        }
+                    };
+                    self.add_import(local_name, target, &alias.name, line);
                 }
             }
             // Handle 'from ... import'
@@ -246,51 +1027,174 @@ impl<'a> SkylosVisitor<'a> {
                 // `from __future__ import ...` is a compiler directive, not a real import.
                 if let Some(module) = &node.module {
                     if module == "__future__" {
+                        // Annotations are no longer evaluated at runtime (PEP 563).
+                        if node.names.iter().any(|a| a.name.as_str() == "annotations") {
+                            self.future_annotations = true;
+                        }
                         // Skip adding definitions for future imports
                         return;
                     }
                 }
 
+                // Resolve relative imports (`from ..pkg import x`) to an absolute module.
+                let level = node.level.as_ref().map_or(0, |l| l.to_usize());
+                let base = crate::utils::resolve_import_module(
+                    &self.module_name,
+                    self.file_path.ends_with("__init__.py"),
+                    level,
+                    node.module.as_deref(),
+                );
+
                 let line = self.line_index.line_index(node.range.start());
                 for alias in &node.names {
                     let asname = alias.asname.as_ref().unwrap_or(&alias.name);
-                    self.add_def(asname.to_string(), "import", line);
+                    // Star imports bind no single name; they are expanded to the names
+                    // the target module exports once every module has been visited.
+                    if alias.name.as_str() == "*" {
+                        let qualified_name = if self.module_name.is_empty() {
+                            "*".to_string()
+                        } else {
+                            format!("{}.*", self.module_name)
+                        };
+                        self.add_def(qualified_name, "import", line);
+                        let target = match base.as_deref() {
+                            Some("") | None => self.module_name.clone(),
+                            Some(base) => base.to_string(),
+                        };
+                        self.add_imported_module(target.clone(), line);
+                        self.star_imports.push(ImportBinding {
+                            local_name: "*".to_string(),
+                            target,
+                            line,
+                        });
+                        continue;
+                    }
+                    let target = match base.as_deref() {
+                        Some("") | None => alias.name.to_string(),
+                        Some(base) => format!("{}.{}", base, alias.name),
+                    };
+                    let imported = target.clone();
+                    self.add_import(asname.to_string(), target, &imported, line);
                 }
             }
             // Handle assignments
             Stmt::Assign(node) => {
                 // Handle __all__ exports. `__all__ = ["a", "b"]` explicitly exports names.
-                if let Some(Expr::Name(target)) = node.targets.first() {
-                    if target.id.as_str() == "__all__" {
-                        if let Expr::List(list) = &*node.value {
-                            for elt in &list.elts {
-                                if let Expr::Constant(constant) = elt {
-                                    if let ast::Constant::Str(s) = &constant.value {
-                                        self.exports.push(s.to_string());
-                                    }
-                                }
-                            }
-                        }
+                if node.targets.iter().any(|t| self.is_module_all(t)) {
+                    self.add_exports(&node.value);
+                }
+                // `__slots__ = ("x", "y")` declares attributes rather than reading names.
+                if node.targets.iter().any(|t| self.is_class_slots(t)) {
+                    self.add_slots(&node.value);
+                } else {
+                    self.visit_expr(&node.value);
+                }
+                let line = self.line_index.line_index(node.range.start());
+                for target in &node.targets {
+                    self.add_attribute_targets(target, line);
+                    self.bind_target(target, line);
+                }
+            }
+            // Handle annotated assignments (`x: int = 5`).
+            // A bare annotation (`x: int`) declares a name but does not bind it.
+            Stmt::AnnAssign(node) => {
+                // Annotations of local variables are never evaluated.
+                let in_function = self.scopes.last().is_some_and(Scope::is_function_like);
+                self.visit_annotation(&node.annotation, !in_function);
+                let line = self.line_index.line_index(node.range.start());
+                self.add_attribute_targets(&node.target, line);
+                if let Some(value) = &node.value {
+                    if self.is_module_all(&node.target) {
+                        self.add_exports(value);
+                    }
+                    self.visit_expr(value);
+                    self.bind_target(&node.target, line);
+                } else if let Expr::Name(target) = &*node.target {
+                    // A bare annotation in a class body declares a field.
+                    if self
+                        .scopes
+                        .last()
+                        .is_some_and(|s| s.kind == ScopeKind::Class)
+                    {
+                        self.add_attribute(target.id.as_str(), line, true);
                     }
+                } else {
+                    // `self.x: int` binds nothing, but reads `self`.
+                    self.bind_target(&node.target, line);
+                }
+            }
+            // Type aliases (`type Vector = list[float]`) bind a name like an assignment.
+            Stmt::TypeAlias(node) => {
+                self.visit_type_params(&node.type_params);
+                // The value is evaluated lazily, for typing purposes only.
+                self.visit_annotation(&node.value, false);
+                let line = self.line_index.line_index(node.range.start());
+                self.bind_target(&node.name, line);
+            }
+            // Handle augmented assignments (`x += 1`).
+            // The implicit read only feeds the new value, so it does not count as a use.
+            Stmt::AugAssign(node) => {
+                // `__all__ += [...]` exports more names.
+                if matches!(node.op, ast::Operator::Add) && self.is_module_all(&node.target) {
+                    self.add_exports(&node.value);
                 }
                 self.visit_expr(&node.value);
+                let line = self.line_index.line_index(node.range.start());
+                self.bind_target(&node.target, line);
+            }
+            // `global`/`nonlocal` redirect assignments to an outer scope.
+            Stmt::Global(node) => {
+                if let Some(scope) = self.scopes.last_mut() {
+                    for name in &node.names {
+                        scope.declare_global(name.as_str());
+                    }
+                }
+            }
+            Stmt::Nonlocal(node) => {
+                if let Some(scope) = self.scopes.last_mut() {
+                    for name in &node.names {
+                        scope.declare_nonlocal(name.as_str());
+                    }
+                }
             }
             // Handle expression statements
             Stmt::Expr(node) => {
+                // `__all__.extend([...])` and `__all__.append("name")` export more names.
+                if let Expr::Call(call) = &*node.value {
+                    if let Expr::Attribute(method) = &*call.func {
+                        if self.is_module_all(&method.value) {
+                            match (method.attr.as_str(), call.args.first()) {
+                                ("extend", Some(arg)) => self.add_exports(arg),
+                                ("append", Some(arg)) => self.add_export(arg),
+                                _ => {}
+                            }
+                        }
+                    }
+                }
                 self.visit_expr(&node.value);
             }
             // Control Flow Handling - traverse bodies recursively
             Stmt::If(node) => {
                 self.visit_expr(&node.test);
-                for stmt in &node.body {
-                    self.visit_stmt(stmt);
+                // `if TYPE_CHECKING:` blocks only run under a type checker.
+                let is_type_checking = matches!(
+                    dotted_name(&node.test).as_deref(),
+                    Some("TYPE_CHECKING" | "typing.TYPE_CHECKING")
+                );
+                let statement = self.next_branch_statement();
+                if is_type_checking {
+                    self.typing_depth += 1;
                 }
-                for stmt in &node.orelse {
-                    self.visit_stmt(stmt);
+                self.visit_branch(statement, 0, &node.body);
+                if is_type_checking {
+                    self.typing_depth -= 1;
                 }
+                self.visit_branch(statement, 1, &node.orelse);
             }
             Stmt::For(node) => {
                 self.visit_expr(&node.iter);
+                let line = self.line_index.line_index(node.range.start());
+                self.bind_target(&node.target, line);
                 for stmt in &node.body {
                     self.visit_stmt(stmt);
                 }
@@ -300,6 +1204,8 @@ impl<'a> SkylosVisitor<'a> {
             }
             Stmt::AsyncFor(node) => {
                 self.visit_expr(&node.iter);
+                let line = self.line_index.line_index(node.range.start());
+                self.bind_target(&node.target, line);
                 for stmt in &node.body {
                     self.visit_stmt(stmt);
                 }
@@ -317,71 +1223,298 @@ impl<'a> SkylosVisitor<'a> {
                 }
             }
             Stmt::With(node) => {
+                let line = self.line_index.line_index(node.range.start());
                 for item in &node.items {
                     self.visit_expr(&item.context_expr);
+                    if let Some(vars) = &item.optional_vars {
+                        self.bind_target(vars, line);
+                    }
                 }
                 for stmt in &node.body {
                     self.visit_stmt(stmt);
                 }
             }
             Stmt::AsyncWith(node) => {
+                let line = self.line_index.line_index(node.range.start());
                 for item in &node.items {
                     self.visit_expr(&item.context_expr);
+                    if let Some(vars) = &item.optional_vars {
+                        self.bind_target(vars, line);
+                    }
                 }
                 for stmt in &node.body {
                     self.visit_stmt(stmt);
                 }
             }
             Stmt::Try(node) => {
-                for stmt in &node.body {
-                    self.visit_stmt(stmt);
+                self.visit_try(&node.body, &node.handlers, &node.orelse, &node.finalbody);
+            }
+            Stmt::TryStar(node) => {
+                self.visit_try(&node.body, &node.handlers, &node.orelse, &node.finalbody);
+            }
+            Stmt::Return(node) => {
+                if let Some(value) = &node.value {
+                    self.visit_expr(value);
                 }
-                for handler in &node.handlers {
-                    // Fix: Unwrap the Excepthandler enum
-                    if let ast::ExceptHandler::ExceptHandler(handler_node) = handler {
-                        if let Some(exc) = &handler_node.type_ {
-                            self.visit_expr(exc);
-                        }
-                        for stmt in &handler_node.body {
-                            self.visit_stmt(stmt);
-                        }
+            }
+            Stmt::Delete(node) => {
+                for target in &node.targets {
+                    self.visit_delete_target(target);
+                }
+            }
+            Stmt::Match(node) => {
+                self.visit_expr(&node.subject);
+                let statement = self.next_branch_statement();
+                for (i, case) in node.cases.iter().enumerate() {
+                    self.branches.push((statement, i));
+                    self.visit_pattern(&case.pattern);
+                    if let Some(guard) = &case.guard {
+                        self.visit_expr(guard);
                     }
+                    for stmt in &case.body {
+                        self.visit_stmt(stmt);
+                    }
+                    self.branches.pop();
                 }
-                for stmt in &node.orelse {
-                    self.visit_stmt(stmt);
+            }
+            Stmt::Raise(node) => {
+                if let Some(exc) = &node.exc {
+                    self.visit_expr(exc);
                 }
-                for stmt in &node.finalbody {
-                    self.visit_stmt(stmt);
+                if let Some(cause) = &node.cause {
+                    self.visit_expr(cause);
                 }
             }
-            Stmt::TryStar(node) => {
-                for stmt in &node.body {
-                    self.visit_stmt(stmt);
+            Stmt::Assert(node) => {
+                self.visit_expr(&node.test);
+                if let Some(msg) = &node.msg {
+                    self.visit_expr(msg);
                 }
-                for handler in &node.handlers {
-                    // Fix: Unwrap the Excepthandler enum
-                    if let ast::ExceptHandler::ExceptHandler(handler_node) = handler {
-                        if let Some(exc) = &handler_node.type_ {
-                            self.visit_expr(exc);
-                        }
-                        for stmt in &handler_node.body {
-                            self.visit_stmt(stmt);
-                        }
-                    }
+            }
+            Stmt::Pass(_) | Stmt::Break(_) | Stmt::Continue(_) => {}
+        }
+    }
+
+    /// Returns a new id for an `if`/`try`/`match` statement, identifying its branches.
+    fn next_branch_statement(&mut self) -> usize {
+        self.branch_statements += 1;
+        self.branch_statements
+    }
+
+    /// Visits a block that only runs in one branch of an `if`/`try`/`match` statement.
+    fn visit_branch(&mut self, statement: usize, branch: usize, body: &[Stmt]) {
+        self.branches.push((statement, branch));
+        for stmt in body {
+            self.visit_stmt(stmt);
+        }
+        self.branches.pop();
+    }
+
+    /// Visits a `try` (or `try*`) statement.
+    ///
+    /// The body and `else` block form one branch and each handler another, so a
+    /// fallback binding in `except ImportError:` is not a redefinition.
+    /// The `finally` block always runs.
+    fn visit_try(
+        &mut self,
+        body: &[Stmt],
+        handlers: &[ast::ExceptHandler],
+        orelse: &[Stmt],
+        finalbody: &[Stmt],
+    ) {
+        let statement = self.next_branch_statement();
+        self.visit_branch(statement, 0, body);
+        for (i, handler) in handlers.iter().enumerate() {
+            // Unwrap the ExceptHandler enum (it has a single variant).
+            let ast::ExceptHandler::ExceptHandler(handler_node) = handler;
+            if let Some(exc) = &handler_node.type_ {
+                self.visit_expr(exc);
+            }
+            self.branches.push((statement, i + 1));
+            if let Some(name) = &handler_node.name {
+                let line = self.line_index.line_index(handler_node.range.start());
+                self.bind_name(name.as_str(), line, BindingKind::Other);
+            }
+            for stmt in &handler_node.body {
+                self.visit_stmt(stmt);
+            }
+            self.branches.pop();
+        }
+        self.visit_branch(statement, 0, orelse);
+        for stmt in finalbody {
+            self.visit_stmt(stmt);
+        }
+    }
+
+    /// Visits the parts of a function definition evaluated in the enclosing scope when
+    /// the `def` statement runs: decorators, parameter defaults and annotations, the
+    /// return annotation and type parameter bounds.
+    fn visit_function_header(
+        &mut self,
+        decorators: &[Expr],
+        args: &ast::Arguments,
+        returns: Option<&Expr>,
+        type_params: &[ast::TypeParam],
+    ) {
+        for decorator in decorators {
+            self.visit_expr(decorator);
+        }
+        self.visit_type_params(type_params);
+        self.visit_argument_defaults(args);
+        for arg in args
+            .posonlyargs
+            .iter()
+            .chain(&args.args)
+            .chain(&args.kwonlyargs)
+            .map(|arg| &arg.def)
+            .chain(args.vararg.as_deref())
+            .chain(args.kwarg.as_deref())
+        {
+            if let Some(annotation) = &arg.annotation {
+                self.visit_annotation(annotation, true);
+            }
+        }
+        if let Some(returns) = returns {
+            self.visit_annotation(returns, true);
+        }
+    }
+
+    /// Visits a type annotation.
+    ///
+    /// String constants inside it are forward references and are parsed as expressions.
+    /// If the annotation is not `evaluated` at runtime (or the module uses
+    /// `from __future__ import annotations`), its references are only needed for typing.
+    fn visit_annotation(&mut self, annotation: &Expr, evaluated: bool) {
+        let typing_only = !evaluated || self.future_annotations;
+        let was_in_annotation = std::mem::replace(&mut self.in_annotation, true);
+        if typing_only {
+            self.typing_depth += 1;
+        }
+        self.visit_expr(annotation);
+        if typing_only {
+            self.typing_depth -= 1;
+        }
+        self.in_annotation = was_in_annotation;
+    }
+
+    /// Visits a string annotation (`"Foo"`, `"list[Foo]"`) as the expression it contains.
+    /// Returns `false` if the string is not a valid expression.
+    fn visit_string_annotation(&mut self, source: &str) -> bool {
+        let Ok(ast::Mod::Expression(parsed)) = rustpython_parser::parse(
+            source.trim(),
+            rustpython_parser::Mode::Expression,
+            "<annotation>",
+        ) else {
+            return false;
+        };
+        // Forward references are never evaluated at runtime.
+        self.typing_depth += 1;
+        self.visit_expr(&parsed.body);
+        self.typing_depth -= 1;
+        true
+    }
+
+    /// Visits the default values of parameters (functions and lambdas).
+    fn visit_argument_defaults(&mut self, args: &ast::Arguments) {
+        for arg in args
+            .posonlyargs
+            .iter()
+            .chain(&args.args)
+            .chain(&args.kwonlyargs)
+        {
+            if let Some(default) = &arg.default {
+                self.visit_expr(default);
+            }
+        }
+    }
+
+    /// Visits the bounds of type parameters (`def f[T: Base]()`, `class C[T: Base]`).
+    fn visit_type_params(&mut self, type_params: &[ast::TypeParam]) {
+        for param in type_params {
+            if let ast::TypeParam::TypeVar(var) = param {
+                if let Some(bound) = &var.bound {
+                    self.visit_expr(bound);
                 }
-                for stmt in &node.orelse {
-                    self.visit_stmt(stmt);
+            }
+        }
+    }
+
+    /// Visits a `match` pattern: value patterns and class patterns are reads,
+    /// capture patterns (`case Point(x=px)`, `case [*rest]`) bind variables.
+    fn visit_pattern(&mut self, pattern: &ast::Pattern) {
+        let line = self.line_index.line_index(pattern.range().start());
+        match pattern {
+            ast::Pattern::MatchValue(node) => self.visit_expr(&node.value),
+            ast::Pattern::MatchSingleton(_) => {}
+            ast::Pattern::MatchSequence(node) => {
+                for pattern in &node.patterns {
+                    self.visit_pattern(pattern);
                 }
-                for stmt in &node.finalbody {
-                    self.visit_stmt(stmt);
+            }
+            ast::Pattern::MatchMapping(node) => {
+                for key in &node.keys {
+                    self.visit_expr(key);
+                }
+                for pattern in &node.patterns {
+                    self.visit_pattern(pattern);
+                }
+                if let Some(rest) = &node.rest {
+                    self.bind_name(rest.as_str(), line, BindingKind::Variable);
                 }
             }
-            Stmt::Return(node) => {
-                if let Some(value) = &node.value {
-                    self.visit_expr(value);
+            ast::Pattern::MatchClass(node) => {
+                self.visit_expr(&node.cls);
+                for pattern in node.patterns.iter().chain(&node.kwd_patterns) {
+                    self.visit_pattern(pattern);
+                }
+            }
+            ast::Pattern::MatchStar(node) => {
+                if let Some(name) = &node.name {
+                    self.bind_name(name.as_str(), line, BindingKind::Variable);
+                }
+            }
+            ast::Pattern::MatchAs(node) => {
+                if let Some(pattern) = &node.pattern {
+                    self.visit_pattern(pattern);
+                }
+                if let Some(name) = &node.name {
+                    self.bind_name(name.as_str(), line, BindingKind::Variable);
+                }
+            }
+            ast::Pattern::MatchOr(node) => {
+                for pattern in &node.patterns {
+                    self.visit_pattern(pattern);
                 }
             }
-            _ => {}
+        }
+    }
+
+    /// Visits the target of a `del` statement.
+    /// Deleting a name is not a read; deleting an attribute or item reads its base.
+    fn visit_delete_target(&mut self, target: &Expr) {
+        match target {
+            // `del name` disposes of the definition, so binding the name again is fine.
+            Expr::Name(node) => {
+                if let Some(scope) = self.scopes.last_mut() {
+                    scope.take_unread_definition(node.id.as_str());
+                }
+            }
+            Expr::Tuple(node) => {
+                for elt in &node.elts {
+                    self.visit_delete_target(elt);
+                }
+            }
+            Expr::List(node) => {
+                for elt in &node.elts {
+                    self.visit_delete_target(elt);
+                }
+            }
+            Expr::Attribute(node) => self.visit_expr(&node.value),
+            Expr::Subscript(node) => {
+                self.visit_expr(&node.value);
+                self.visit_expr(&node.slice);
+            }
+            _ => self.visit_expr(target),
         }
     }
 
@@ -389,12 +1522,25 @@ impl<'a> SkylosVisitor<'a> {
     fn visit_function_def(
         &mut self,
         name: &str,
+        args: &ast::Arguments,
         body: &[Stmt],
+        decorators: &[Expr],
         range_start: rustpython_ast::TextSize,
     ) {
         let qualified_name = self.get_qualified_name(name);
         let line = self.line_index.line_index(range_start);
 
+        // Methods defined directly in a class body receive `self`/`cls` first,
+        // unless they are static.
+        let decorator_names: Vec<String> = decorators.iter().filter_map(decorator_name).collect();
+        let in_class_body = self
+            .scopes
+            .last()
+            .is_some_and(|s| s.kind == ScopeKind::Class);
+        let has_receiver = in_class_body && !decorator_names.iter().any(|d| d == "staticmethod");
+        let parameter_confidence =
+            self.parameter_confidence(name, &decorator_names, args, body, has_receiver);
+
         // Determine if it's a function or a method based on class stack.
         let def_type = if !self.class_stack.is_empty() {
             "method"
@@ -402,32 +1548,272 @@ impl<'a> SkylosVisitor<'a> {
             "function"
         };
 
-        self.add_def(qualified_name, def_type, line);
+        // Decorated functions may be registered (or be `@overload`s), so redefining them
+        // does not make them dead.
+        let is_registered = decorator_names
+            .iter()
+            .any(|d| !PLAIN_DECORATORS.contains(&d.as_str()));
+        self.bind_definition(name, line, "function", None, is_registered);
+        self.add_def(qualified_name.clone(), def_type, line);
 
+        // References in the body belong to this function.
+        self.def_stack.push(self.definitions.len() - 1);
+        self.push_scope(ScopeKind::Function, qualified_name);
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.parameter_confidence = parameter_confidence;
+        }
+        self.bind_arguments(args, has_receiver);
         for stmt in body {
             self.visit_stmt(stmt);
         }
+        self.pop_scope();
+        self.def_stack.pop();
+    }
+
+    /// Computes the confidence for reporting unused parameters of a function.
+    ///
+    /// Many functions must accept parameters they do not use because their signature is
+    /// dictated elsewhere: stubs (abstract methods, `@overload`, `...` bodies), dunder
+    /// methods and framework callbacks. Overrides of a base class method are only known
+    /// once every class is collected, so the analyzer handles them.
+    fn parameter_confidence(
+        &self,
+        name: &str,
+        decorators: &[String],
+        args: &ast::Arguments,
+        body: &[Stmt],
+        has_receiver: bool,
+    ) -> u8 {
+        // Dunder methods implement a protocol with a fixed signature.
+        if name.starts_with("__") && name.ends_with("__") {
+            return 0;
+        }
+        // Stubs declare a signature without implementing it.
+        if decorators
+            .iter()
+            .any(|d| STUB_DECORATORS.contains(&d.as_str()))
+            || is_stub_body(body)
+        {
+            return 10;
+        }
+
+        let mut confidence = 100;
+
+        // Decorated functions are usually registered as callbacks somewhere.
+        if decorators
+            .iter()
+            .any(|d| !PLAIN_DECORATORS.contains(&d.as_str()))
+        {
+            confidence = confidence.min(40);
+        }
+
+        // Callbacks with a well-known framework signature, and pytest hooks.
+        let positional: Vec<&str> = args
+            .posonlyargs
+            .iter()
+            .chain(&args.args)
+            .skip(usize::from(has_receiver))
+            .map(|arg| arg.def.arg.as_str())
+            .collect();
+        let is_callback = CALLBACK_SIGNATURES
+            .iter()
+            .any(|signature| positional.starts_with(signature));
+        if is_callback || name.starts_with("pytest_") {
+            confidence = confidence.min(30);
+        }
+
+        confidence
+    }
+
+    /// Detects dynamic imports and reflective access in a call.
+    ///
+    /// Literal targets become references (`getattr(mod, "func")` references `mod.func`),
+    /// string prefixes are recorded as dynamic prefixes, and anything unbounded marks the
+    /// module as using dynamic access.
+    fn visit_dynamic_call(&mut self, node: &ast::ExprCall) {
+        // `globals().get("name")` reads a module-level name.
+        if let Expr::Attribute(attr) = &*node.func {
+            if attr.attr.as_str() == "get" && is_globals_call(&attr.value) {
+                if let Some(key) = node.args.first() {
+                    self.visit_globals_access(key);
+                }
+                return;
+            }
+        }
+
+        let Some(func) = dotted_name(&node.func) else {
+            return;
+        };
+        match func.as_str() {
+            "importlib.import_module" | "import_module" | "__import__" => {
+                let Some(name) = node.args.first().and_then(string_literal) else {
+                    // `import_module(f"plugins.{name}")` may load any matching module.
+                    if let Some(prefix) = node.args.first().and_then(string_prefix) {
+                        self.dynamic_prefixes.push(prefix);
+                    }
+                    return;
+                };
+                // `import_module(".mod", package="pkg")` is relative to `package`.
+                let relative = name.trim_start_matches('.');
+                let level = name.len() - relative.len();
+                let module = if level == 0 {
+                    Some(name.to_string())
+                } else {
+                    let package = node
+                        .keywords
+                        .iter()
+                        .find(|k| k.arg.as_ref().is_some_and(|a| a.as_str() == "package"))
+                        .and_then(|k| string_literal(&k.value));
+                    package.and_then(|package| {
+                        crate::utils::resolve_import_module(
+                            package,
+                            true,
+                            level,
+                            Some(relative).filter(|r| !r.is_empty()),
+                        )
+                    })
+                };
+                if let Some(module) = module {
+                    self.dynamic_imports.push(module);
+                }
+            }
+            "getattr" | "hasattr" if node.args.len() >= 2 => {
+                self.visit_reflective_access(&node.args[0], &node.args[1]);
+            }
+            "eval" | "exec" => self.has_dynamic_access = true,
+            _ => {}
+        }
+    }
+
+    /// Handles `getattr(obj, attr)` and `hasattr(obj, attr)`.
+    fn visit_reflective_access(&mut self, obj: &Expr, attr: &Expr) {
+        let is_receiver = matches!(obj, Expr::Name(n) if matches!(n.id.as_str(), "self" | "cls"))
+            && !self.class_stack.is_empty();
+        // Members of the current class for `self`/`cls`, otherwise the dotted object name.
+        let owner = if is_receiver { None } else { dotted_name(obj) };
+
+        if let Some(name) = string_literal(attr) {
+            // The bare name is already referenced through the string constant.
+            if is_receiver {
+                let qualified = self.get_qualified_name(name);
+                self.add_ref(&qualified);
+            } else if let Some(owner) = owner {
+                self.add_ref(&format!("{}.{}", owner, name));
+            }
+            return;
+        }
+
+        match (string_prefix(attr), is_receiver) {
+            (Some(prefix), true) => {
+                let qualified = self.get_qualified_name(&prefix);
+                self.dynamic_prefixes.push(qualified);
+            }
+            (Some(prefix), false) => self.dynamic_prefixes.push(prefix),
+            // Any member of the current class may be accessed.
+            (None, true) => {
+                let qualified = self.get_qualified_name("");
+                self.dynamic_prefixes.push(qualified);
+            }
+            (None, false) => {
+                // Any member of a module may be accessed; for other objects we cannot
+                // tell which definitions are reachable.
+                if let Some(owner) = owner {
+                    self.dynamic_prefixes.push(format!("{}.", owner));
+                }
+                self.has_dynamic_access = true;
+            }
+        }
+    }
+
+    /// Handles `globals()[key]` and `globals().get(key)`.
+    fn visit_globals_access(&mut self, key: &Expr) {
+        if let Some(name) = string_literal(key) {
+            let qualified = crate::utils::qualify(&self.module_name, name);
+            self.add_ref(&qualified);
+        } else if let Some(prefix) = string_prefix(key) {
+            let qualified = crate::utils::qualify(&self.module_name, &prefix);
+            self.dynamic_prefixes.push(qualified);
+        } else {
+            self.has_dynamic_access = true;
+        }
+    }
+
+    /// Visits a comprehension in its own scope.
+    ///
+    /// The first iterable is evaluated in the enclosing scope; the loop targets,
+    /// conditions and result expressions belong to the comprehension.
+    fn visit_comprehension(&mut self, generators: &[ast::Comprehension], elts: &[&Expr]) {
+        if let Some(first) = generators.first() {
+            self.visit_expr(&first.iter);
+        }
+        let qualified_name = format!("{}.<comprehension>", self.current_scope_name());
+        self.push_scope(ScopeKind::Comprehension, qualified_name);
+        for (i, gen) in generators.iter().enumerate() {
+            if i > 0 {
+                self.visit_expr(&gen.iter);
+            }
+            let line = self.line_index.line_index(gen.target.range().start());
+            self.bind_target(&gen.target, line);
+            for if_expr in &gen.ifs {
+                self.visit_expr(if_expr);
+            }
+        }
+        for elt in elts {
+            self.visit_expr(elt);
+        }
+        self.pop_scope();
+    }
+
+    /// Returns the qualified name of the current scope.
+    fn current_scope_name(&self) -> String {
+        self.scopes
+            .last()
+            .map(|s| s.qualified_name.clone())
+            .unwrap_or_default()
     }
 
     /// Visits an expression node in the AST.
     pub fn visit_expr(&mut self, expr: &Expr) {
         match expr {
             // Name usage (variable access)
-            Expr::Name(node) => {
-                if node.ctx.is_load() {
-                    self.add_ref(node.id.to_string());
+            Expr::Name(node) if node.ctx.is_load() => {
+                self.add_ref(node.id.as_str());
+                self.add_load(node.id.as_str());
+            }
+            // Walrus (`(y := f(x))`) binds in the enclosing function, even inside
+            // a comprehension.
+            Expr::NamedExpr(node) => {
+                self.visit_expr(&node.value);
+                if let Expr::Name(target) = &*node.target {
+                    let index = self
+                        .scopes
+                        .iter()
+                        .rposition(|s| s.kind != ScopeKind::Comprehension)
+                        .unwrap_or(0);
+                    let line = self.line_index.line_index(node.range.start());
+                    self.bind_name_in(index, target.id.as_str(), line, BindingKind::Variable, None);
                 }
             }
             // Function call
             Expr::Call(node) => {
                 self.visit_expr(&node.func);
-                for arg in &node.args {
-                    self.visit_expr(arg);
+                // The first argument of `typing.cast` is a type.
+                let is_cast = matches!(
+                    dotted_name(&node.func).as_deref(),
+                    Some("cast" | "typing.cast")
+                );
+                for (i, arg) in node.args.iter().enumerate() {
+                    if is_cast && i == 0 {
+                        self.visit_annotation(arg, true);
+                    } else {
+                        self.visit_expr(arg);
+                    }
                 }
                 // Don't forget keyword arguments (e.g., func(a=b))
                 for keyword in &node.keywords {
                     self.visit_expr(&keyword.value);
                 }
+                self.visit_dynamic_call(node);
             }
             // Attribute access (e.g., obj.attr)
             Expr::Attribute(node) => {
@@ -437,40 +1823,48 @@ impl<'a> SkylosVisitor<'a> {
                     // Case 1: Strict self.method usage inside a class context.
                     // We want to track references to methods of the current class.
                     if (base_id == "self" || base_id == "cls") && !self.class_stack.is_empty() {
-                        let method_name = &node.attr;
-                        let mut parts = Vec::new();
-                        if !self.module_name.is_empty() {
-                            parts.push(self.module_name.clone());
-                        }
-                        parts.extend(self.class_stack.clone());
-                        parts.push(method_name.to_string());
-                        let qualified = parts.join(".");
-                        self.add_ref(qualified);
+                        let qualified = self.get_qualified_name(&node.attr);
+                        self.add_ref(&qualified);
                     }
                     // Case 2: External usage (obj.method or sys.exit)
                     else {
                         // Track "sys" from "sys.exit" (Fixes unused import)
-                        self.add_ref(base_id.to_string());
+                        self.add_ref(base_id);
 
                         // Track "sys.exit" (Specific attribute access)
                         let full_attr = format!("{}.{}", base_id, node.attr);
-                        self.add_ref(full_attr);
+                        self.add_ref(&full_attr);
 
                         // FIX: Loose Method Tracking
                         // Track "analyze" from "s.analyze()".
                         // This fixes "unused function" when we can't infer the type of 's'.
-                        self.add_ref(node.attr.to_string());
+                        self.add_attribute_ref(node.attr.as_str());
+                    }
+                } else {
+                    // Case 3: Dotted chains (pkg.mod.helper) are tracked as a whole so
+                    // they can be resolved through imports of `pkg` or `pkg.mod`.
+                    if let Some(dotted) = dotted_name(&node.value) {
+                        self.add_ref(&format!("{}.{}", dotted, node.attr));
                     }
+                    // Loose method tracking for chained calls (text.upper().replace()).
+                    self.add_attribute_ref(node.attr.as_str());
                 }
                 self.visit_expr(&node.value);
             }
             // FIX: Dynamic Dispatch / String References
             Expr::Constant(node) => {
                 if let ast::Constant::Str(s) = &node.value {
+                    // Strings in annotations are forward references.
+                    if self.in_annotation && self.visit_string_annotation(s) {
+                        return;
+                    }
                     // Heuristic: If a string looks like a simple identifier (no spaces/dots),
                     // track it as a reference. This helps with getattr(self, "visit_" + name).
                     if !s.contains(' ') && !s.contains('.') && !s.is_empty() {
-                        self.add_ref(s.to_string());
+                        self.add_ref(s);
+                    }
+                    if is_dotted_path(s) {
+                        self.dotted_strings.push(s.replace(':', "."));
                     }
                 }
             }
@@ -488,7 +1882,13 @@ impl<'a> SkylosVisitor<'a> {
                 self.visit_expr(&node.operand);
             }
             Expr::Lambda(node) => {
+                // Defaults are evaluated where the lambda is created.
+                self.visit_argument_defaults(&node.args);
+                let qualified_name = format!("{}.<lambda>", self.current_scope_name());
+                self.push_scope(ScopeKind::Lambda, qualified_name);
+                self.bind_arguments(&node.args, false);
                 self.visit_expr(&node.body);
+                self.pop_scope();
             }
             Expr::IfExp(node) => {
                 self.visit_expr(&node.test);
@@ -509,41 +1909,16 @@ impl<'a> SkylosVisitor<'a> {
                 }
             }
             Expr::ListComp(node) => {
-                self.visit_expr(&node.elt);
-                for gen in &node.generators {
-                    self.visit_expr(&gen.iter);
-                    for if_expr in &gen.ifs {
-                        self.visit_expr(if_expr);
-                    }
-                }
+                self.visit_comprehension(&node.generators, &[&node.elt]);
             }
             Expr::SetComp(node) => {
-                self.visit_expr(&node.elt);
-                for gen in &node.generators {
-                    self.visit_expr(&gen.iter);
-                    for if_expr in &gen.ifs {
-                        self.visit_expr(if_expr);
-                    }
-                }
+                self.visit_comprehension(&node.generators, &[&node.elt]);
             }
             Expr::DictComp(node) => {
-                self.visit_expr(&node.key);
-                self.visit_expr(&node.value);
-                for gen in &node.generators {
-                    self.visit_expr(&gen.iter);
-                    for if_expr in &gen.ifs {
-                        self.visit_expr(if_expr);
-                    }
-                }
+                self.visit_comprehension(&node.generators, &[&node.key, &node.value]);
             }
             Expr::GeneratorExp(node) => {
-                self.visit_expr(&node.elt);
-                for gen in &node.generators {
-                    self.visit_expr(&gen.iter);
-                    for if_expr in &gen.ifs {
-                        self.visit_expr(if_expr);
-                    }
-                }
+                self.visit_comprehension(&node.generators, &[&node.elt]);
             }
             Expr::Await(node) => self.visit_expr(&node.value),
             Expr::Yield(node) => {
@@ -559,10 +1934,28 @@ impl<'a> SkylosVisitor<'a> {
                 }
             }
             Expr::Subscript(node) => {
+                // `globals()["name"]` reads a module-level name.
+                if is_globals_call(&node.value) {
+                    self.visit_globals_access(&node.slice);
+                }
                 self.visit_expr(&node.value);
+                // Strings in `Literal["a", "b"]` are values, not forward references.
+                let is_literal = self.in_annotation
+                    && dotted_name(&node.value)
+                        .is_some_and(|name| name == "Literal" || name.ends_with(".Literal"));
+                let was_in_annotation = self.in_annotation;
+                if is_literal {
+                    self.in_annotation = false;
+                }
                 self.visit_expr(&node.slice);
+                self.in_annotation = was_in_annotation;
+            }
+            Expr::FormattedValue(node) => {
+                self.visit_expr(&node.value);
+                if let Some(format_spec) = &node.format_spec {
+                    self.visit_expr(format_spec);
+                }
             }
-            Expr::FormattedValue(node) => self.visit_expr(&node.value),
             Expr::JoinedStr(node) => {
                 for value in &node.values {
                     self.visit_expr(value);
@@ -589,7 +1982,145 @@ impl<'a> SkylosVisitor<'a> {
                     self.visit_expr(step);
                 }
             }
-            _ => {}
+            Expr::Starred(node) => self.visit_expr(&node.value),
+            // Names that are assigned or deleted are handled by their statements.
+            Expr::Name(_) => {}
+        }
+    }
+}
+
+/// Returns the value of a string literal.
+fn string_literal(expr: &Expr) -> Option<&str> {
+    match expr {
+        Expr::Constant(node) => match &node.value {
+            ast::Constant::Str(s) => Some(s.as_str()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns the constant prefix of a string built at runtime:
+/// `f"handle_{x}"`, `"handle_" + x`, `"handle_%s" % x` or `"handle_{}".format(x)`.
+/// Returns `None` if there is no non-empty constant prefix.
+fn string_prefix(expr: &Expr) -> Option<String> {
+    let prefix = match expr {
+        Expr::JoinedStr(node) => string_literal(node.values.first()?)?.to_string(),
+        Expr::BinOp(node) => match node.op {
+            ast::Operator::Add => match string_literal(&node.left) {
+                Some(left) => left.to_string(),
+                None => string_prefix(&node.left)?,
+            },
+            ast::Operator::Mod => {
+                let format = string_literal(&node.left)?;
+                format.split('%').next().unwrap_or_default().to_string()
+            }
+            _ => return None,
+        },
+        Expr::Call(node) => match &*node.func {
+            Expr::Attribute(attr) if attr.attr.as_str() == "format" => {
+                let format = string_literal(&attr.value)?;
+                format.split('{').next().unwrap_or_default().to_string()
+            }
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(prefix).filter(|p| !p.is_empty())
+}
+
+/// Whether a string looks like a dotted path to a module or module member: identifiers
+/// joined by dots, optionally followed by `:attr` (`"pkg.mod.Class"`, `"main:app"`).
+fn is_dotted_path(s: &str) -> bool {
+    (s.contains('.') || s.contains(':'))
+        && s.matches(':').count() <= 1
+        && s.split(['.', ':']).all(|part| {
+            part.chars()
+                .next()
+                .is_some_and(|c| c.is_alphabetic() || c == '_')
+                && part.chars().all(|c| c.is_alphanumeric() || c == '_')
+        })
+}
+
+/// Whether an expression is a call to `globals()`.
+fn is_globals_call(expr: &Expr) -> bool {
+    matches!(expr, Expr::Call(call) if matches!(&*call.func, Expr::Name(n) if n.id.as_str() == "globals"))
+}
+
+/// Computes the confidence for reporting unused class-level fields of a class.
+///
+/// Fields of dataclasses, attrs classes, pydantic models, `NamedTuple`s and `TypedDict`s
+/// are used by generated `__init__`, comparison and serialization code. Enum members are
+/// often only reached by value or name lookup (`Color(1)`, `Color["RED"]`) or iteration.
+/// Fields of other subclasses may be read by a base class outside the project.
+fn field_confidence(decorators: &[Expr], bases: &[String]) -> u8 {
+    let last_component = |name: &str| name.rsplit('.').next().unwrap_or(name).to_string();
+    let is_schema = decorators.iter().any(|decorator| {
+        let target = match decorator {
+            Expr::Call(node) => &*node.func,
+            _ => decorator,
+        };
+        dotted_name(target).is_some_and(|name| SCHEMA_DECORATORS.contains(&name.as_str()))
+    }) || bases
+        .iter()
+        .any(|base| SCHEMA_BASES.contains(&last_component(base).as_str()));
+    if is_schema {
+        return 0;
+    }
+    if bases
+        .iter()
+        .any(|base| ENUM_BASES.contains(&last_component(base).as_str()))
+    {
+        return 40;
+    }
+    if bases.iter().any(|base| base != "object") {
+        return 50;
+    }
+    100
+}
+
+/// Returns the last component of a decorator's name (`app.route("/")` -> `route`).
+fn decorator_name(decorator: &Expr) -> Option<String> {
+    let target = match decorator {
+        Expr::Call(node) => &*node.func,
+        _ => decorator,
+    };
+    match target {
+        Expr::Name(node) => Some(node.id.to_string()),
+        Expr::Attribute(node) => Some(node.attr.to_string()),
+        _ => None,
+    }
+}
+
+/// Whether a function body is a stub: only docstrings, `pass`, `...` and
+/// `raise NotImplementedError`.
+fn is_stub_body(body: &[Stmt]) -> bool {
+    body.iter().all(|stmt| match stmt {
+        Stmt::Pass(_) => true,
+        Stmt::Expr(node) => matches!(
+            &*node.value,
+            Expr::Constant(c) if matches!(c.value, ast::Constant::Str(_) | ast::Constant::Ellipsis)
+        ),
+        Stmt::Raise(node) => node.exc.as_deref().is_some_and(|exc| {
+            let exc = match exc {
+                Expr::Call(call) => &*call.func,
+                _ => exc,
+            };
+            matches!(exc, Expr::Name(n) if n.id.as_str() == "NotImplementedError")
+        }),
+        _ => false,
+    })
+}
+
+/// Returns the dotted name of a `Name`/`Attribute` chain (e.g. `pkg.mod.func`).
+///
+/// Returns `None` if the chain contains anything else, such as a call or subscript.
+pub fn dotted_name(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Name(node) => Some(node.id.to_string()),
+        Expr::Attribute(node) => {
+            dotted_name(&node.value).map(|base| format!("{}.{}", base, node.attr))
         }
+        _ => None,
     }
 }
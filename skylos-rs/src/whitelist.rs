@@ -0,0 +1,95 @@
+use crate::analyzer::AnalysisResult;
+use crate::utils::LineIndex;
+use crate::visitor::{Definition, SkylosVisitor};
+use anyhow::{Context, Result};
+use rustpython_parser::{parse, Mode};
+use std::fs;
+use std::path::Path;
+
+/// Loads the names declared as used by a whitelist file.
+///
+/// Two formats are supported:
+/// - A Python file (`whitelist.py`), as used by vulture. Every name it references counts
+///   as used, e.g. `_.on_startup` for the `on_startup` attribute of any object, or
+///   `MyModel.Meta` for `MyModel` and its `Meta` member.
+/// - Any other file is a plain list with one name per line. Names may be simple
+///   (`on_startup`) or fully qualified (`pkg.mod.helper`); `#` starts a comment.
+pub fn load_whitelist(path: &Path) -> Result<Vec<String>> {
+    let source = fs::read_to_string(path)
+        .with_context(|| format!("failed to read whitelist {}", path.display()))?;
+
+    if path.extension().is_some_and(|ext| ext == "py") {
+        let line_index = LineIndex::new(&source);
+        let mut visitor = SkylosVisitor::new(path.to_path_buf(), String::new(), &line_index);
+        let module = parse(&source, Mode::Module, &path.to_string_lossy())
+            .with_context(|| format!("failed to parse whitelist {}", path.display()))?;
+        if let rustpython_ast::Mod::Module(module) = module {
+            for stmt in &module.body {
+                visitor.visit_stmt(stmt);
+            }
+        }
+        return Ok(visitor.reference_names().map(str::to_string).collect());
+    }
+
+    Ok(source
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Renders a whitelist covering every unused definition in an analysis result.
+///
+/// Each definition and dead module is listed by its fully qualified name, and each
+/// dependency finding by its package name, so loading the whitelist marks exactly the
+/// reported findings as used. The kind and location of each
+/// finding is kept as a comment.
+pub fn make_whitelist(result: &AnalysisResult) -> String {
+    let mut whitelist =
+        String::from("# Skylos whitelist: names listed here are treated as used.\n");
+    for def in unused_definitions(result) {
+        whitelist.push_str(&format!(
+            "{}  # unused {} ({}:{})\n",
+            def.full_name,
+            def.def_type,
+            def.file.display(),
+            def.line
+        ));
+    }
+    for module in &result.dead_modules {
+        whitelist.push_str(&format!(
+            "{}  # unused module ({})\n",
+            module.module_name,
+            module.file.display()
+        ));
+    }
+    let dependencies = (result.unused_dependencies.iter().map(|d| ("unused", d))).chain(
+        result
+            .undeclared_dependencies
+            .iter()
+            .map(|d| ("undeclared", d)),
+    );
+    for (kind, dependency) in dependencies {
+        whitelist.push_str(&format!(
+            "{}  # {} dependency ({}:{})\n",
+            dependency.name,
+            kind,
+            dependency.file.display(),
+            dependency.line
+        ));
+    }
+    whitelist
+}
+
+/// Returns every reported unused definition, in report order.
+fn unused_definitions(result: &AnalysisResult) -> impl Iterator<Item = &Definition> {
+    result
+        .unused_functions
+        .iter()
+        .chain(&result.unused_imports)
+        .chain(&result.unused_classes)
+        .chain(&result.unused_variables)
+        .chain(&result.unused_parameters)
+        .chain(&result.unused_attributes)
+}
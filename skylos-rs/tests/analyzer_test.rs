@@ -1,3 +1,6 @@
+mod common;
+
+use common::write_file;
 use skylos_rs::analyzer::Skylos;
 use std::fs::{self, File};
 use std::io::Write;
@@ -116,31 +119,77 @@ def _private_unused():
 fn test_module_name_generation_implicit() {
     let dir = tempdir().unwrap();
 
-    // Create src/package/submodule.py
+    // Create src/package/submodule.py (namespace package, no __init__.py)
     let package_path = dir.path().join("src").join("package");
     fs::create_dir_all(&package_path).unwrap();
 
     let file_path = package_path.join("submodule.py");
     let mut file = File::create(&file_path).unwrap();
-    write!(file, "def test_func(): pass").unwrap();
+    write!(file, "def helper(): pass").unwrap();
 
     let skylos = Skylos::new(0, false, false, false);
     let result = skylos.analyze(dir.path()).unwrap();
 
-    // We can't check internal module name directly, but we can check if full_name reflects it?
-    // In Rust impl, module name is just file_stem (e.g. "submodule"), not dotted path "src.package.submodule"
-    // So the full name would be "submodule.test_func" or "test_func" if module name is ignored in some contexts.
-    // Let's check what we get.
-
+    // The module name is relative to the project root, with the `src/` layout dropped.
     if let Some(func) = result.unused_functions.first() {
-        // Based on analyzer.rs: let module_name = path.file_stem()
-        // It creates "submodule"
-        assert_eq!(func.full_name, "submodule.test_func");
+        assert_eq!(func.full_name, "package.submodule.helper");
     } else {
         panic!("No unused function found");
     }
 }
 
+#[test]
+fn test_module_name_generation_packages() {
+    let dir = tempdir().unwrap();
+
+    // pkg/a/utils.py and pkg/b/utils.py share a file stem but live in different packages.
+    for sub in ["a", "b"] {
+        let package_path = dir.path().join("pkg").join(sub);
+        fs::create_dir_all(&package_path).unwrap();
+        File::create(package_path.join("__init__.py")).unwrap();
+        let mut file = File::create(package_path.join("utils.py")).unwrap();
+        write!(file, "def helper_{}(): pass", sub).unwrap();
+    }
+    File::create(dir.path().join("pkg").join("__init__.py")).unwrap();
+
+    let skylos = Skylos::new(0, false, false, false);
+    let result = skylos.analyze(dir.path()).unwrap();
+
+    let full_names: Vec<String> = result
+        .unused_functions
+        .iter()
+        .map(|f| f.full_name.clone())
+        .collect();
+    assert!(full_names.contains(&"pkg.a.utils.helper_a".to_string()));
+    assert!(full_names.contains(&"pkg.b.utils.helper_b".to_string()));
+
+    // Scanning a sub-package still yields the real dotted path.
+    let result = skylos.analyze(&dir.path().join("pkg").join("a")).unwrap();
+    assert_eq!(result.unused_functions[0].full_name, "pkg.a.utils.helper_a");
+}
+
+#[test]
+fn test_same_named_functions_do_not_mask_each_other() {
+    let dir = tempdir().unwrap();
+    write_file(dir.path(), "pkg/a/utils.py", "def helper():\n    pass\n");
+    write_file(
+        dir.path(),
+        "pkg/b/utils.py",
+        "def helper():\n    pass\n\nhelper()\n",
+    );
+
+    let skylos = Skylos::new(60, false, false, false);
+    let result = skylos.analyze(dir.path()).unwrap();
+
+    // The call in `pkg.b.utils` only uses its own `helper`.
+    let unused: Vec<&str> = result
+        .unused_functions
+        .iter()
+        .map(|f| f.full_name.as_str())
+        .collect();
+    assert_eq!(unused, vec!["pkg.a.utils.helper"]);
+}
+
 #[test]
 fn test_heuristics_auto_called_methods() {
     let dir = tempdir().unwrap();
@@ -216,7 +265,7 @@ def _private_function():
         .iter()
         .find(|f| f.simple_name == "public_function")
         .unwrap();
-    assert_eq!(public_def.in_init, true);
+    assert!(public_def.in_init);
     // Base 100 - 20 = 80
     assert_eq!(public_def.confidence, 80);
 
@@ -254,3 +303,1217 @@ my_func()
 
     assert!(!unused_funcs.contains(&"my_func".to_string()));
 }
+
+#[test]
+fn test_cross_file_import_resolution() {
+    let dir = tempdir().unwrap();
+    write_file(dir.path(), "pkg/__init__.py", "from .mod import exported\n");
+    write_file(
+        dir.path(),
+        "pkg/mod.py",
+        r#"
+def helper():
+    pass
+
+def exported():
+    pass
+
+def dead():
+    pass
+"#,
+    );
+    write_file(dir.path(), "pkg/sub/__init__.py", "");
+    write_file(
+        dir.path(),
+        "pkg/sub/worker.py",
+        r#"
+from ..mod import helper as h
+from pkg import exported
+
+h()
+exported()
+"#,
+    );
+
+    let skylos = Skylos::new(0, false, false, false);
+    let result = skylos.analyze(dir.path()).unwrap();
+
+    let unused: Vec<String> = result
+        .unused_functions
+        .iter()
+        .map(|f| f.full_name.clone())
+        .collect();
+    // Used through a relative, renamed import.
+    assert!(!unused.contains(&"pkg.mod.helper".to_string()));
+    // Used through the package `__init__` re-export.
+    assert!(!unused.contains(&"pkg.mod.exported".to_string()));
+    assert!(unused.contains(&"pkg.mod.dead".to_string()));
+
+    // The re-export itself is used by `from pkg import exported`.
+    assert!(result.unused_imports.is_empty());
+}
+
+#[test]
+fn test_same_module_references_are_counted() {
+    let dir = tempdir().unwrap();
+    write_file(
+        dir.path(),
+        "m.py",
+        "def helper():\n    pass\n\ndef local_caller():\n    helper()\n",
+    );
+    write_file(
+        dir.path(),
+        "other.py",
+        "from m import helper\n\ndef remote_caller():\n    helper()\n",
+    );
+
+    let skylos = Skylos::new(0, false, false, false);
+    let result = skylos.analyze(dir.path()).unwrap();
+
+    // Both callers are dead, but the use within `m` counts along with the import.
+    let helper = result
+        .unused_functions
+        .iter()
+        .find(|f| f.full_name == "m.helper")
+        .unwrap();
+    assert_eq!(helper.references, 2);
+    assert_eq!(
+        helper.dead_reason.as_deref(),
+        Some("referenced_from_dead_code")
+    );
+}
+
+#[test]
+fn test_imports_are_tracked_per_module() {
+    let dir = tempdir().unwrap();
+    write_file(dir.path(), "a.py", "import json\n");
+    write_file(dir.path(), "b.py", "import json\n\njson.dumps({})\n");
+
+    let skylos = Skylos::new(0, false, false, false);
+    let result = skylos.analyze(dir.path()).unwrap();
+
+    // `json` is used in b.py, which must not hide the unused import in a.py.
+    let unused: Vec<String> = result
+        .unused_imports
+        .iter()
+        .map(|i| i.full_name.clone())
+        .collect();
+    assert_eq!(unused, vec!["a.json".to_string()]);
+}
+
+#[test]
+fn test_transitively_dead_code() {
+    let dir = tempdir().unwrap();
+    write_file(
+        dir.path(),
+        "app.py",
+        r#"
+import json
+
+def dead_entry():
+    return helper()
+
+def helper():
+    return Formatter().format(json)
+
+class Formatter:
+    def format(self, value):
+        return value
+
+def live_entry():
+    return shared()
+
+def shared():
+    pass
+
+if __name__ == "__main__":
+    live_entry()
+"#,
+    );
+
+    let skylos = Skylos::new(60, false, false, false);
+    let result = skylos.analyze(dir.path()).unwrap();
+
+    let reason = |defs: &[skylos_rs::visitor::Definition], name: &str| {
+        defs.iter()
+            .find(|d| d.simple_name == name)
+            .and_then(|d| d.dead_reason.clone())
+    };
+
+    assert_eq!(
+        reason(&result.unused_functions, "dead_entry").as_deref(),
+        Some("unreferenced")
+    );
+    // Only called by dead code, so the whole subgraph is dead.
+    assert_eq!(
+        reason(&result.unused_functions, "helper").as_deref(),
+        Some("referenced_from_dead_code")
+    );
+    assert_eq!(
+        reason(&result.unused_classes, "Formatter").as_deref(),
+        Some("referenced_from_dead_code")
+    );
+    assert_eq!(
+        reason(&result.unused_imports, "json").as_deref(),
+        Some("referenced_from_dead_code")
+    );
+    // Reachable from the `__main__` guard.
+    assert_eq!(reason(&result.unused_functions, "live_entry"), None);
+    assert_eq!(reason(&result.unused_functions, "shared"), None);
+}
+
+#[test]
+fn test_same_module_references_keep_definitions_alive() {
+    let dir = tempdir().unwrap();
+    write_file(dir.path(), "m.py", "def helper():\n    pass\n\nhelper()\n");
+    // The only import of `helper` is in dead code.
+    write_file(
+        dir.path(),
+        "dead.py",
+        "from m import helper\n\ndef unused():\n    helper()\n",
+    );
+    write_file(dir.path(), "main.py", "import m\n");
+
+    let skylos = Skylos::new(0, false, false, false);
+    let result = skylos.analyze(dir.path()).unwrap();
+
+    // Called at module level of `m`, which `main` imports.
+    let unused: Vec<&str> = result
+        .unused_functions
+        .iter()
+        .map(|f| f.full_name.as_str())
+        .collect();
+    assert_eq!(unused, vec!["dead.unused"]);
+}
+
+#[test]
+fn test_public_api_roots() {
+    let dir = tempdir().unwrap();
+    write_file(
+        dir.path(),
+        "lib.py",
+        r#"
+def api_call():
+    return _impl()
+
+def _impl():
+    pass
+"#,
+    );
+
+    let mut skylos = Skylos::new(0, false, false, false);
+    skylos.public_api = vec!["lib.api_call".to_string()];
+    let result = skylos.analyze(dir.path()).unwrap();
+
+    // api_call is unreferenced but is a root, so its callee stays alive.
+    let unused: Vec<String> = result
+        .unused_functions
+        .iter()
+        .map(|f| f.simple_name.clone())
+        .collect();
+    assert!(unused.contains(&"api_call".to_string()));
+    assert!(!unused.contains(&"_impl".to_string()));
+}
+
+#[test]
+fn test_unused_variables() {
+    let dir = tempdir().unwrap();
+    write_file(
+        dir.path(),
+        "config.py",
+        r#"
+TIMEOUT = 30
+UNUSED_SETTING = "x"
+"#,
+    );
+    write_file(
+        dir.path(),
+        "app.py",
+        r#"
+from config import TIMEOUT
+
+def run_job():
+    result = TIMEOUT * 2
+    leftover = result
+    return result
+
+def other():
+    leftover = 1
+    return leftover
+
+run_job()
+other()
+"#,
+    );
+
+    let skylos = Skylos::new(60, false, false, false);
+    let result = skylos.analyze(dir.path()).unwrap();
+
+    let unused: Vec<String> = result
+        .unused_variables
+        .iter()
+        .map(|v| v.full_name.clone())
+        .collect();
+    assert!(unused.contains(&"config.UNUSED_SETTING".to_string()));
+    assert!(!unused.contains(&"config.TIMEOUT".to_string()));
+    // `leftover` is read in `other`, which must not hide the unused one in `run_job`.
+    assert!(unused.contains(&"app.run_job.leftover".to_string()));
+    assert!(!unused.contains(&"app.other.leftover".to_string()));
+    assert!(!unused.contains(&"app.run_job.result".to_string()));
+}
+
+#[test]
+fn test_unused_parameters() {
+    let dir = tempdir().unwrap();
+    write_file(
+        dir.path(),
+        "app.py",
+        r#"
+class Handler(BaseHandler):
+    def handle(self, event_data, verbose):
+        return self
+
+class Shape:
+    def area(self, scale):
+        raise NotImplementedError
+
+class Square(Shape):
+    def area(self, scale):
+        return 4
+
+    def resize(self, factor):
+        return self
+
+def render(template, context, debug=False):
+    return template.format(**context)
+
+render("x", {})
+Handler().handle(1, 2)
+Square().area(1)
+Square().resize(2)
+"#,
+    );
+
+    let skylos = Skylos::new(60, false, false, false);
+    let result = skylos.analyze(dir.path()).unwrap();
+
+    let mut unused: Vec<String> = result
+        .unused_parameters
+        .iter()
+        .map(|p| p.full_name.clone())
+        .collect();
+    unused.sort();
+    // `resize` overrides nothing, although `Square` has a base class.
+    assert_eq!(
+        unused,
+        vec![
+            "app.Square.resize.factor".to_string(),
+            "app.render.debug".to_string()
+        ]
+    );
+
+    // Possible overrides are only reported at a lower threshold.
+    let skylos = Skylos::new(50, false, false, false);
+    let result = skylos.analyze(dir.path()).unwrap();
+    let mut unused: Vec<String> = result
+        .unused_parameters
+        .iter()
+        .map(|p| p.full_name.clone())
+        .collect();
+    unused.sort();
+    assert_eq!(
+        unused,
+        vec![
+            "app.Handler.handle.event_data".to_string(),
+            "app.Handler.handle.verbose".to_string(),
+            "app.Square.area.scale".to_string(),
+            "app.Square.resize.factor".to_string(),
+            "app.render.debug".to_string()
+        ]
+    );
+}
+
+#[test]
+fn test_inherited_method_liveness() {
+    let dir = tempdir().unwrap();
+    write_file(
+        dir.path(),
+        "base.py",
+        r#"
+class Plugin:
+    def process(self, data):
+        return self.transform(self.prepare(data))
+
+    def prepare(self, data):
+        return data
+
+    def transform(self, data):
+        raise NotImplementedError
+
+class Legacy:
+    def hook(self):
+        return 1
+"#,
+    );
+    write_file(
+        dir.path(),
+        "plugins.py",
+        r#"
+from base import Plugin, Legacy
+
+class Upper(Plugin):
+    def transform(self, data):
+        return data.upper()
+
+    def orphan(self):
+        return 1
+
+class Lower(Upper):
+    def transform(self, data):
+        return self.normalize(data)
+
+    def normalize(self, data):
+        return data.lower()
+
+class OldStyle(Legacy):
+    def hook(self):
+        return 2
+
+plugin = Lower()
+plugin.process("x")
+"#,
+    );
+
+    let skylos = Skylos::new(60, false, false, false);
+    let result = skylos.analyze(dir.path()).unwrap();
+
+    let unused: Vec<String> = result
+        .unused_functions
+        .iter()
+        .map(|f| f.full_name.clone())
+        .collect();
+    // Overrides of a called base method are alive, across files and levels.
+    assert!(!unused.contains(&"plugins.Upper.transform".to_string()));
+    assert!(!unused.contains(&"plugins.Lower.transform".to_string()));
+    assert!(!unused.contains(&"plugins.Lower.normalize".to_string()));
+    // `self.prepare()` on a subclass resolves to the inherited method.
+    assert!(!unused.contains(&"base.Plugin.prepare".to_string()));
+    assert!(unused.contains(&"plugins.Upper.orphan".to_string()));
+    // Overriding a method nobody calls does not make it alive.
+    assert!(unused.contains(&"base.Legacy.hook".to_string()));
+    assert!(unused.contains(&"plugins.OldStyle.hook".to_string()));
+}
+
+#[test]
+fn test_overrides_of_external_base_classes() {
+    let dir = tempdir().unwrap();
+    write_file(
+        dir.path(),
+        "app.py",
+        r#"
+import logging
+from html.parser import HTMLParser
+
+class LinkParser(HTMLParser):
+    def handle_starttag(self, tag, attrs):
+        return tag
+
+class ListHandler(logging.Handler):
+    def emit(self, record):
+        return record
+
+class Plain(object):
+    def orphan(self):
+        return 1
+
+class Failure(Exception):
+    def orphan(self):
+        return 1
+
+LinkParser().feed("<a>")
+logging.getLogger().addHandler(ListHandler())
+Plain()
+Failure()
+"#,
+    );
+
+    let skylos = Skylos::new(60, false, false, false);
+    let result = skylos.analyze(dir.path()).unwrap();
+    let unused: Vec<&str> = result
+        .unused_functions
+        .iter()
+        .map(|f| f.full_name.as_str())
+        .collect();
+    // Library methods may call overrides we cannot see.
+    assert_eq!(unused, vec!["app.Plain.orphan", "app.Failure.orphan"]);
+
+    // They are still reported at a lower threshold.
+    let skylos = Skylos::new(40, false, false, false);
+    let result = skylos.analyze(dir.path()).unwrap();
+    let unused: Vec<&str> = result
+        .unused_functions
+        .iter()
+        .map(|f| f.full_name.as_str())
+        .collect();
+    assert!(unused.contains(&"app.LinkParser.handle_starttag"));
+    assert!(unused.contains(&"app.ListHandler.emit"));
+}
+
+#[test]
+fn test_all_exports_and_star_imports() {
+    let dir = tempdir().unwrap();
+    write_file(dir.path(), "pkg/__init__.py", "");
+    write_file(
+        dir.path(),
+        "pkg/core.py",
+        r#"
+__all__ = ["parse", "render"]
+
+def parse():
+    return 1
+
+def render():
+    return 2
+
+def internal():
+    return 3
+"#,
+    );
+    write_file(
+        dir.path(),
+        "pkg/shapes.py",
+        r#"
+def circle():
+    return 1
+
+def square():
+    return 2
+
+def _private():
+    return 3
+"#,
+    );
+    write_file(
+        dir.path(),
+        "pkg/api.py",
+        r#"
+from .core import *
+from .shapes import *
+
+__all__ = ["parse", "missing"]
+__all__.append("shapes_total")
+
+shapes_total = circle()
+"#,
+    );
+    write_file(
+        dir.path(),
+        "app.py",
+        r#"
+from pkg.api import parse
+
+parse()
+"#,
+    );
+
+    let skylos = Skylos::new(60, false, false, false);
+    let result = skylos.analyze(dir.path()).unwrap();
+
+    let unused: Vec<String> = result
+        .unused_functions
+        .iter()
+        .map(|f| f.full_name.clone())
+        .collect();
+    // `render` is exported by `pkg.core`, and `parse` is used through the star import.
+    assert!(!unused.contains(&"pkg.core.parse".to_string()));
+    assert!(!unused.contains(&"pkg.core.render".to_string()));
+    assert!(unused.contains(&"pkg.core.internal".to_string()));
+    // Without `__all__`, a star import provides the public names.
+    assert!(!unused.contains(&"pkg.shapes.circle".to_string()));
+    assert!(unused.contains(&"pkg.shapes.square".to_string()));
+
+    // Both star imports are used.
+    let unused_imports: Vec<String> = result
+        .unused_imports
+        .iter()
+        .map(|i| i.full_name.clone())
+        .collect();
+    assert!(!unused_imports.contains(&"pkg.api.*".to_string()));
+
+    let undefined: Vec<(String, usize)> = result
+        .undefined_exports
+        .iter()
+        .map(|e| (e.name.clone(), e.line))
+        .collect();
+    assert_eq!(undefined, vec![("missing".to_string(), 5)]);
+    assert_eq!(result.undefined_exports[0].rule_id, "SKY-E001");
+}
+
+#[test]
+fn test_unused_star_import() {
+    let dir = tempdir().unwrap();
+    write_file(dir.path(), "helpers.py", "def helper():\n    return 1\n");
+    write_file(
+        dir.path(),
+        "app.py",
+        "from helpers import *\nfrom os.path import *\n\n__all__ = ['anything']\n",
+    );
+
+    let skylos = Skylos::new(60, false, false, false);
+    let result = skylos.analyze(dir.path()).unwrap();
+
+    let unused_imports: Vec<&str> = result
+        .unused_imports
+        .iter()
+        .map(|i| i.full_name.as_str())
+        .collect();
+    assert_eq!(unused_imports, vec!["app.*"]);
+    // The unresolved star import from `os.path` may define anything.
+    assert!(result.undefined_exports.is_empty());
+}
+
+#[test]
+fn test_dynamic_access_confidence() {
+    let dir = tempdir().unwrap();
+    write_file(
+        dir.path(),
+        "commands.py",
+        r#"
+def cmd_start():
+    return 1
+
+def cmd_stop():
+    return 2
+
+def status():
+    return 3
+
+def unrelated():
+    return 4
+"#,
+    );
+    write_file(
+        dir.path(),
+        "cli.py",
+        r#"
+import commands
+
+def dispatch(name):
+    getattr(commands, "status")()
+    return getattr(commands, "cmd_" + name)()
+
+dispatch("start")
+"#,
+    );
+    write_file(
+        dir.path(),
+        "registry.py",
+        r#"
+def lookup(name):
+    return globals()[name]
+
+def register():
+    return 1
+"#,
+    );
+
+    let skylos = Skylos::new(60, false, false, false);
+    let result = skylos.analyze(dir.path()).unwrap();
+
+    let unused: Vec<String> = result
+        .unused_functions
+        .iter()
+        .map(|f| f.full_name.clone())
+        .collect();
+    // The literal `getattr` target resolves through the import.
+    assert!(!unused.contains(&"commands.status".to_string()));
+    // Prefix matches are below the default threshold.
+    assert!(!unused.contains(&"commands.cmd_start".to_string()));
+    assert!(!unused.contains(&"commands.cmd_stop".to_string()));
+    assert!(unused.contains(&"commands.unrelated".to_string()));
+
+    let skylos = Skylos::new(0, false, false, false);
+    let result = skylos.analyze(dir.path()).unwrap();
+    let confidence = |name: &str| {
+        result
+            .unused_functions
+            .iter()
+            .find(|f| f.full_name == name)
+            .map(|f| f.confidence)
+    };
+    assert_eq!(confidence("commands.cmd_stop"), Some(40));
+    // `globals()[name]` lowers confidence for the whole module.
+    assert_eq!(confidence("registry.register"), Some(60));
+    assert_eq!(confidence("registry.lookup"), Some(60));
+}
+
+#[test]
+fn test_imports_used_outside_expressions() {
+    let dir = tempdir().unwrap();
+    write_file(
+        dir.path(),
+        "app.py",
+        r#"
+from typing import Optional
+from functools import lru_cache
+from errors import AppError
+from meta import Registry
+from defaults import DEFAULT_SIZE
+from unused import nothing
+
+@lru_cache
+def load(size: Optional[int] = DEFAULT_SIZE) -> Optional[str]:
+    if size is None:
+        raise AppError("missing size")
+    return None
+
+class Plugin(metaclass=Registry):
+    pass
+
+load()
+Plugin()
+"#,
+    );
+
+    let skylos = Skylos::new(60, false, false, false);
+    let result = skylos.analyze(dir.path()).unwrap();
+
+    let unused: Vec<&str> = result
+        .unused_imports
+        .iter()
+        .map(|i| i.simple_name.as_str())
+        .collect();
+    assert_eq!(unused, vec!["nothing"]);
+}
+
+#[test]
+fn test_typing_only_imports() {
+    let dir = tempdir().unwrap();
+    write_file(
+        dir.path(),
+        "app.py",
+        r#"
+from typing import TYPE_CHECKING
+from decimal import Decimal
+from models import Account
+from shapes import Shape
+
+if TYPE_CHECKING:
+    from models import User
+    from models import Unused
+
+def total(user: "User", account: "Account", shape: Shape) -> Decimal:
+    value: "Decimal" = Decimal(0)
+    return value
+
+total(None, None, None)
+"#,
+    );
+
+    let skylos = Skylos::new(60, false, false, false);
+    let result = skylos.analyze(dir.path()).unwrap();
+
+    let typing_only: Vec<&str> = result
+        .typing_only_imports
+        .iter()
+        .map(|i| i.simple_name.as_str())
+        .collect();
+    // `Account` is only used in a string annotation; `Shape` is used in an evaluated one,
+    // and `User` is already imported for type checking only.
+    assert_eq!(typing_only, vec!["Account"]);
+
+    let unused: Vec<&str> = result
+        .unused_imports
+        .iter()
+        .map(|i| i.simple_name.as_str())
+        .collect();
+    assert_eq!(unused, vec!["Unused"]);
+}
+
+#[test]
+fn test_unreachable_code() {
+    let dir = tempdir().unwrap();
+    write_file(
+        dir.path(),
+        "app.py",
+        r#"
+def handler(x):
+    if x:
+        return 1
+    raise ValueError(x)
+    log(x)
+    return 0
+
+handler(1)
+"#,
+    );
+
+    let skylos = Skylos::new(60, false, false, false);
+    let result = skylos.analyze(dir.path()).unwrap();
+
+    assert_eq!(result.unreachable_code.len(), 1);
+    let block = &result.unreachable_code[0];
+    assert_eq!(block.rule_id, "SKY-U001");
+    assert_eq!((block.line, block.end_line), (6, 7));
+    assert!(block.message.contains("'raise'"));
+}
+
+#[test]
+fn test_unused_attributes() {
+    let dir = tempdir().unwrap();
+    write_file(
+        dir.path(),
+        "shapes.py",
+        r#"
+class Shape:
+    label = "shape"
+    stale = "never read"
+
+    def __init__(self):
+        self.width = 1
+        self.height = 2
+        self.scratch = 0
+        self.color = "red"
+
+    def area(self):
+        return self.width * self.height
+
+    def describe(self):
+        return self.label
+
+
+class Square(Shape):
+    label = "square"
+    sides = 4
+"#,
+    );
+    write_file(
+        dir.path(),
+        "app.py",
+        r#"
+from shapes import Shape, Square
+
+shape = Square()
+print(shape.area(), shape.describe(), shape.color, getattr(shape, "sides"))
+"#,
+    );
+
+    let skylos = Skylos::new(50, false, false, false);
+    let result = skylos.analyze(dir.path()).unwrap();
+
+    let mut unused: Vec<&str> = result
+        .unused_attributes
+        .iter()
+        .map(|a| a.full_name.as_str())
+        .collect();
+    unused.sort();
+    // `width`/`height` are read through `self`, `color` through another object, `sides`
+    // through `getattr`, and `Square.label` overrides an attribute the base class reads.
+    assert_eq!(unused, vec!["shapes.Shape.scratch", "shapes.Shape.stale"]);
+}
+
+#[test]
+fn test_whitelist() {
+    let dir = tempdir().unwrap();
+    write_file(
+        dir.path(),
+        "app.py",
+        r#"
+import os
+
+def hook(event, context):
+    leftover = 1
+    return event
+
+def callback():
+    return helper()
+
+def helper():
+    return 1
+
+class Handler:
+    def on_load(self):
+        pass
+
+    def process(self):
+        pass
+"#,
+    );
+
+    let mut skylos = Skylos::new(60, false, false, false);
+    let result = skylos.analyze(dir.path()).unwrap();
+    let whitelist = skylos_rs::whitelist::make_whitelist(&result);
+    assert!(whitelist.contains("app.callback  # unused function"));
+    assert!(whitelist.contains("app.os  # unused import"));
+    assert!(whitelist.contains("app.hook.leftover  # unused variable"));
+
+    // Loading the generated whitelist accepts every finding.
+    let path = dir.path().join("whitelist.txt");
+    fs::write(&path, whitelist).unwrap();
+    skylos.whitelist = skylos_rs::whitelist::load_whitelist(&path).unwrap();
+    let result = skylos.analyze(dir.path()).unwrap();
+    assert!(result.unused_functions.is_empty());
+    assert!(result.unused_imports.is_empty());
+    assert!(result.unused_classes.is_empty());
+    assert!(result.unused_variables.is_empty());
+    assert!(result.unused_parameters.is_empty());
+
+    // Simple names whitelist every definition with that name, and keep callees alive.
+    skylos.whitelist = vec!["callback".to_string(), "process".to_string()];
+    let result = skylos.analyze(dir.path()).unwrap();
+    let unused: Vec<&str> = result
+        .unused_functions
+        .iter()
+        .map(|f| f.simple_name.as_str())
+        .collect();
+    assert_eq!(unused, vec!["hook"]);
+}
+
+#[test]
+fn test_dead_modules() {
+    let dir = tempdir().unwrap();
+    write_file(
+        dir.path(),
+        "pyproject.toml",
+        "[project.scripts]\napp = \"app.cli:main\"\n",
+    );
+    write_file(dir.path(), "app/__init__.py", "");
+    write_file(
+        dir.path(),
+        "app/cli.py",
+        "from app import core\nfrom .plugins import loader\n\ndef main():\n    core.run(loader)\n",
+    );
+    write_file(dir.path(), "app/core.py", "def run(loader):\n    pass\n");
+    write_file(dir.path(), "app/plugins/__init__.py", "");
+    write_file(
+        dir.path(),
+        "app/plugins/loader.py",
+        "import importlib\n\ndef load(name):\n    importlib.import_module(\"app.plugins.csv\")\n    return importlib.import_module(f\"app.extras.{name}\")\n",
+    );
+    write_file(dir.path(), "app/plugins/csv.py", "");
+    write_file(dir.path(), "app/extras/__init__.py", "");
+    write_file(dir.path(), "app/extras/json.py", "");
+    write_file(
+        dir.path(),
+        "app/settings.py",
+        "WSGI = \"app.wsgi:application\"\n",
+    );
+    write_file(dir.path(), "app/wsgi.py", "application = None\n");
+    write_file(dir.path(), "app/__main__.py", "");
+    write_file(
+        dir.path(),
+        "app/tool.py",
+        "def run():\n    pass\n\nif __name__ == \"__main__\":\n    run()\n",
+    );
+    write_file(dir.path(), "app/legacy.py", "def old():\n    pass\n\n\n");
+    write_file(dir.path(), "app/unused.py", "X = 1\n");
+    write_file(
+        dir.path(),
+        "tests/test_core.py",
+        "def test_run():\n    pass\n",
+    );
+    write_file(
+        dir.path(),
+        "setup.py",
+        "from setuptools import setup\n\nsetup()\n",
+    );
+
+    let skylos = Skylos::new(60, false, false, false);
+    let result = skylos.analyze(dir.path()).unwrap();
+    let dead: Vec<(&str, usize)> = result
+        .dead_modules
+        .iter()
+        .map(|m| (m.module_name.as_str(), m.line_count))
+        .collect();
+    // `app.settings` is loaded by a framework in practice, but nothing in the project
+    // names it. Modules are listed largest first.
+    assert_eq!(
+        dead,
+        vec![("app.legacy", 4), ("app.settings", 1), ("app.unused", 1)]
+    );
+    assert_eq!(result.dead_modules[0].rule_id, "SKY-M001");
+
+    // Modules matching a dynamic import prefix are only reported at low thresholds.
+    let skylos = Skylos::new(30, false, false, false);
+    let result = skylos.analyze(dir.path()).unwrap();
+    assert!(result
+        .dead_modules
+        .iter()
+        .any(|m| m.module_name == "app.extras.json" && m.confidence == 40));
+}
+
+#[test]
+fn test_dependency_report() {
+    let dir = tempdir().unwrap();
+    write_file(
+        dir.path(),
+        "pyproject.toml",
+        "[project]\nname = \"app\"\ndependencies = [\"requests\", \"PyYAML\", \"rich\"]\n",
+    );
+    write_file(dir.path(), "app/__init__.py", "");
+    write_file(
+        dir.path(),
+        "app/main.py",
+        "import os\nimport yaml\nimport numpy as np\nfrom . import util\nfrom app.util import helper\n\nhelper(os, yaml, np)\n",
+    );
+    write_file(
+        dir.path(),
+        "app/util.py",
+        "import importlib\n\ndef helper(*args):\n    return importlib.import_module(\"rich.console\")\n",
+    );
+
+    let skylos = Skylos::new(60, false, false, false);
+    let result = skylos.analyze(dir.path()).unwrap();
+    let unused: Vec<&str> = result
+        .unused_dependencies
+        .iter()
+        .map(|d| d.name.as_str())
+        .collect();
+    assert_eq!(unused, vec!["requests"]);
+    let undeclared: Vec<(&str, usize)> = result
+        .undeclared_dependencies
+        .iter()
+        .map(|d| (d.name.as_str(), d.line))
+        .collect();
+    assert_eq!(undeclared, vec![("numpy", 3)]);
+}
+
+#[test]
+fn test_import_cycles() {
+    let dir = tempdir().unwrap();
+    write_file(dir.path(), "app/__init__.py", "");
+    write_file(
+        dir.path(),
+        "app/models.py",
+        "from app.services import save\n\ndef create():\n    return save()\n",
+    );
+    write_file(
+        dir.path(),
+        "app/services.py",
+        "import os\nfrom . import models\n\ndef save():\n    return models\n",
+    );
+    write_file(
+        dir.path(),
+        "app/views.py",
+        "def render():\n    from app.api import client\n    return client\n",
+    );
+    write_file(
+        dir.path(),
+        "app/api.py",
+        "from app.views import render\n\nclient = render\n",
+    );
+
+    let skylos = Skylos::new(60, false, false, false);
+    let result = skylos.analyze(dir.path()).unwrap();
+    let cycles: Vec<(&str, &str)> = result
+        .import_cycles
+        .iter()
+        .map(|c| (c.rule_id.as_str(), c.message.as_str()))
+        .collect();
+    assert_eq!(
+        cycles,
+        vec![
+            (
+                "SKY-C001",
+                "Import cycle: app.models -> app.services -> app.models"
+            ),
+            (
+                "SKY-C002",
+                "Import cycle through function-local imports: app.views -> app.api -> app.views"
+            ),
+        ]
+    );
+    let lines: Vec<usize> = result.import_cycles[0]
+        .path
+        .iter()
+        .map(|s| s.line)
+        .collect();
+    assert_eq!(lines, vec![1, 2]);
+}
+
+#[test]
+fn test_collect_graphs() {
+    let dir = tempdir().unwrap();
+    write_file(dir.path(), "app/__init__.py", "");
+    write_file(
+        dir.path(),
+        "app/main.py",
+        "from app.services import save\n\nif __name__ == \"__main__\":\n    save()\n",
+    );
+    write_file(
+        dir.path(),
+        "app/services.py",
+        "def save():\n    return helper()\n\ndef helper():\n    return 1\n\ndef orphan():\n    return helper()\n",
+    );
+    write_file(dir.path(), "app/legacy.py", "def old():\n    pass\n");
+
+    let skylos = Skylos::new(60, false, false, false);
+    let result = skylos.analyze(dir.path()).unwrap();
+    assert!(result.module_graph.is_none());
+    assert!(result.symbol_graph.is_none());
+
+    let mut skylos = Skylos::new(60, false, false, false);
+    skylos.collect_graphs = true;
+    let result = skylos.analyze(dir.path()).unwrap();
+
+    let modules = result.module_graph.unwrap();
+    let nodes: Vec<(&str, bool)> = modules
+        .nodes
+        .iter()
+        .map(|n| (n.id.as_str(), n.dead))
+        .collect();
+    assert_eq!(
+        nodes,
+        vec![
+            ("app", false),
+            ("app.legacy", true),
+            ("app.main", false),
+            ("app.services", false),
+        ]
+    );
+    let edges: Vec<(&str, &str, &str)> = modules
+        .edges
+        .iter()
+        .map(|e| {
+            (
+                modules.nodes[e.from].id.as_str(),
+                modules.nodes[e.to].id.as_str(),
+                e.kind.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(edges, vec![("app.main", "app.services", "module-level")]);
+
+    let symbols = result.symbol_graph.unwrap();
+    let nodes: Vec<(&str, bool)> = symbols
+        .nodes
+        .iter()
+        .map(|n| (n.id.as_str(), n.dead))
+        .collect();
+    assert_eq!(
+        nodes,
+        vec![
+            ("app.legacy.old", true),
+            ("app.services.helper", false),
+            ("app.services.orphan", true),
+            ("app.services.save", false),
+        ]
+    );
+    let edges: Vec<(&str, &str)> = symbols
+        .edges
+        .iter()
+        .map(|e| {
+            (
+                symbols.nodes[e.from].id.as_str(),
+                symbols.nodes[e.to].id.as_str(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        edges,
+        vec![
+            ("app.services.orphan", "app.services.helper"),
+            ("app.services.save", "app.services.helper"),
+        ]
+    );
+}
+
+#[test]
+fn test_configured_analysis() {
+    let dir = tempdir().unwrap();
+    write_file(dir.path(), "app/a.py", "def unused():\n    pass\n");
+    write_file(
+        dir.path(),
+        "app/migrations/m0001.py",
+        "def forward():\n    pass\n",
+    );
+    write_file(dir.path(), "scripts/tool.py", "def run():\n    pass\n");
+    write_file(
+        dir.path(),
+        "app/nested.py",
+        "def outer():\n    if True:\n        for x in []:\n            pass\n",
+    );
+
+    let mut skylos = Skylos::new(60, false, false, true);
+    skylos.include = vec!["app/**".to_string()];
+    skylos.exclude = vec!["migrations".to_string()];
+    skylos.max_nesting_depth = 2;
+    skylos
+        .severities
+        .insert("SKY-Q001".to_string(), "HIGH".to_string());
+    let result = skylos.analyze(dir.path()).unwrap();
+
+    let mut unused: Vec<&str> = result
+        .unused_functions
+        .iter()
+        .map(|f| f.full_name.as_str())
+        .collect();
+    unused.sort();
+    assert_eq!(unused, vec!["app.a.unused", "app.nested.outer"]);
+    assert_eq!(result.analysis_summary.total_files, 2);
+
+    let quality: Vec<(&str, usize, &str)> = result
+        .quality
+        .iter()
+        .map(|q| (q.rule_id.as_str(), q.line, q.severity.as_str()))
+        .collect();
+    assert_eq!(quality, vec![("SKY-Q001", 3, "HIGH")]);
+}
+
+#[test]
+fn test_analyze_multiple_paths() {
+    let dir = tempdir().unwrap();
+    write_file(
+        dir.path(),
+        "src/app/core.py",
+        "def helper():\n    pass\n\ndef unused():\n    pass\n",
+    );
+    write_file(
+        dir.path(),
+        "tests/test_core.py",
+        "from app.core import helper\n\ndef test_helper():\n    helper()\n",
+    );
+
+    let skylos = Skylos::new(60, false, false, false);
+    let result = skylos
+        .analyze_paths(&[dir.path().join("src"), dir.path().join("tests")])
+        .unwrap();
+
+    // Module names are relative to each path, so the test import resolves.
+    let unused: Vec<&str> = result
+        .unused_functions
+        .iter()
+        .map(|f| f.full_name.as_str())
+        .collect();
+    assert_eq!(unused, vec!["app.core.unused"]);
+    assert_eq!(
+        result.unused_functions[0].file,
+        dir.path().join("src/app/core.py")
+    );
+    assert_eq!(result.analysis_summary.total_files, 2);
+}
+
+#[test]
+fn test_analyze_overlapping_paths() {
+    let dir = tempdir().unwrap();
+    write_file(
+        dir.path(),
+        "pkg/a.py",
+        "def foo():\n    pass\n\ndef unused():\n    pass\n",
+    );
+    write_file(dir.path(), "main.py", "from pkg.a import foo\n\nfoo()\n");
+
+    // `pkg/a.py` is module `pkg.a` of the outer path, not `a` of `pkg`.
+    let skylos = Skylos::new(60, false, false, false);
+    let result = skylos
+        .analyze_paths(&[dir.path().join("pkg"), dir.path().to_path_buf()])
+        .unwrap();
+    let unused: Vec<&str> = result
+        .unused_functions
+        .iter()
+        .map(|f| f.full_name.as_str())
+        .collect();
+    assert_eq!(unused, vec!["pkg.a.unused"]);
+    assert!(result.unused_imports.is_empty());
+    assert_eq!(result.analysis_summary.total_files, 2);
+}
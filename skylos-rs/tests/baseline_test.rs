@@ -0,0 +1,120 @@
+// Unit tests for baseline files
+// Tests fingerprints, stale entries and matching of repeated findings
+
+use skylos_rs::analyzer::{AnalysisResult, Skylos};
+use skylos_rs::baseline::{Baseline, BASELINE_VERSION};
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+fn analyze(root: &Path) -> AnalysisResult {
+    Skylos::new(0, false, true, false).analyze(root).unwrap()
+}
+
+fn function_names(result: &AnalysisResult) -> Vec<&str> {
+    result
+        .unused_functions
+        .iter()
+        .map(|f| f.simple_name.as_str())
+        .collect()
+}
+
+#[test]
+fn test_baseline_hides_known_findings() {
+    let dir = tempdir().unwrap();
+    let root = dir.path();
+    let baseline_path = root.join("skylos-baseline.json");
+    fs::write(
+        root.join("app.py"),
+        "import subprocess\n\ndef old():\n    subprocess.call('ls', shell=True)\n",
+    )
+    .unwrap();
+
+    let result = analyze(root);
+    assert_eq!(function_names(&result), vec!["old"]);
+    assert_eq!(result.danger.len(), 1);
+    Baseline::from_result(&result, &baseline_path)
+        .save(&baseline_path)
+        .unwrap();
+
+    let baseline = Baseline::load(&baseline_path).unwrap();
+    assert_eq!(baseline.version, BASELINE_VERSION);
+    let rules: Vec<&str> = baseline.findings.iter().map(|e| e.rule.as_str()).collect();
+    assert_eq!(
+        rules,
+        vec!["unused_functions", "unused_imports", "SKY-M001", "SKY-D002"]
+    );
+    assert!(baseline
+        .findings
+        .iter()
+        .all(|entry| entry.file == Path::new("app.py")));
+
+    // Shifting and reindenting the code keeps the findings known.
+    fs::write(
+        root.join("app.py"),
+        "\"\"\"App.\"\"\"\nimport subprocess\n\n\ndef old():\n    subprocess.call('ls',   shell=True)\n\ndef new():\n    subprocess.call('rm', shell=True)\n",
+    )
+    .unwrap();
+    let mut result = analyze(root);
+    let stale = baseline.apply(&mut result, &baseline_path);
+    assert!(stale.is_empty());
+    assert_eq!(function_names(&result), vec!["new"]);
+    assert_eq!(result.danger.len(), 1);
+    assert_eq!(result.danger[0].line, 9);
+    assert_eq!(result.analysis_summary.danger_count, 1);
+}
+
+#[test]
+fn test_baseline_stale_entries() {
+    let dir = tempdir().unwrap();
+    let root = dir.path();
+    let baseline_path = root.join("baseline.json");
+    fs::write(
+        root.join("app.py"),
+        "def old():\n    pass\n\ndef gone():\n    pass\n",
+    )
+    .unwrap();
+
+    let baseline = Baseline::from_result(&analyze(root), &baseline_path);
+    fs::write(root.join("app.py"), "def old():\n    pass\n").unwrap();
+
+    let mut result = analyze(root);
+    let stale = baseline.apply(&mut result, &baseline_path);
+    assert!(result.unused_functions.is_empty());
+    assert_eq!(stale.len(), 1);
+    assert_eq!(stale[0].rule, "unused_functions");
+    assert_eq!(stale[0].name.as_deref(), Some("app.gone"));
+}
+
+#[test]
+fn test_baseline_counts_repeated_findings() {
+    let dir = tempdir().unwrap();
+    let root = dir.path();
+    let baseline_path = root.join("baseline.json");
+    let call = "import subprocess\nsubprocess.call('ls', shell=True)\n";
+    fs::write(root.join("app.py"), call).unwrap();
+
+    let baseline = Baseline::from_result(&analyze(root), &baseline_path);
+
+    // The same line a second time is a new finding.
+    fs::write(
+        root.join("app.py"),
+        format!("{}subprocess.call('ls', shell=True)\n", call),
+    )
+    .unwrap();
+    let mut result = analyze(root);
+    let stale = baseline.apply(&mut result, &baseline_path);
+    assert!(stale.is_empty());
+    assert_eq!(result.danger.len(), 1);
+}
+
+#[test]
+fn test_baseline_rejects_unknown_version() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("baseline.json");
+    fs::write(&path, "{\"version\": 99, \"findings\": []}").unwrap();
+    let error = Baseline::load(&path).unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("unsupported baseline version 99"));
+}
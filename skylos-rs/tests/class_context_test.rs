@@ -39,7 +39,7 @@ class MyClass:
     assert!(defs.contains(&"test_module.MyClass.another_method".to_string()));
 
     // Check references
-    let refs: Vec<String> = visitor.references.iter().map(|r| r.0.clone()).collect();
+    let refs: Vec<String> = visitor.reference_names().map(str::to_string).collect();
     println!("References: {:?}", refs);
     assert!(refs.contains(&"test_module.MyClass.my_method".to_string()));
 }
@@ -36,7 +36,7 @@ class ChildClass(BaseClass):
         }
     }
 
-    let refs: Vec<String> = visitor.references.iter().map(|r| r.0.clone()).collect();
+    let refs: Vec<String> = visitor.reference_names().map(str::to_string).collect();
     println!("References: {:?}", refs);
 
     // 1. Verify Base Class reference
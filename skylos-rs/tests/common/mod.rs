@@ -0,0 +1,11 @@
+// Helpers shared by the test files
+
+use std::fs;
+use std::path::Path;
+
+/// Writes `content` to `root/rel`, creating parent directories.
+pub fn write_file(root: &Path, rel: &str, content: &str) {
+    let path = root.join(rel);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, content).unwrap();
+}
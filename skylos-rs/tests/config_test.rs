@@ -0,0 +1,157 @@
+// Unit tests for configuration files
+// Tests discovery, parsing and validation of `.skylos.toml` and `[tool.skylos]`
+
+mod common;
+
+use common::write_file;
+use skylos_rs::config::Config;
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn test_pyproject_config() {
+    let dir = tempdir().unwrap();
+    write_file(
+        dir.path(),
+        "pyproject.toml",
+        r#"
+[project]
+name = "app"
+
+[tool.ruff]
+line-length = 100
+
+[tool.skylos]
+confidence = 80
+danger = true
+exclude = ["migrations", "build/"]
+include = ["src/**"]
+whitelist = ["tools/whitelist.py"]
+public-api = ["app.api"]
+fail-on = "high"
+max-dead-code = 10
+
+[tool.skylos.severity]
+SKY-Q001 = "high"
+
+[tool.skylos.thresholds]
+max-nesting-depth = 3
+
+[tool.skylos.max-findings]
+danger = 0
+"#,
+    );
+    write_file(dir.path(), "src/app/main.py", "");
+
+    let (config, file) = Config::discover(&dir.path().join("src/app"))
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        file,
+        fs::canonicalize(dir.path()).unwrap().join("pyproject.toml")
+    );
+    assert_eq!(config.confidence, Some(80));
+    assert_eq!(config.danger, Some(true));
+    assert_eq!(config.secrets, None);
+    assert_eq!(config.exclude, vec!["migrations", "build/"]);
+    assert_eq!(config.include, vec!["src/**"]);
+    assert_eq!(config.public_api, vec!["app.api"]);
+    assert_eq!(config.severity.get("SKY-Q001").unwrap(), "HIGH");
+    assert_eq!(config.thresholds.max_nesting_depth, Some(3));
+    assert_eq!(config.fail_on.as_deref(), Some("HIGH"));
+    assert_eq!(config.max_dead_code, Some(10));
+    assert_eq!(config.max_findings.get("danger"), Some(&0));
+    // Whitelists are relative to the configuration file.
+    assert_eq!(
+        config.whitelist,
+        vec![fs::canonicalize(dir.path())
+            .unwrap()
+            .join("tools/whitelist.py")]
+    );
+}
+
+#[test]
+fn test_discovery_order() {
+    let dir = tempdir().unwrap();
+    write_file(dir.path(), ".skylos.toml", "confidence = 90\n");
+    // Without `[tool.skylos]`, a `pyproject.toml` is skipped.
+    write_file(
+        dir.path(),
+        "pkg/pyproject.toml",
+        "[project]\nname = \"pkg\"\n",
+    );
+    write_file(dir.path(), "pkg/mod.py", "");
+
+    let (config, file) = Config::discover(&dir.path().join("pkg/mod.py"))
+        .unwrap()
+        .unwrap();
+    assert_eq!(config.confidence, Some(90));
+    assert!(file.ends_with(".skylos.toml"));
+
+    // `.skylos.toml` takes precedence over `pyproject.toml` in the same directory.
+    write_file(
+        dir.path(),
+        "pyproject.toml",
+        "[tool.skylos]\nconfidence = 10\n",
+    );
+    let (config, _) = Config::discover(dir.path()).unwrap().unwrap();
+    assert_eq!(config.confidence, Some(90));
+
+    let empty = tempdir().unwrap();
+    write_file(empty.path(), "pyproject.toml", "[project]\nname = \"x\"\n");
+    assert!(Config::discover(empty.path()).unwrap().is_none());
+}
+
+#[test]
+fn test_unknown_keys_are_rejected() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join(".skylos.toml");
+
+    fs::write(&path, "confidance = 50\n").unwrap();
+    let error = format!("{:#}", Config::load(&path).unwrap_err());
+    assert!(error.contains("unknown field `confidance`"), "{}", error);
+    assert!(error.contains("invalid configuration in"), "{}", error);
+
+    fs::write(&path, "[thresholds]\nmax-depth = 3\n").unwrap();
+    let error = format!("{:#}", Config::load(&path).unwrap_err());
+    assert!(error.contains("unknown field `max-depth`"), "{}", error);
+
+    let pyproject = dir.path().join("pyproject.toml");
+    fs::write(&pyproject, "[tool.skylos]\nsecret = true\n").unwrap();
+    let error = format!("{:#}", Config::load(&pyproject).unwrap_err());
+    assert!(error.contains("unknown field `secret`"), "{}", error);
+}
+
+#[test]
+fn test_invalid_values_are_rejected() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join(".skylos.toml");
+
+    fs::write(&path, "[severity]\nSKY-X999 = \"HIGH\"\n").unwrap();
+    let error = format!("{:#}", Config::load(&path).unwrap_err());
+    assert!(error.contains("unknown rule `SKY-X999`"), "{}", error);
+
+    fs::write(&path, "[severity]\nSKY-D001 = \"urgent\"\n").unwrap();
+    let error = format!("{:#}", Config::load(&path).unwrap_err());
+    assert!(error.contains("unknown severity `urgent`"), "{}", error);
+
+    fs::write(&path, "confidence = 120\n").unwrap();
+    let error = format!("{:#}", Config::load(&path).unwrap_err());
+    assert!(error.contains("between 0 and 100"), "{}", error);
+
+    fs::write(&path, "fail-on = \"urgent\"\n").unwrap();
+    let error = format!("{:#}", Config::load(&path).unwrap_err());
+    assert!(
+        error.contains("unknown severity `urgent` in `fail-on`"),
+        "{}",
+        error
+    );
+
+    fs::write(&path, "[max-findings]\nunused = 0\n").unwrap();
+    let error = format!("{:#}", Config::load(&path).unwrap_err());
+    assert!(error.contains("unknown category `unused`"), "{}", error);
+
+    fs::write(&path, "exclude = [\"[\"]\n").unwrap();
+    let error = format!("{:#}", Config::load(&path).unwrap_err());
+    assert!(error.contains("invalid exclude pattern"), "{}", error);
+}
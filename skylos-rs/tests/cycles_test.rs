@@ -0,0 +1,129 @@
+use skylos_rs::cycles::{ImportCycle, ImportGraph};
+use skylos_rs::visitor::ImportContext;
+use std::path::PathBuf;
+
+fn graph(modules: &[&str], imports: &[(&str, &str, usize, ImportContext)]) -> ImportGraph {
+    let mut graph = ImportGraph::new();
+    for module in modules {
+        graph.add_module(
+            module,
+            PathBuf::from(format!("{}.py", module.replace('.', "/"))),
+        );
+    }
+    for (from, imported, line, context) in imports {
+        graph.add_import(from, imported, *line, *context);
+    }
+    graph
+}
+
+/// Returns `(rule_id, message, [(file, line)])` for a cycle.
+fn summary(cycle: &ImportCycle) -> (&str, &str, Vec<(String, usize)>) {
+    let steps = cycle
+        .path
+        .iter()
+        .map(|step| (step.file.display().to_string(), step.line))
+        .collect();
+    (cycle.rule_id.as_str(), cycle.message.as_str(), steps)
+}
+
+#[test]
+fn test_module_level_cycle() {
+    use ImportContext::Module;
+    let graph = graph(
+        &["app.a", "app.b", "app.c", "app.d"],
+        &[
+            ("app.a", "app.b.helper", 1, Module),
+            ("app.b", "app.c", 2, Module),
+            ("app.c", "app.a", 3, Module),
+            ("app.c", "app.b", 4, Module),
+            // Imports of outside modules and of the module itself are ignored.
+            ("app.d", "os.path", 1, Module),
+            ("app.d", "app.d.x", 2, Module),
+        ],
+    );
+
+    let cycles = graph.find_cycles();
+    assert_eq!(cycles.len(), 1);
+    assert_eq!(cycles[0].modules, vec!["app.a", "app.b", "app.c"]);
+    assert_eq!(cycles[0].kind, "module-level");
+    assert_eq!(
+        summary(&cycles[0]),
+        (
+            "SKY-C001",
+            "Import cycle: app.a -> app.b -> app.c -> app.a",
+            vec![
+                ("app/a.py".to_string(), 1),
+                ("app/b.py".to_string(), 2),
+                ("app/c.py".to_string(), 3),
+            ]
+        )
+    );
+}
+
+#[test]
+fn test_deferred_cycles() {
+    use ImportContext::{Function, Module, TypeChecking};
+    let graph = graph(
+        &["models", "services", "types", "views"],
+        &[
+            ("models", "services", 1, Module),
+            ("services", "models", 10, Function),
+            ("types", "views", 1, Module),
+            ("views", "types", 2, TypeChecking),
+        ],
+    );
+
+    let cycles = graph.find_cycles();
+    let summaries: Vec<_> = cycles.iter().map(summary).collect();
+    assert_eq!(
+        summaries,
+        vec![
+            (
+                "SKY-C002",
+                "Import cycle through function-local imports: services -> models -> services",
+                vec![
+                    ("services.py".to_string(), 10),
+                    ("models.py".to_string(), 1)
+                ]
+            ),
+            (
+                "SKY-C003",
+                "Import cycle through type-checking imports: views -> types -> views",
+                vec![("views.py".to_string(), 2), ("types.py".to_string(), 1)]
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_module_level_import_takes_precedence() {
+    use ImportContext::{Function, Module};
+    let graph = graph(
+        &["a", "b", "c"],
+        &[
+            ("a", "b", 5, Function),
+            ("a", "b", 9, Module),
+            ("b", "a", 1, Module),
+            // `c` is only part of the cycle through a deferred import.
+            ("b", "c", 2, Module),
+            ("c", "a", 7, Function),
+        ],
+    );
+
+    let cycles = graph.find_cycles();
+    assert_eq!(cycles.len(), 2);
+    assert_eq!(
+        summary(&cycles[0]),
+        (
+            "SKY-C001",
+            "Import cycle: a -> b -> a",
+            vec![("a.py".to_string(), 9), ("b.py".to_string(), 1)]
+        )
+    );
+    assert_eq!(cycles[1].rule_id, "SKY-C002");
+    assert_eq!(cycles[1].modules, vec!["a", "b", "c"]);
+    assert_eq!(
+        cycles[1].message,
+        "Import cycle through function-local imports: c -> a -> b -> c"
+    );
+}
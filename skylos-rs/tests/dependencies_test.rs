@@ -0,0 +1,183 @@
+// Unit tests for the dependency report
+// Tests reading dependency manifests and mapping distributions to import names
+
+use skylos_rs::dependencies::{check_dependencies, import_names, load_dependencies};
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+use tempfile::tempdir;
+
+/// Returns `(name, group, file name, line)` for every dependency declared in `dir`.
+fn declared(dir: &std::path::Path) -> Vec<(String, Option<String>, String, usize)> {
+    load_dependencies(dir)
+        .expect("Manifests should be found")
+        .into_iter()
+        .map(|d| {
+            let file = d.file.file_name().unwrap().to_string_lossy().to_string();
+            (d.name, d.group, file, d.line)
+        })
+        .collect()
+}
+
+#[test]
+fn test_pyproject_dependencies() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join("pyproject.toml"),
+        r#"[build-system]
+requires = ["setuptools>=61"]
+
+[project]
+name = "app"
+dependencies = [
+    "requests>=2.0",
+    "PyYAML[libyaml] ; python_version >= '3.8'",
+]
+
+[project.optional-dependencies]
+test = ["pytest"]
+
+[dependency-groups]
+lint = ["ruff"]
+
+[tool.poetry.dependencies]
+python = "^3.9"
+rich = "^13"
+
+[tool.poetry.group.docs.dependencies]
+mkdocs = "*"
+"#,
+    )
+    .unwrap();
+
+    let some = |group: &str| Some(group.to_string());
+    let pyproject = |name: &str, group: Option<String>, line: usize| {
+        (name.to_string(), group, "pyproject.toml".to_string(), line)
+    };
+    assert_eq!(
+        declared(dir.path()),
+        vec![
+            pyproject("requests", None, 7),
+            pyproject("PyYAML", None, 8),
+            pyproject("pytest", some("test"), 12),
+            pyproject("ruff", some("lint"), 15),
+            pyproject("setuptools", some("build-system"), 2),
+            pyproject("rich", None, 19),
+            pyproject("mkdocs", some("docs"), 22),
+        ]
+    );
+}
+
+#[test]
+fn test_requirements_and_setup_files() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join("requirements.txt"),
+        "# comment\nflask>=2.0  # web\n-r requirements-dev.txt\n-e .\nhttps://example.com/pkg.tar.gz\nmypkg @ https://example.com/mypkg.zip\n",
+    )
+    .unwrap();
+    fs::create_dir(dir.path().join("requirements")).unwrap();
+    fs::write(dir.path().join("requirements/dev.txt"), "black\n").unwrap();
+    fs::write(
+        dir.path().join("setup.py"),
+        r#"from setuptools import setup
+
+REQUIRES = ["numpy>=1.20", "pandas"]
+
+setup(
+    name="app",
+    install_requires=REQUIRES,
+    extras_require={"plot": ["matplotlib"]},
+)
+"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("setup.cfg"),
+        "[metadata]\nname = app\n\n[options]\ninstall_requires =\n    click>=8\n    attrs\nzip_safe = False\n\n[options.extras_require]\nyaml = PyYAML\n",
+    )
+    .unwrap();
+
+    let names: Vec<(String, Option<String>, String, usize)> = declared(dir.path());
+    let summary: Vec<(&str, Option<&str>, &str, usize)> = names
+        .iter()
+        .map(|(n, g, f, l)| (n.as_str(), g.as_deref(), f.as_str(), *l))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("numpy", None, "setup.py", 3),
+            ("pandas", None, "setup.py", 3),
+            ("matplotlib", Some("plot"), "setup.py", 8),
+            ("click", None, "setup.cfg", 6),
+            ("attrs", None, "setup.cfg", 7),
+            ("PyYAML", Some("yaml"), "setup.cfg", 11),
+            ("black", None, "dev.txt", 1),
+            ("flask", None, "requirements.txt", 2),
+            ("mypkg", None, "requirements.txt", 6),
+        ]
+    );
+}
+
+#[test]
+fn test_no_manifests() {
+    let dir = tempdir().unwrap();
+    let project = dir.path().join("project");
+    fs::create_dir(&project).unwrap();
+    // Manifests are looked up from the analyzed directory upwards.
+    assert!(load_dependencies(&project).is_none());
+    fs::write(dir.path().join("requirements.txt"), "flask\n").unwrap();
+    assert_eq!(load_dependencies(&project).unwrap().len(), 1);
+}
+
+#[test]
+fn test_import_names() {
+    assert_eq!(import_names("PyYAML"), vec!["yaml"]);
+    assert_eq!(import_names("beautifulsoup4"), vec!["bs4"]);
+    assert_eq!(import_names("Pillow"), vec!["PIL"]);
+    assert_eq!(import_names("typing-extensions"), vec!["typing_extensions"]);
+    assert_eq!(import_names("Flask_Cors"), vec!["flask_cors"]);
+    assert_eq!(
+        import_names("zope.interface"),
+        vec!["zope_interface", "zope"]
+    );
+    assert_eq!(
+        import_names("google-cloud-storage"),
+        vec!["google_cloud_storage", "google"]
+    );
+}
+
+#[test]
+fn test_check_dependencies() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join("requirements.txt"),
+        "PyYAML\nrequests\nrequests\npytest-cov\ntypes-requests\n",
+    )
+    .unwrap();
+    let dependencies = load_dependencies(dir.path()).unwrap();
+
+    let used: HashSet<String> = ["yaml", "os", "app", "numpy"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let imports: Vec<(String, PathBuf, usize)> =
+        ["yaml", "os", "app", "numpy", "numpy", "_private"]
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (name.to_string(), PathBuf::from("app/main.py"), i + 1))
+            .collect();
+    let first_party: HashSet<String> = ["app".to_string()].into_iter().collect();
+
+    let (unused, undeclared) = check_dependencies(&dependencies, &used, &imports, &first_party);
+    let unused: Vec<(&str, &str, usize)> = unused
+        .iter()
+        .map(|f| (f.name.as_str(), f.rule_id.as_str(), f.line))
+        .collect();
+    assert_eq!(unused, vec![("requests", "SKY-P001", 2)]);
+    let undeclared: Vec<(&str, &str, usize)> = undeclared
+        .iter()
+        .map(|f| (f.name.as_str(), f.rule_id.as_str(), f.line))
+        .collect();
+    assert_eq!(undeclared, vec![("numpy", "SKY-P002", 4)]);
+}
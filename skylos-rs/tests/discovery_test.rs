@@ -0,0 +1,196 @@
+// Unit tests for file discovery
+// Tests ignore files, default excludes, multiple paths and symbolic links
+
+mod common;
+
+use common::write_file;
+use skylos_rs::discovery::{common_root, Discovery};
+use std::path::{Path, PathBuf};
+use tempfile::tempdir;
+
+/// Paths of the discovered files, relative to `root`.
+fn find(discovery: &Discovery, root: &Path, paths: &[&str]) -> Vec<String> {
+    let paths: Vec<PathBuf> = paths.iter().map(|path| root.join(path)).collect();
+    discovery
+        .find_files(&paths)
+        .unwrap()
+        .into_iter()
+        .map(|file| {
+            let path = file.path.strip_prefix(root).unwrap();
+            path.to_string_lossy().replace('\\', "/")
+        })
+        .collect()
+}
+
+#[test]
+fn test_ignore_files_and_default_excludes() {
+    let dir = tempdir().unwrap();
+    let root = dir.path();
+    write_file(root, ".gitignore", "generated/\n*_pb2.py\n");
+    write_file(root, "app/.ignore", "scratch.py\n");
+    write_file(root, "app/main.py", "");
+    write_file(root, "app/scratch.py", "");
+    write_file(root, "app/api_pb2.py", "");
+    write_file(root, "app/notes.txt", "");
+    write_file(root, "generated/models.py", "");
+    write_file(root, "build/lib/app/main.py", "");
+    write_file(root, "venv/lib/site.py", "");
+    write_file(root, "app/__pycache__/main.py", "");
+    write_file(root, "pkg.egg-info/setup.py", "");
+
+    let discovery = Discovery::default();
+    assert_eq!(find(&discovery, root, &[""]), vec!["app/main.py"]);
+
+    // An explicitly given file is analyzed even if it is ignored.
+    assert_eq!(
+        find(&discovery, root, &["generated/models.py"]),
+        vec!["generated/models.py"]
+    );
+}
+
+#[test]
+fn test_include_folder_and_globs() {
+    let dir = tempdir().unwrap();
+    let root = dir.path();
+    write_file(root, "app/main.py", "");
+    write_file(root, "app/migrations/0001_initial.py", "");
+    write_file(root, "build/tool.py", "");
+    write_file(root, "tests/test_main.py", "");
+
+    let discovery = Discovery {
+        exclude: vec!["migrations".to_string()],
+        include_folders: vec!["build".to_string()],
+        ..Discovery::default()
+    };
+    assert_eq!(
+        find(&discovery, root, &[""]),
+        vec!["app/main.py", "build/tool.py", "tests/test_main.py"]
+    );
+
+    // Including a folder also overrides a user exclude of it.
+    let discovery = Discovery {
+        exclude: vec!["migrations".to_string()],
+        include: vec!["app/**".to_string()],
+        include_folders: vec!["migrations".to_string()],
+    };
+    assert_eq!(
+        find(&discovery, root, &[""]),
+        vec!["app/main.py", "app/migrations/0001_initial.py"]
+    );
+}
+
+#[test]
+fn test_multiple_paths() {
+    let dir = tempdir().unwrap();
+    let root = dir.path();
+    write_file(root, "src/app/main.py", "");
+    write_file(root, "tests/test_main.py", "");
+    write_file(root, "scripts/run.py", "");
+
+    let files = Discovery::default()
+        .find_files(&[
+            root.join("src"),
+            root.join("tests"),
+            root.join("src/app"),
+            root.join("scripts/run.py"),
+        ])
+        .unwrap();
+    // Overlapping paths yield each file once, under the outermost path.
+    let found: Vec<(&Path, &Path)> = files
+        .iter()
+        .map(|file| {
+            (
+                file.path.strip_prefix(root).unwrap(),
+                file.root.strip_prefix(root).unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (Path::new("src/app/main.py"), Path::new("src")),
+            (Path::new("tests/test_main.py"), Path::new("tests")),
+            (Path::new("scripts/run.py"), Path::new("scripts")),
+        ]
+    );
+
+    assert_eq!(
+        common_root(&[&root.join("src"), &root.join("tests")]),
+        root.to_path_buf()
+    );
+    assert_eq!(common_root(&[Path::new("src")]), PathBuf::from("src"));
+    assert_eq!(
+        common_root(&[Path::new("src"), Path::new("tests")]),
+        PathBuf::from(".")
+    );
+}
+
+#[test]
+fn test_overlapping_paths_use_outermost_root() {
+    let dir = tempdir().unwrap();
+    let root = dir.path();
+    write_file(root, "pkg/a.py", "");
+    write_file(root, "pkg/sub/b.py", "");
+    write_file(root, "main.py", "");
+
+    // The inner paths come first, yet the module names stay relative to `root`.
+    let files = Discovery::default()
+        .find_files(&[
+            root.join("pkg/sub/b.py"),
+            root.join("pkg"),
+            root.to_path_buf(),
+        ])
+        .unwrap();
+    let mut found: Vec<(&Path, &Path)> = files
+        .iter()
+        .map(|file| {
+            (
+                file.path.strip_prefix(root).unwrap(),
+                file.root.strip_prefix(root).unwrap(),
+            )
+        })
+        .collect();
+    found.sort();
+    assert_eq!(
+        found,
+        vec![
+            (Path::new("main.py"), Path::new("")),
+            (Path::new("pkg/a.py"), Path::new("")),
+            (Path::new("pkg/sub/b.py"), Path::new("")),
+        ]
+    );
+}
+
+#[test]
+fn test_missing_path() {
+    let dir = tempdir().unwrap();
+    write_file(dir.path(), "app.py", "");
+
+    let error = Discovery::default()
+        .find_files(&[dir.path().to_path_buf(), dir.path().join("missing")])
+        .unwrap_err();
+    assert!(error.to_string().contains("does not exist"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_symlink_loops() {
+    use std::os::unix::fs::symlink;
+
+    let dir = tempdir().unwrap();
+    let root = dir.path();
+    write_file(root, "app/main.py", "");
+    write_file(root, "lib/util.py", "");
+    symlink(root, root.join("app/loop")).unwrap();
+    symlink(root.join("lib"), root.join("app/lib")).unwrap();
+    symlink(root.join("lib/util.py"), root.join("app/alias.py")).unwrap();
+
+    // Links are followed without looping, and files reached through several paths are
+    // kept under their shortest real path.
+    let mut files = find(&Discovery::default(), root, &[""]);
+    files.sort();
+    assert_eq!(files, vec!["app/main.py", "lib/util.py"]);
+    let mut files = find(&Discovery::default(), root, &["app"]);
+    files.sort();
+    assert_eq!(files, vec!["app/lib/util.py", "app/main.py"]);
+}
@@ -0,0 +1,101 @@
+// Unit tests for failure thresholds
+// Tests severities, dead code and per-category maximums, and parsing their flags
+
+use skylos_rs::analyzer::{AnalysisResult, Skylos};
+use skylos_rs::gate::{category_counts, parse_max_findings, parse_severity, Gate, CATEGORIES};
+use std::fs;
+use tempfile::tempdir;
+
+/// Analyzes a project with an unused function, an unused import and a `CRITICAL`
+/// danger finding.
+fn analyze() -> AnalysisResult {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join("app.py"),
+        "import os\nimport subprocess\n\ndef old():\n    pass\n\nsubprocess.call('ls', shell=True)\n",
+    )
+    .unwrap();
+    Skylos::new(0, false, true, false)
+        .analyze(dir.path())
+        .unwrap()
+}
+
+#[test]
+fn test_gate_passes_without_thresholds() {
+    let result = analyze();
+    assert!(Gate::default().check(&result).is_empty());
+}
+
+#[test]
+fn test_gate_fail_on_severity() {
+    let result = analyze();
+    let gate = Gate {
+        fail_on: Some("CRITICAL".to_string()),
+        ..Gate::default()
+    };
+    assert_eq!(
+        gate.check(&result),
+        vec!["1 finding(s) with severity CRITICAL or higher"]
+    );
+
+    // Unused definitions have no severity.
+    let mut result = analyze();
+    result.danger.clear();
+    result.dead_modules.clear();
+    let gate = Gate {
+        fail_on: Some("LOW".to_string()),
+        ..Gate::default()
+    };
+    assert!(gate.check(&result).is_empty());
+}
+
+#[test]
+fn test_gate_max_findings() {
+    let result = analyze();
+    // One unused function, one unused import and one dead module.
+    let gate = Gate {
+        max_dead_code: Some(3),
+        ..Gate::default()
+    };
+    assert!(gate.check(&result).is_empty());
+    let gate = Gate {
+        max_dead_code: Some(2),
+        ..Gate::default()
+    };
+    assert_eq!(
+        gate.check(&result),
+        vec!["3 dead code finding(s), more than the maximum of 2"]
+    );
+
+    let gate = Gate {
+        max_findings: [("unused_imports".to_string(), 0), ("danger".to_string(), 1)]
+            .into_iter()
+            .collect(),
+        ..Gate::default()
+    };
+    assert_eq!(
+        gate.check(&result),
+        vec!["1 unused_imports finding(s), more than the maximum of 0"]
+    );
+}
+
+#[test]
+fn test_parse_flags() {
+    assert_eq!(parse_severity("high"), Ok("HIGH".to_string()));
+    assert!(parse_severity("urgent").is_err());
+    assert_eq!(
+        parse_max_findings("unused_imports=0"),
+        Ok(("unused_imports".to_string(), 0))
+    );
+    assert!(parse_max_findings("unused_imports").is_err());
+    assert!(parse_max_findings("unused=0").is_err());
+    assert!(parse_max_findings("danger=-1").is_err());
+
+    // Every category can be given a maximum.
+    let result = analyze();
+    let names: Vec<&str> = category_counts(&result)
+        .iter()
+        .map(|(name, _)| *name)
+        .collect();
+    assert_eq!(names, CATEGORIES);
+}
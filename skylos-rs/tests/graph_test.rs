@@ -0,0 +1,148 @@
+use skylos_rs::graph::{Graph, GraphFormat, REFERENCE_EDGE};
+use std::path::{Path, PathBuf};
+
+/// Builds a module graph where module `a.b` lives in `a/b.py`.
+fn graph(modules: &[(&str, bool)], edges: &[(usize, usize, &str)]) -> Graph {
+    let mut graph = Graph::default();
+    for (module, dead) in modules {
+        let file = PathBuf::from(format!("{}.py", module.replace('.', "/")));
+        graph.add_node(module.to_string(), Some(file), *dead);
+    }
+    for (from, to, kind) in edges {
+        graph.add_edge(*from, *to, kind);
+    }
+    graph
+}
+
+fn edge_list(graph: &Graph) -> Vec<(&str, &str, &str)> {
+    graph
+        .edges
+        .iter()
+        .map(|e| {
+            (
+                graph.nodes[e.from].id.as_str(),
+                graph.nodes[e.to].id.as_str(),
+                e.kind.as_str(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_collapse() {
+    let graph = graph(
+        &[
+            ("app.models.user", true),
+            ("app.models.order", true),
+            ("app.views", false),
+            ("lib.util", true),
+        ],
+        &[
+            (0, 1, "module-level"),
+            (2, 0, "function-local"),
+            (2, 1, "module-level"),
+            (3, 0, "type-checking"),
+        ],
+    );
+
+    let collapsed = graph.collapse(2);
+    let nodes: Vec<(&str, bool)> = collapsed
+        .nodes
+        .iter()
+        .map(|n| (n.id.as_str(), n.dead))
+        .collect();
+    assert_eq!(
+        nodes,
+        vec![
+            ("app.models", true),
+            ("app.views", false),
+            ("lib.util", true)
+        ]
+    );
+    assert_eq!(collapsed.nodes[0].file, None);
+    assert_eq!(collapsed.nodes[1].file, Some(PathBuf::from("app/views.py")));
+    // The edge inside `app.models` is dropped, and the module-level import wins.
+    assert_eq!(
+        edge_list(&collapsed),
+        vec![
+            ("app.views", "app.models", "module-level"),
+            ("lib.util", "app.models", "type-checking"),
+        ]
+    );
+
+    let top = graph.collapse(1);
+    let nodes: Vec<(&str, bool)> = top.nodes.iter().map(|n| (n.id.as_str(), n.dead)).collect();
+    assert_eq!(nodes, vec![("app", false), ("lib", true)]);
+    assert_eq!(edge_list(&top), vec![("lib", "app", "type-checking")]);
+}
+
+#[test]
+fn test_filter() {
+    let graph = graph(
+        &[
+            ("app.models", false),
+            ("app.views", false),
+            ("lib.util", false),
+        ],
+        &[(1, 0, "module-level"), (2, 0, "module-level")],
+    );
+    let filtered = graph.filter(Path::new("app"));
+    let nodes: Vec<&str> = filtered.nodes.iter().map(|n| n.id.as_str()).collect();
+    assert_eq!(nodes, vec!["app.models", "app.views"]);
+    assert_eq!(
+        edge_list(&filtered),
+        vec![("app.views", "app.models", "module-level")]
+    );
+}
+
+#[test]
+fn test_render_dot() {
+    let graph = graph(
+        &[("app", false), ("app.old", true)],
+        &[(0, 1, "function-local")],
+    );
+    assert_eq!(
+        graph.render(GraphFormat::Dot, true),
+        "digraph skylos {\n    rankdir=LR;\n    node [shape=box];\n    \"app\";\n    \
+         \"app.old\" [style=filled, fillcolor=\"#ffcccc\", color=\"#cc0000\"];\n    \
+         \"app\" -> \"app.old\" [style=dashed];\n}\n"
+    );
+    assert!(!graph.render(GraphFormat::Dot, false).contains("fillcolor"));
+}
+
+#[test]
+fn test_render_mermaid() {
+    let graph = graph(
+        &[("app", false), ("app.old", true)],
+        &[(0, 1, REFERENCE_EDGE)],
+    );
+    assert_eq!(
+        graph.render(GraphFormat::Mermaid, true),
+        "graph LR\n    n0[\"app\"]\n    n1[\"app.old\"]\n    n0 --> n1\n    \
+         classDef dead fill:#ffcccc,stroke:#cc0000\n    class n1 dead\n"
+    );
+}
+
+#[test]
+fn test_render_json() {
+    let graph = graph(
+        &[("app", false), ("app.old", true)],
+        &[(0, 1, "type-checking")],
+    );
+    let json: serde_json::Value =
+        serde_json::from_str(&graph.render(GraphFormat::Json, false)).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "nodes": [
+                {
+                    "id": "app",
+                    "file": "app.py",
+                    "dead": false,
+                    "edges": [{"to": "app.old", "kind": "type-checking"}],
+                },
+                {"id": "app.old", "file": "app/old.py", "dead": true, "edges": []},
+            ]
+        })
+    );
+}
@@ -0,0 +1,61 @@
+use skylos_rs::hierarchy::ClassHierarchy;
+
+fn sample() -> ClassHierarchy {
+    let mut hierarchy = ClassHierarchy::new();
+    hierarchy.add_class("base.Plugin", vec!["abc.ABC".to_string()]);
+    hierarchy.add_member("base.Plugin", "process");
+    hierarchy.add_member("base.Plugin", "transform");
+    hierarchy.add_class("mid.Cached", vec!["base.Plugin".to_string()]);
+    hierarchy.add_member("mid.Cached", "transform");
+    hierarchy.add_class("impl.Fast", vec!["mid.Cached".to_string()]);
+    hierarchy.add_member("impl.Fast", "transform");
+    hierarchy
+}
+
+#[test]
+fn test_ancestors() {
+    let hierarchy = sample();
+    assert_eq!(
+        hierarchy.ancestors("impl.Fast"),
+        vec!["mid.Cached", "base.Plugin", "abc.ABC"]
+    );
+    assert!(hierarchy.ancestors("base.Unknown").is_empty());
+}
+
+#[test]
+fn test_external_ancestors() {
+    let hierarchy = sample();
+    assert_eq!(hierarchy.external_ancestors("impl.Fast"), vec!["abc.ABC"]);
+    assert!(hierarchy.external_ancestors("base.Unknown").is_empty());
+}
+
+#[test]
+fn test_overridden() {
+    let hierarchy = sample();
+    assert_eq!(
+        hierarchy.overridden("impl.Fast", "transform"),
+        vec!["mid.Cached.transform", "base.Plugin.transform"]
+    );
+    assert!(hierarchy.overridden("impl.Fast", "other").is_empty());
+}
+
+#[test]
+fn test_resolve_inherited() {
+    let hierarchy = sample();
+    assert_eq!(
+        hierarchy.resolve_inherited("impl.Fast.process"),
+        Some("base.Plugin.process".to_string())
+    );
+    // Defined on the class itself, or not a known class.
+    assert_eq!(hierarchy.resolve_inherited("impl.Fast.transform"), None);
+    assert_eq!(hierarchy.resolve_inherited("impl.process"), None);
+}
+
+#[test]
+fn test_inheritance_cycle() {
+    let mut hierarchy = ClassHierarchy::new();
+    hierarchy.add_class("m.A", vec!["m.B".to_string()]);
+    hierarchy.add_class("m.B", vec!["m.A".to_string()]);
+    assert_eq!(hierarchy.ancestors("m.A"), vec!["m.B"]);
+    assert_eq!(hierarchy.resolve_inherited("m.A.missing"), None);
+}
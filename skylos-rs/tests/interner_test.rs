@@ -0,0 +1,26 @@
+use skylos_rs::interner::Interner;
+
+#[test]
+fn test_intern_returns_same_symbol() {
+    let mut interner = Interner::new();
+    assert!(interner.is_empty());
+    let a = interner.intern("pkg.mod.helper");
+    let b = interner.intern("helper");
+    assert_ne!(a, b);
+    assert_eq!(interner.intern("pkg.mod.helper"), a);
+    assert_eq!(interner.len(), 2);
+    assert_eq!(interner.resolve(a), "pkg.mod.helper");
+    assert_eq!(interner.resolve(b), "helper");
+}
+
+#[test]
+fn test_get_and_iter() {
+    let mut interner = Interner::new();
+    let a = interner.intern("a");
+    let b = interner.intern("b");
+    assert_eq!(interner.get("a"), Some(a));
+    assert_eq!(interner.get("missing"), None);
+    let all: Vec<_> = interner.iter().collect();
+    assert_eq!(all, vec![(a, "a"), (b, "b")]);
+    assert_eq!((a.index(), b.index()), (0, 1));
+}
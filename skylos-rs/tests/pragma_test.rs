@@ -1,8 +1,14 @@
 use skylos_rs::analyzer::Skylos;
-use std::fs::File;
+use skylos_rs::suppression::{remove_unused_suppressions, PragmaKind, Rules, Suppressions};
+use skylos_rs::utils::LineIndex;
+use std::fs::{self, File};
 use std::io::Write;
 use tempfile::tempdir;
 
+fn parse(source: &str) -> Suppressions {
+    Suppressions::parse(source, &LineIndex::new(source))
+}
+
 #[test]
 fn test_analyze_respects_ignore_pragmas() {
     let dir = tempdir().unwrap();
@@ -36,3 +42,256 @@ used()
     assert!(!unreachable.contains(&"unused_ignore".to_string()));
     assert!(!unreachable.contains(&"used".to_string()));
 }
+
+#[test]
+fn test_parse_pragmas() {
+    let source = r##"# skylos: ignore-file[SKY-Q001]
+import os  # skylos: ignore
+x = 1  # noqa: E501  # skylos: ignore[SKY-D002, sky-s101] reviewed
+# skylos: ignore-next-line
+# skylos: disable=SKY-D001,SKY-D002 legacy code
+# skylos: enable
+s = "# skylos: ignore"
+t = """
+# skylos: ignore-file
+"""
+# skylos: unknown
+"##;
+    let suppressions = parse(source);
+    let pragmas: Vec<(PragmaKind, &Rules, usize)> = suppressions
+        .pragmas
+        .iter()
+        .map(|p| (p.kind, &p.rules, p.line))
+        .collect();
+    let only = |rules: &[&str]| Rules::Only(rules.iter().map(|r| r.to_string()).collect());
+    assert_eq!(
+        pragmas,
+        vec![
+            (PragmaKind::File, &only(&["SKY-Q001"]), 1),
+            (PragmaKind::Line, &Rules::All, 2),
+            (PragmaKind::Line, &only(&["SKY-D002", "SKY-S101"]), 3),
+            (PragmaKind::NextLine, &Rules::All, 4),
+            (PragmaKind::Disable, &only(&["SKY-D001", "SKY-D002"]), 5),
+            (PragmaKind::Enable, &Rules::All, 6),
+        ]
+    );
+}
+
+#[test]
+fn test_suppressed_lines_and_rules() {
+    let source = r#"import os  # skylos: ignore[SKY-D002]
+# skylos: ignore-next-line[SKY-D001]
+eval(x)
+# skylos: disable=SKY-D002,SKY-Q001
+a()
+# skylos: enable=SKY-D002
+b()
+# skylos: disable
+c()
+"#;
+    let suppressions = parse(source);
+    // Same line, listed rules only; unused definitions need a bare pragma.
+    assert!(suppressions.is_suppressed(1, Some("SKY-D002")));
+    assert!(!suppressions.is_suppressed(1, Some("SKY-D001")));
+    assert!(!suppressions.is_suppressed(1, None));
+    // Next line only.
+    assert!(suppressions.is_suppressed(3, Some("SKY-D001")));
+    assert!(!suppressions.is_suppressed(2, Some("SKY-D001")));
+    // `enable=SKY-D002` ends its region, `SKY-Q001` stays disabled.
+    assert!(suppressions.is_suppressed(5, Some("SKY-D002")));
+    assert!(!suppressions.is_suppressed(7, Some("SKY-D002")));
+    assert!(suppressions.is_suppressed(7, Some("SKY-Q001")));
+    // A bare `disable` runs to the end of the file, for every rule.
+    assert!(suppressions.is_suppressed(9, Some("SKY-D002")));
+    assert!(suppressions.is_suppressed(9, None));
+
+    let file = parse("# skylos: ignore-file\ndef f():\n    pass\n");
+    assert!(file.is_suppressed(2, None));
+    assert!(file.is_suppressed(3, Some("SKY-U001")));
+    assert!(parse("x = '# skylos: ignore'\n").is_empty());
+}
+
+#[test]
+fn test_suppressions_apply_to_every_category() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join("app.py"),
+        r#"import subprocess
+import json  # skylos: ignore
+__all__ = ["missing"]  # skylos: ignore[SKY-E001]
+
+
+def kept():  # skylos: ignore
+    helper()
+
+
+def helper():
+    pass
+
+
+def run(cmd):
+    subprocess.call(cmd, shell=True)  # skylos: ignore[SKY-D002] trusted input
+    subprocess.call(cmd, shell=True)  # skylos: ignore[SKY-D001]
+    return cmd
+    print("never")  # skylos: ignore[SKY-U001]
+
+
+run("ls")
+"#,
+    )
+    .unwrap();
+    fs::write(
+        dir.path().join("legacy.py"),
+        "# skylos: ignore-file[SKY-M001, SKY-D002]\nimport subprocess\nsubprocess.call('ls', shell=True)\n",
+    )
+    .unwrap();
+
+    let skylos = Skylos::new(60, false, true, false);
+    let result = skylos.analyze(dir.path()).unwrap();
+
+    let unused: Vec<&str> = result
+        .unused_functions
+        .iter()
+        .chain(&result.unused_imports)
+        .map(|d| d.full_name.as_str())
+        .collect();
+    // The suppressed function is used, so it keeps its callee alive.
+    assert!(unused.is_empty(), "{:?}", unused);
+    assert!(result.undefined_exports.is_empty());
+    assert!(result.unreachable_code.is_empty());
+    assert!(result
+        .dead_modules
+        .iter()
+        .all(|m| m.module_name != "legacy"));
+    let danger: Vec<(&str, usize)> = result
+        .danger
+        .iter()
+        .map(|d| (d.rule_id.as_str(), d.line))
+        .collect();
+    assert_eq!(danger, vec![("SKY-D002", 16)]);
+    assert_eq!(result.analysis_summary.danger_count, 1);
+}
+
+#[test]
+fn test_unused_suppressions() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join("app.py"),
+        r#"import subprocess
+import os  # skylos: ignore
+import sys  # skylos: ignore[SKY-D002]
+
+
+def used():  # pragma: no skylos
+    pass
+
+
+def entry():  # pragma: no skylos
+    helper()
+
+
+def helper():
+    subprocess.call("ls", shell=True)  # skylos: ignore[SKY-D002]
+    # skylos: ignore-next-line[SKY-Q001]
+    return 1
+
+
+used()
+"#,
+    )
+    .unwrap();
+
+    let mut skylos = Skylos::new(60, true, true, true);
+    skylos.report_unused_suppressions = true;
+    let result = skylos.analyze(dir.path()).unwrap();
+    let unused: Vec<(usize, &str)> = result
+        .unused_suppressions
+        .iter()
+        .map(|u| (u.line, u.pragma.as_str()))
+        .collect();
+    // `entry` is unused, and `helper` is only called by it, so both of their pragmas
+    // are needed, as is the one on the shell call.
+    assert_eq!(
+        unused,
+        vec![
+            (3, "# skylos: ignore[SKY-D002]"),
+            (6, "# pragma: no skylos"),
+            (16, "# skylos: ignore-next-line[SKY-Q001]"),
+        ]
+    );
+    assert!(result
+        .unused_suppressions
+        .iter()
+        .all(|u| u.rule_id == "SKY-I001"));
+
+    // Pragmas that may apply to a rule which did not run are left out: the quality
+    // pragma, and the pragma without a rule list.
+    let mut skylos = Skylos::new(60, false, true, false);
+    skylos.report_unused_suppressions = true;
+    let result = skylos.analyze(dir.path()).unwrap();
+    let unused: Vec<usize> = result.unused_suppressions.iter().map(|u| u.line).collect();
+    assert_eq!(unused, vec![3]);
+
+    // Without the danger rules, even the unused `SKY-D002` pragma is left out.
+    let mut skylos = Skylos::new(60, false, false, false);
+    skylos.report_unused_suppressions = true;
+    let result = skylos.analyze(dir.path()).unwrap();
+    assert!(result.unused_suppressions.is_empty());
+}
+
+#[test]
+fn test_fix_keeps_suppressions_of_rules_that_did_not_run() {
+    let dir = tempdir().unwrap();
+    let file = dir.path().join("app.py");
+    let source = r#"eval("1")  # skylos: ignore[SKY-D001]
+eval("2")  # skylos: ignore
+"#;
+    fs::write(&file, source).unwrap();
+
+    // Without `enable_danger`, the pragmas suppress nothing but are still needed.
+    let mut skylos = Skylos::new(60, false, false, false);
+    skylos.report_unused_suppressions = true;
+    let result = skylos.analyze(dir.path()).unwrap();
+    assert!(result.unused_suppressions.is_empty());
+    let removed = remove_unused_suppressions(&result.unused_suppressions).unwrap();
+    assert_eq!(removed, 0);
+    assert_eq!(fs::read_to_string(&file).unwrap(), source);
+}
+
+#[test]
+fn test_remove_unused_suppressions() {
+    let dir = tempdir().unwrap();
+    let file = dir.path().join("app.py");
+    fs::write(
+        &file,
+        r##"import os  # skylos: ignore
+import sys  # noqa: F401  # skylos: ignore reason
+
+
+def f():
+    # skylos: ignore-next-line
+    x = "# skylos: ignore"  # skylos: ignore  # type: str
+    return x
+"##,
+    )
+    .unwrap();
+
+    let mut skylos = Skylos::new(60, true, true, true);
+    skylos.report_unused_suppressions = true;
+    let result = skylos.analyze(dir.path()).unwrap();
+    // The imports are unused, so only the pragmas inside `f` are reported.
+    assert_eq!(result.unused_suppressions.len(), 2);
+    let removed = remove_unused_suppressions(&result.unused_suppressions).unwrap();
+    assert_eq!(removed, 2);
+    assert_eq!(
+        fs::read_to_string(&file).unwrap(),
+        r##"import os  # skylos: ignore
+import sys  # noqa: F401  # skylos: ignore reason
+
+
+def f():
+    x = "# skylos: ignore"  # type: str
+    return x
+"##
+    );
+}
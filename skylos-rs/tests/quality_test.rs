@@ -30,7 +30,7 @@ def deeply_nested():
     }
 
     assert!(
-        visitor.findings.len() > 0,
+        !visitor.findings.is_empty(),
         "Should detect deeply nested code"
     );
     assert!(visitor.findings.iter().any(|f| f.rule_id == "SKY-Q001"));
@@ -0,0 +1,217 @@
+// Unit tests for unreachable code detection
+// Tests the control-flow pass over function bodies
+
+use rustpython_parser::{parse, Mode};
+use skylos_rs::unreachable::UnreachableVisitor;
+use skylos_rs::utils::LineIndex;
+use std::path::PathBuf;
+
+/// Returns `(rule_id, line, end_line)` for every unreachable block in the source.
+fn unreachable_blocks(source: &str) -> Vec<(String, usize, usize)> {
+    let tree = parse(source, Mode::Module, "test.py").expect("Failed to parse");
+    let line_index = LineIndex::new(source);
+    let mut visitor = UnreachableVisitor::new(PathBuf::from("test.py"), &line_index);
+
+    if let rustpython_ast::Mod::Module(module) = tree {
+        for stmt in &module.body {
+            visitor.visit_stmt(stmt);
+        }
+    }
+
+    visitor
+        .findings
+        .into_iter()
+        .map(|f| (f.rule_id, f.line, f.end_line))
+        .collect()
+}
+
+#[test]
+fn test_code_after_terminators() {
+    let source = r#"
+import sys
+
+def after_return():
+    return 1
+    print("dead")
+    x = 2
+
+def after_raise():
+    raise ValueError()
+    print("dead")
+
+def after_exit():
+    sys.exit(1)
+    print("dead")
+
+def in_loop(items):
+    for item in items:
+        if item:
+            break
+        else:
+            continue
+        print("dead")
+    return items
+"#;
+
+    assert_eq!(
+        unreachable_blocks(source),
+        vec![
+            ("SKY-U001".to_string(), 6, 7),
+            ("SKY-U001".to_string(), 11, 11),
+            ("SKY-U001".to_string(), 15, 15),
+            ("SKY-U001".to_string(), 23, 23),
+        ]
+    );
+}
+
+#[test]
+fn test_constant_conditions() {
+    let source = r#"
+def f():
+    if False:
+        print("dead")
+    if 0:
+        print("dead")
+    while False:
+        print("dead")
+    if True:
+        pass
+    else:
+        print("dead")
+        print("dead")
+"#;
+
+    assert_eq!(
+        unreachable_blocks(source),
+        vec![
+            ("SKY-U002".to_string(), 4, 4),
+            ("SKY-U002".to_string(), 6, 6),
+            ("SKY-U002".to_string(), 8, 8),
+            ("SKY-U002".to_string(), 12, 13),
+        ]
+    );
+}
+
+#[test]
+fn test_infinite_loops() {
+    let source = r#"
+def serve():
+    while True:
+        handle()
+    cleanup()
+
+def poll():
+    while True:
+        if ready():
+            break
+        for item in items():
+            break
+    return done()
+"#;
+
+    assert_eq!(
+        unreachable_blocks(source),
+        vec![("SKY-U001".to_string(), 5, 5)]
+    );
+}
+
+#[test]
+fn test_shadowed_except_clauses() {
+    let source = r#"
+def f():
+    try:
+        work()
+    except Exception:
+        pass
+    except ValueError:
+        print("dead")
+    except KeyboardInterrupt:
+        pass
+
+    try:
+        work()
+    except (OSError, KeyError):
+        pass
+    except (FileNotFoundError, KeyError):
+        print("dead")
+    except MyError:
+        pass
+
+    try:
+        work()
+    except:
+        pass
+    except MyError:
+        print("dead")
+"#;
+
+    assert_eq!(
+        unreachable_blocks(source),
+        vec![
+            ("SKY-U003".to_string(), 8, 8),
+            ("SKY-U003".to_string(), 17, 17),
+            ("SKY-U003".to_string(), 26, 26),
+        ]
+    );
+}
+
+#[test]
+fn test_branches_that_all_exit() {
+    let source = r#"
+def branches(x):
+    if x:
+        return 1
+    else:
+        raise ValueError()
+    print("dead")
+
+def handled(x):
+    try:
+        return parse(x)
+    except ValueError:
+        return None
+    print("dead")
+
+def partial(x):
+    if x:
+        return 1
+    try:
+        return parse(x)
+    except ValueError:
+        pass
+    with open(x):
+        return 2
+    print("alive")
+"#;
+
+    assert_eq!(
+        unreachable_blocks(source),
+        vec![
+            ("SKY-U001".to_string(), 7, 7),
+            ("SKY-U001".to_string(), 14, 14),
+        ]
+    );
+}
+
+#[test]
+fn test_module_level_code_is_not_checked() {
+    let source = r#"
+import sys
+
+if False:
+    print("module-level")
+
+class Config:
+    def method(self):
+        return 1
+        print("dead")
+
+sys.exit(0)
+print("after exit")
+"#;
+
+    assert_eq!(
+        unreachable_blocks(source),
+        vec![("SKY-U001".to_string(), 10, 10)]
+    );
+}
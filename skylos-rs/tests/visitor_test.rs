@@ -1,6 +1,6 @@
 use rustpython_parser::{parse, Mode};
 use skylos_rs::utils::LineIndex;
-use skylos_rs::visitor::SkylosVisitor;
+use skylos_rs::visitor::{ImportContext, SkylosVisitor};
 use std::collections::HashSet;
 use std::path::PathBuf;
 
@@ -132,6 +132,66 @@ from os.path import join as path_join
     assert!(import_names.contains("path_join"));
 }
 
+#[test]
+fn test_import_bindings() {
+    let code = r#"
+import os.path
+import numpy as np
+from pkg.mod import helper as h
+from . import sibling
+from ..base import Base
+"#;
+    let tree = parse(code, Mode::Module, "test.py").expect("Failed to parse");
+    let line_index = LineIndex::new(code);
+    let mut visitor = SkylosVisitor::new(
+        PathBuf::from("pkg/sub/test.py"),
+        "pkg.sub.test".to_string(),
+        &line_index,
+    );
+    if let rustpython_ast::Mod::Module(module) = tree {
+        for stmt in &module.body {
+            visitor.visit_stmt(stmt);
+        }
+    }
+
+    let bindings: Vec<(String, String)> = visitor
+        .imports
+        .iter()
+        .map(|i| (i.local_name.clone(), i.target.clone()))
+        .collect();
+    assert_eq!(
+        bindings,
+        vec![
+            ("os".to_string(), "os".to_string()),
+            ("np".to_string(), "numpy".to_string()),
+            ("h".to_string(), "pkg.mod.helper".to_string()),
+            ("sibling".to_string(), "pkg.sub.sibling".to_string()),
+            ("Base".to_string(), "pkg.base.Base".to_string()),
+        ]
+    );
+    // The full dotted names are kept, e.g. to tell which modules are imported.
+    assert_eq!(
+        visitor.imported_modules,
+        vec![
+            "os.path",
+            "numpy",
+            "pkg.mod.helper",
+            "pkg.sub.sibling",
+            "pkg.base.Base"
+        ]
+    );
+
+    // Import definitions are qualified with the importing module.
+    let import_names: HashSet<String> = visitor
+        .definitions
+        .iter()
+        .filter(|d| d.def_type == "import")
+        .map(|d| d.full_name.clone())
+        .collect();
+    assert!(import_names.contains("pkg.sub.test.np"));
+    assert!(import_names.contains("pkg.sub.test.h"));
+}
+
 #[test]
 fn test_nested_functions() {
     let code = r#"
@@ -180,15 +240,140 @@ class MyClass:
     
     def method(self):
         local_var = "function level"
+        unused_local = 1
         return local_var
 "#;
     visit_code!(code, visitor);
 
-    let _vars: Vec<_> = visitor
+    let vars: Vec<_> = visitor
         .definitions
         .iter()
         .filter(|d| d.def_type == "variable")
         .collect();
+
+    let module_var = vars.iter().find(|v| v.simple_name == "MODULE_VAR").unwrap();
+    assert_eq!(module_var.full_name, "test.MODULE_VAR");
+    assert!(!module_var.is_local);
+
+    let local_var = vars.iter().find(|v| v.simple_name == "local_var").unwrap();
+    assert_eq!(local_var.full_name, "test.MyClass.method.local_var");
+    assert!(local_var.is_local);
+    assert_eq!(local_var.references, 1);
+
+    let unused = vars
+        .iter()
+        .find(|v| v.simple_name == "unused_local")
+        .unwrap();
+    assert_eq!(unused.references, 0);
+    assert_eq!(unused.line, 9);
+
+    // Class-level names are attributes, not variables.
+    assert!(!vars.iter().any(|v| v.simple_name == "CLASS_VAR"));
+}
+
+/// Returns the names of function-local variables that are never read.
+fn unused_locals(visitor: &SkylosVisitor) -> HashSet<String> {
+    visitor
+        .definitions
+        .iter()
+        .filter(|d| d.def_type == "variable" && d.is_local && d.references == 0)
+        .map(|d| d.simple_name.clone())
+        .collect()
+}
+
+#[test]
+fn test_unused_variable_targets() {
+    let code = r#"
+def f(items, path):
+    first, *rest = items
+    count: int = 0
+    total = 0
+    total += 1
+    for index, item in enumerate(items):
+        print(item)
+    with open(path) as handle, open(path) as other:
+        handle.read()
+    if (n := len(items)) > 10:
+        pass
+    _, ignored_by_convention = 1, 2
+    _private = 3
+    return first
+"#;
+    visit_code!(code, visitor);
+
+    let unused = unused_locals(&visitor);
+    let expected: HashSet<String> = [
+        "rest",
+        "count",
+        "total",
+        "index",
+        "other",
+        "n",
+        "ignored_by_convention",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+    assert_eq!(unused, expected);
+}
+
+#[test]
+fn test_variable_scopes() {
+    let code = r#"
+counter = 0
+
+def bump():
+    global counter
+    counter += 1
+
+def outer():
+    captured = 1
+    shadowed = 2
+    state = 0
+    def inner():
+        nonlocal state
+        state = 1
+        return captured
+    return inner, [shadowed for shadowed in range(3)], [y for x in range(3) if (y := x)]
+
+def uses_locals():
+    hidden = 1
+    return locals()
+
+result = [item for item in range(3)]
+"#;
+    visit_code!(code, visitor);
+
+    let unused = unused_locals(&visitor);
+    // Read by a closure.
+    assert!(!unused.contains("captured"));
+    // The comprehension variable shadows the local, which is never read.
+    assert!(unused.contains("shadowed"));
+    // Assigned through `nonlocal`, but never read.
+    assert!(unused.contains("state"));
+    // Walrus inside a comprehension binds in the function and is read there.
+    assert!(!unused.contains("y"));
+    // `locals()` may read anything.
+    assert!(!unused.contains("hidden"));
+    // Comprehension variable that is read.
+    assert!(!unused.contains("item"));
+
+    // `global counter` binds the module-level variable; no local is created.
+    let counters: Vec<_> = visitor
+        .definitions
+        .iter()
+        .filter(|d| d.simple_name == "counter")
+        .collect();
+    assert_eq!(counters.len(), 1);
+    assert!(!counters[0].is_local);
+
+    let module_vars: HashSet<String> = visitor
+        .definitions
+        .iter()
+        .filter(|d| d.def_type == "variable" && !d.is_local)
+        .map(|d| d.full_name.clone())
+        .collect();
+    assert!(module_vars.contains("test.result"));
 }
 
 #[test]
@@ -199,10 +384,74 @@ value = getattr(obj, 'attribute_name')
 "#;
     visit_code!(code, visitor);
 
-    let ref_names: HashSet<String> = visitor.references.iter().map(|(n, _)| n.clone()).collect();
+    let ref_names: HashSet<String> = visitor.reference_names().map(str::to_string).collect();
     assert!(ref_names.contains("attribute_name"));
 }
 
+#[test]
+fn test_dynamic_access() {
+    let code = r#"
+import importlib
+import handlers
+
+plugin = importlib.import_module("plugins.csv")
+legacy = __import__("legacy")
+sibling = importlib.import_module(".sibling", package="app.core")
+loaded = importlib.import_module(f"extensions.{name}")
+
+class Dispatcher:
+    def dispatch(self, kind, payload):
+        if hasattr(self, "prepare"):
+            self.prepare()
+        handler = getattr(self, f"handle_{kind}")
+        return handler(payload)
+
+getattr(handlers, "on_start")
+getattr(handlers, "cmd_" + name)
+globals()["setup"]()
+globals().get("teardown")
+"#;
+    visit_code!(code, visitor);
+
+    assert_eq!(
+        visitor.dynamic_imports,
+        vec!["plugins.csv", "legacy", "app.core.sibling"]
+    );
+    let ref_names: HashSet<String> = visitor.reference_names().map(str::to_string).collect();
+    assert!(ref_names.contains("test.Dispatcher.prepare"));
+    assert!(ref_names.contains("handlers.on_start"));
+    assert!(ref_names.contains("test.setup"));
+    assert!(ref_names.contains("test.teardown"));
+    assert_eq!(
+        visitor.dynamic_prefixes,
+        vec!["extensions.", "test.Dispatcher.handle_", "cmd_"]
+    );
+    // Every access above is bounded by a literal or a prefix.
+    assert!(!visitor.has_dynamic_access);
+}
+
+#[test]
+fn test_unbounded_dynamic_access() {
+    for code in [
+        "value = getattr(obj, name)\n",
+        "value = globals()[name]\n",
+        "eval(source)\n",
+    ] {
+        visit_code!(code, visitor);
+        assert!(visitor.has_dynamic_access, "{}", code);
+    }
+
+    // Unbounded access on `self` only affects the members of the class.
+    let code = r#"
+class Registry:
+    def lookup(self, name):
+        return getattr(self, name)
+"#;
+    visit_code!(code, visitor);
+    assert!(!visitor.has_dynamic_access);
+    assert_eq!(visitor.dynamic_prefixes, vec!["test.Registry."]);
+}
+
 #[test]
 fn test_all_detection() {
     let code = r#"
@@ -214,6 +463,53 @@ __all__ = ['function1', 'Class1']
     assert!(visitor.exports.contains(&"Class1".to_string()));
 }
 
+#[test]
+fn test_all_forms() {
+    let code = r#"
+__all__ = ('a', 'b')
+__all__ += ['c']
+__all__.extend(['d'] + ['e'])
+__all__.append('f')
+__all__.append(name)
+
+class Config:
+    __all__ = ['not_exported']
+
+def setup():
+    __all__ = ['local']
+"#;
+    visit_code!(code, visitor);
+
+    assert_eq!(visitor.exports, vec!["a", "b", "c", "d", "e", "f"]);
+    assert_eq!(visitor.export_lines, vec![2, 2, 3, 4, 4, 5]);
+}
+
+#[test]
+fn test_star_import_binding() {
+    let code = r#"
+from .helpers import *
+"#;
+    let tree = parse(code, Mode::Module, "pkg/mod.py").expect("Failed to parse");
+    let line_index = LineIndex::new(code);
+    let mut visitor = SkylosVisitor::new(
+        PathBuf::from("pkg/mod.py"),
+        "pkg.mod".to_string(),
+        &line_index,
+    );
+    if let rustpython_ast::Mod::Module(module) = tree {
+        for stmt in &module.body {
+            visitor.visit_stmt(stmt);
+        }
+    }
+
+    assert_eq!(visitor.star_imports.len(), 1);
+    assert_eq!(visitor.star_imports[0].target, "pkg.helpers");
+    assert!(visitor
+        .definitions
+        .iter()
+        .any(|d| d.full_name == "pkg.mod.*"));
+}
+
 #[test]
 fn test_decorators() {
     let code = r#"
@@ -223,8 +519,149 @@ def decorated():
 "#;
     visit_code!(code, visitor);
 
-    let _ref_names: HashSet<String> = visitor.references.iter().map(|(n, _)| n.clone()).collect();
-    // assert!(_ref_names.contains("my_decorator")); // Uncomment when fixed
+    let ref_names: HashSet<String> = visitor.reference_names().map(str::to_string).collect();
+    assert!(ref_names.contains("my_decorator"));
+}
+
+/// Visits `code` and returns the set of referenced names.
+fn referenced_names(code: &str) -> HashSet<String> {
+    visit_code!(code, visitor);
+    visitor.reference_names().map(str::to_string).collect()
+}
+
+#[test]
+fn test_statement_coverage() {
+    // Each snippet must reference `used`.
+    let cases = [
+        ("FunctionDef decorator", "@used\ndef f(): pass\n"),
+        ("FunctionDef default", "def f(a=used): pass\n"),
+        ("FunctionDef kw-only default", "def f(*, a=used): pass\n"),
+        ("FunctionDef annotation", "def f(a: used): pass\n"),
+        ("FunctionDef *args annotation", "def f(*a: used): pass\n"),
+        (
+            "FunctionDef **kwargs annotation",
+            "def f(**a: used): pass\n",
+        ),
+        ("FunctionDef return annotation", "def f() -> used: pass\n"),
+        ("FunctionDef body", "def f():\n    used()\n"),
+        ("AsyncFunctionDef decorator", "@used\nasync def f(): pass\n"),
+        (
+            "AsyncFunctionDef annotation",
+            "async def f(a: used) -> None: pass\n",
+        ),
+        ("ClassDef decorator", "@used\nclass C: pass\n"),
+        ("ClassDef base", "class C(used): pass\n"),
+        ("ClassDef metaclass", "class C(metaclass=used): pass\n"),
+        ("ClassDef body", "class C:\n    x = used\n"),
+        ("Return", "def f():\n    return used\n"),
+        ("Delete attribute", "del used.attr\n"),
+        ("Delete subscript", "del used[0]\n"),
+        ("Assign", "x = used\n"),
+        ("Assign target base", "used.attr = 1\n"),
+        ("TypeAlias", "type Alias = list[used]\n"),
+        ("AugAssign", "x += used\n"),
+        ("AnnAssign annotation", "x: used\n"),
+        ("AnnAssign value", "x: int = used\n"),
+        ("AnnAssign attribute", "used.attr: int\n"),
+        ("For", "for x in used: pass\n"),
+        (
+            "AsyncFor",
+            "async def f():\n    async for x in used: pass\n",
+        ),
+        ("While", "while used: pass\n"),
+        ("If", "if used: pass\n"),
+        ("With", "with used() as f: pass\n"),
+        ("AsyncWith", "async def f():\n    async with used: pass\n"),
+        ("Match subject", "match used:\n    case _: pass\n"),
+        ("Match value", "match x:\n    case used.VALUE: pass\n"),
+        ("Match class", "match x:\n    case used(a=1): pass\n"),
+        ("Match guard", "match x:\n    case [a] if used: pass\n"),
+        (
+            "Match mapping key",
+            "match x:\n    case {used.KEY: 1}: pass\n",
+        ),
+        ("Match or", "match x:\n    case 1 | used.OTHER: pass\n"),
+        ("Match body", "match x:\n    case _: used()\n"),
+        ("Raise", "raise used\n"),
+        ("Raise cause", "raise ValueError() from used\n"),
+        ("Try", "try:\n    pass\nexcept used:\n    pass\n"),
+        ("TryStar", "try:\n    pass\nexcept* used:\n    pass\n"),
+        ("Assert", "assert used\n"),
+        ("Assert message", "assert x, used\n"),
+        ("Expr", "used\n"),
+    ];
+    for (variant, code) in cases {
+        assert!(referenced_names(code).contains("used"), "{}", variant);
+    }
+}
+
+#[test]
+fn test_expression_coverage() {
+    // Each expression must reference `used`.
+    let cases = [
+        ("BoolOp", "x and used"),
+        ("NamedExpr", "(y := used)"),
+        ("BinOp", "1 + used"),
+        ("UnaryOp", "not used"),
+        ("Lambda", "lambda: used"),
+        ("Lambda default", "lambda a=used: a"),
+        ("IfExp", "1 if used else 2"),
+        ("Dict", "{1: used}"),
+        ("Dict unpacking", "{**used}"),
+        ("Set", "{used}"),
+        ("ListComp", "[x for x in used]"),
+        ("SetComp", "{x for x in y if used}"),
+        ("DictComp", "{x: used for x in y}"),
+        ("GeneratorExp", "(used for x in y)"),
+        ("Await", "await used"),
+        ("Yield", "(yield used)"),
+        ("YieldFrom", "(yield from used)"),
+        ("Compare", "x < used"),
+        ("Call", "used()"),
+        ("Call keyword", "f(a=used)"),
+        ("FormattedValue", "f'{used}'"),
+        ("FormattedValue spec", "f'{x:{used}}'"),
+        ("JoinedStr", "f'a{used}b'"),
+        ("Attribute", "used.attr"),
+        ("Subscript", "x[used]"),
+        ("Starred", "f(*used)"),
+        ("Name", "used"),
+        ("List", "[used]"),
+        ("Tuple", "(used,)"),
+        ("Slice", "x[1:used]"),
+    ];
+    for (variant, expr) in cases {
+        let code = format!("async def f():\n    return {}\n", expr);
+        assert!(referenced_names(&code).contains("used"), "{}", variant);
+    }
+}
+
+#[test]
+fn test_match_and_delete_bindings() {
+    let code = r#"
+def f(command):
+    match command:
+        case {"action": action, **rest}:
+            return action
+        case [first, *others]:
+            return first
+        case Point(x=px) as point:
+            return px
+
+def g():
+    value = 1
+    del value
+"#;
+    visit_code!(code, visitor);
+
+    let unused = unused_locals(&visitor);
+    assert!(!unused.contains("action"));
+    assert!(unused.contains("rest"));
+    assert!(unused.contains("others"));
+    assert!(unused.contains("point"));
+    assert!(!unused.contains("px"));
+    // Deleting a variable is not a read.
+    assert!(unused.contains("value"));
 }
 
 #[test]
@@ -250,7 +687,7 @@ class Child(Parent):
     assert!(child.base_classes.contains(&"Parent".to_string()));
 
     // Verify reference to Parent
-    let ref_names: HashSet<String> = visitor.references.iter().map(|(n, _)| n.clone()).collect();
+    let ref_names: HashSet<String> = visitor.reference_names().map(str::to_string).collect();
     assert!(ref_names.contains("Parent"));
     assert!(ref_names.contains("test.Parent"));
 }
@@ -262,7 +699,7 @@ squares = [x**2 for x in range(10)]
 "#;
     visit_code!(code, visitor);
 
-    let ref_names: HashSet<String> = visitor.references.iter().map(|(n, _)| n.clone()).collect();
+    let ref_names: HashSet<String> = visitor.reference_names().map(str::to_string).collect();
     assert!(ref_names.contains("range"));
 }
 
@@ -273,7 +710,7 @@ double = lambda x: x * 2
 "#;
     visit_code!(code, visitor);
 
-    let _ref_names: HashSet<String> = visitor.references.iter().map(|(n, _)| n.clone()).collect();
+    let _ref_names: HashSet<String> = visitor.reference_names().map(str::to_string).collect();
 }
 
 #[test]
@@ -283,12 +720,25 @@ result = text.upper().replace(" ", "_")
 "#;
     visit_code!(code, visitor);
 
-    let ref_names: HashSet<String> = visitor.references.iter().map(|(n, _)| n.clone()).collect();
+    let ref_names: HashSet<String> = visitor.reference_names().map(str::to_string).collect();
 
     assert!(ref_names.contains("upper"));
     assert!(ref_names.contains("replace"));
 }
 
+#[test]
+fn test_dotted_attribute_chains() {
+    let code = r#"
+pkg.mod.helper()
+"#;
+    visit_code!(code, visitor);
+
+    let ref_names: HashSet<String> = visitor.reference_names().map(str::to_string).collect();
+    assert!(ref_names.contains("pkg.mod"));
+    assert!(ref_names.contains("pkg.mod.helper"));
+    assert!(ref_names.contains("helper"));
+}
+
 #[test]
 fn test_star_imports() {
     let code = r#"
@@ -304,3 +754,502 @@ from os import *
     let import_names: HashSet<String> = imports.iter().map(|i| i.simple_name.clone()).collect();
     assert!(import_names.contains("*"));
 }
+
+/// Returns the confidence of each unused parameter, keyed by qualified name.
+fn unused_params(visitor: &SkylosVisitor) -> std::collections::HashMap<String, u8> {
+    visitor
+        .definitions
+        .iter()
+        .filter(|d| d.def_type == "parameter" && d.references == 0)
+        .map(|d| (d.full_name.clone(), d.confidence))
+        .collect()
+}
+
+#[test]
+fn test_unused_parameters() {
+    let code = r#"
+def f(a, b, *args, c, d=1, **kwargs):
+    return a + c
+
+def g(_ignored, used):
+    return (lambda x, y: x)(used, 1)
+
+class Plain:
+    def method(self, value):
+        return self
+
+    @classmethod
+    def build(cls, value):
+        return cls
+
+    @staticmethod
+    def helper(value):
+        return 1
+"#;
+    visit_code!(code, visitor);
+
+    let unused = unused_params(&visitor);
+    for name in ["test.f.b", "test.f.args", "test.f.d", "test.f.kwargs"] {
+        assert_eq!(unused.get(name), Some(&100), "{}", name);
+    }
+    assert!(!unused.contains_key("test.f.a"));
+    assert!(!unused.contains_key("test.f.c"));
+    // `_`-prefixed parameters and lambda parameters are never reported.
+    assert!(!unused.contains_key("test.g._ignored"));
+    assert!(!unused.keys().any(|k| k.ends_with(".y")));
+    // The receiver is not a parameter, but a static method has none.
+    assert!(!unused.contains_key("test.Plain.method.self"));
+    assert!(!unused.contains_key("test.Plain.build.cls"));
+    assert!(unused.contains_key("test.Plain.method.value"));
+    assert!(unused.contains_key("test.Plain.build.value"));
+    assert!(unused.contains_key("test.Plain.helper.value"));
+}
+
+#[test]
+fn test_unused_parameter_confidence() {
+    let code = r#"
+from abc import ABC, abstractmethod
+from typing import overload
+
+class Base(ABC):
+    @abstractmethod
+    def area(self, scale):
+        pass
+
+    def stub(self, value):
+        raise NotImplementedError()
+
+    def __exit__(self, exc_type, exc, tb):
+        return False
+
+class Child(Base):
+    def area(self, scale):
+        return 1
+
+@overload
+def parse(value: int) -> int: ...
+
+@receiver(post_save)
+def on_saved(sender, instance, **kwargs):
+    return instance
+
+def handler(signum, frame):
+    return 0
+
+def plain(value):
+    return 0
+"#;
+    visit_code!(code, visitor);
+
+    let unused = unused_params(&visitor);
+    assert_eq!(unused.get("test.Base.area.scale"), Some(&10));
+    assert_eq!(unused.get("test.Base.stub.value"), Some(&10));
+    assert_eq!(unused.get("test.parse.value"), Some(&10));
+    assert_eq!(unused.get("test.Base.__exit__.tb"), Some(&0));
+    // Overrides are only known to the analyzer, once every class is collected.
+    assert_eq!(unused.get("test.Child.area.scale"), Some(&100));
+    assert_eq!(unused.get("test.on_saved.sender"), Some(&30));
+    assert_eq!(unused.get("test.handler.frame"), Some(&30));
+    assert_eq!(unused.get("test.plain.value"), Some(&100));
+}
+
+/// Returns the referenced names, split into runtime and typing-only references.
+fn typed_references(visitor: &SkylosVisitor) -> (HashSet<String>, HashSet<String>) {
+    let mut runtime = HashSet::new();
+    let mut typing = HashSet::new();
+    for (name, reference) in visitor.reference_names().zip(&visitor.references) {
+        if reference.typing_only {
+            typing.insert(name.to_string());
+        } else {
+            runtime.insert(name.to_string());
+        }
+    }
+    (runtime, typing)
+}
+
+#[test]
+fn test_string_annotations() {
+    let code = r#"
+from typing import TYPE_CHECKING, Literal, Optional, cast
+
+if TYPE_CHECKING:
+    from models import User
+    from services import Service
+
+def load(user: "User", mode: Literal["fast", "slow"]) -> "Optional[models.Account]":
+    service: "Service | None" = None
+    return cast("Profile", user)
+
+class Node:
+    parent: "Node"
+"#;
+    visit_code!(code, visitor);
+    let (runtime, typing) = typed_references(&visitor);
+
+    // Forward references are parsed, including dotted names and unions.
+    for name in [
+        "User",
+        "Service",
+        "models",
+        "models.Account",
+        "Profile",
+        "Node",
+    ] {
+        assert!(typing.contains(name), "{}", name);
+        assert!(!runtime.contains(name), "{}", name);
+    }
+    // Evaluated annotations are runtime references; `Literal` strings are values.
+    assert!(typing.contains("Optional"));
+    assert!(runtime.contains("Literal"));
+    assert!(!typing.contains("fast"));
+    assert!(runtime.contains("TYPE_CHECKING"));
+
+    let type_checking: Vec<&str> = visitor
+        .type_checking_imports
+        .iter()
+        .map(|&i| visitor.definitions[i].simple_name.as_str())
+        .collect();
+    assert_eq!(type_checking, vec!["User", "Service"]);
+}
+
+#[test]
+fn test_future_annotations() {
+    let code = r#"
+from __future__ import annotations
+
+def area(shape: Shape) -> Decimal:
+    local: Cache = None
+    return shape
+"#;
+    visit_code!(code, visitor);
+    let (runtime, typing) = typed_references(&visitor);
+
+    for name in ["Shape", "Decimal", "Cache"] {
+        assert!(typing.contains(name), "{}", name);
+        assert!(!runtime.contains(name), "{}", name);
+    }
+}
+
+/// Returns the attribute definitions of the visited code, with their confidence.
+fn attributes(visitor: &SkylosVisitor) -> std::collections::HashMap<String, u8> {
+    visitor
+        .definitions
+        .iter()
+        .filter(|d| d.def_type == "attribute")
+        .map(|d| (d.full_name.clone(), d.confidence))
+        .collect()
+}
+
+#[test]
+fn test_class_attributes() {
+    let code = r#"
+class Plain:
+    __slots__ = ("slot", "other")
+    limit = 10
+    label: str
+
+    def __init__(self):
+        self.name = "x"
+        self.first, (self.second, _) = pair()
+        self.count: int = 0
+        self.limit = 5
+
+    @classmethod
+    def configure(cls):
+        cls.shared = True
+
+    def method(self):
+        self.method = None
+        self.count += 1
+        return helper(self).value
+"#;
+    visit_code!(code, visitor);
+
+    let attrs = attributes(&visitor);
+    let expected: std::collections::HashMap<String, u8> = [
+        ("test.Plain.limit", 100),
+        ("test.Plain.slot", 100),
+        ("test.Plain.other", 100),
+        ("test.Plain.label", 100),
+        ("test.Plain.name", 100),
+        ("test.Plain.first", 100),
+        ("test.Plain.second", 100),
+        ("test.Plain.count", 100),
+        ("test.Plain.shared", 100),
+    ]
+    .into_iter()
+    .map(|(name, confidence)| (name.to_string(), confidence))
+    .collect();
+    assert_eq!(attrs, expected);
+
+    // Slot names are declarations, not references.
+    let refs: HashSet<&str> = visitor.reference_names().collect();
+    assert!(!refs.contains("slot"));
+    assert!(refs.contains("value"));
+}
+
+#[test]
+fn test_class_field_confidence() {
+    let code = r#"
+from dataclasses import dataclass
+from enum import Enum
+import attr
+
+@dataclass(frozen=True)
+class Point:
+    x: int
+    y: int = 0
+
+    def __post_init__(self):
+        self.norm = 1
+
+@attr.s
+class Legacy:
+    z = attr.ib()
+
+class Model(pydantic.BaseModel):
+    name: str
+
+class Pair(NamedTuple):
+    left: int
+
+class Color(Enum):
+    RED = 1
+
+class View(BaseView):
+    template_name = "index.html"
+
+    def get(self):
+        self.cache = {}
+"#;
+    visit_code!(code, visitor);
+
+    let attrs = attributes(&visitor);
+    assert_eq!(attrs["test.Point.x"], 0);
+    assert_eq!(attrs["test.Point.y"], 0);
+    assert_eq!(attrs["test.Point.norm"], 100);
+    assert_eq!(attrs["test.Legacy.z"], 0);
+    assert_eq!(attrs["test.Model.name"], 0);
+    assert_eq!(attrs["test.Pair.left"], 0);
+    assert_eq!(attrs["test.Color.RED"], 40);
+    assert_eq!(attrs["test.View.template_name"], 50);
+    assert_eq!(attrs["test.View.cache"], 100);
+}
+
+/// Returns `(name, line, original_line)` for every redefinition in the visited code.
+fn redefinitions(visitor: &SkylosVisitor) -> Vec<(String, usize, usize)> {
+    visitor
+        .redefinitions
+        .iter()
+        .map(|r| (r.name.clone(), r.line, r.original_line))
+        .collect()
+}
+
+#[test]
+fn test_redefinitions() {
+    let code = r#"
+import json
+import os
+import os.path
+
+def handler():
+    pass
+
+def handler():
+    pass
+
+json = load()
+
+class Config:
+    pass
+
+print(Config)
+
+class Config:
+    pass
+
+def used():
+    pass
+
+used()
+
+def used():
+    pass
+
+import sys
+del sys
+import sys
+"#;
+    visit_code!(code, visitor);
+
+    assert_eq!(
+        redefinitions(&visitor),
+        vec![("handler".to_string(), 9, 6), ("json".to_string(), 12, 2),]
+    );
+    assert_eq!(
+        visitor.redefinitions[0].message,
+        "Redefinition of unused function 'handler' from line 6"
+    );
+    assert_eq!(visitor.redefinitions[0].rule_id, "SKY-R001");
+}
+
+#[test]
+fn test_intentional_redefinitions() {
+    let code = r#"
+from typing import overload
+
+try:
+    import simplejson as json
+except ImportError:
+    import json
+
+try:
+    from functools import cache
+except ImportError:
+    def cache(func):
+        return func
+
+if PY2:
+    def compat():
+        pass
+else:
+    def compat():
+        pass
+
+def fallback():
+    pass
+
+if FAST:
+    def fallback():
+        pass
+
+@overload
+def parse(x: int) -> int: ...
+@overload
+def parse(x: str) -> str: ...
+def parse(x):
+    return x
+
+@app.route("/")
+def index():
+    pass
+
+@app.route("/home")
+def index():
+    pass
+
+class Temperature:
+    @property
+    def value(self):
+        return self._value
+
+    @value.setter
+    def value(self, new):
+        self._value = new
+
+    def method(self):
+        pass
+
+    def method(self):
+        pass
+"#;
+    visit_code!(code, visitor);
+
+    // Only the method defined twice in the class body is a real redefinition.
+    assert_eq!(
+        redefinitions(&visitor),
+        vec![("method".to_string(), 56, 53)]
+    );
+}
+
+#[test]
+fn test_dotted_strings() {
+    let code = r#"
+INSTALLED_APPS = ["app.apps.AppConfig", "django.contrib.admin"]
+uvicorn.run("main:app")
+message = "Done. Exiting."
+version = "1.2.3"
+"#;
+    visit_code!(code, visitor);
+
+    assert_eq!(
+        visitor.dotted_strings,
+        vec!["app.apps.AppConfig", "django.contrib.admin", "main.app"]
+    );
+}
+
+#[test]
+fn test_import_contexts() {
+    let code = r#"
+import os
+from typing import TYPE_CHECKING
+
+try:
+    import ujson as json
+except ImportError:
+    import json
+
+if TYPE_CHECKING:
+    from app.models import User
+
+class Config:
+    import logging
+
+def load():
+    from app import services
+    return services
+"#;
+    visit_code!(code, visitor);
+
+    use skylos_rs::visitor::ImportContext::{Function, Module, TypeChecking};
+    let imports: Vec<(&str, usize, ImportContext)> = visitor
+        .imported_modules
+        .iter()
+        .map(String::as_str)
+        .zip(visitor.import_lines.iter().copied())
+        .zip(visitor.import_contexts.iter().copied())
+        .map(|((name, line), context)| (name, line, context))
+        .collect();
+    assert_eq!(
+        imports,
+        vec![
+            ("os", 2, Module),
+            ("typing.TYPE_CHECKING", 3, Module),
+            ("ujson", 6, Module),
+            ("json", 8, Module),
+            ("app.models.User", 11, TypeChecking),
+            ("logging", 14, Module),
+            ("app.services", 17, Function),
+        ]
+    );
+}
+
+#[test]
+fn test_reference_scopes() {
+    let code = r#"
+import os
+
+def load(path):
+    return os.path.join(path)
+"#;
+    visit_code!(code, visitor);
+
+    let load = visitor
+        .definitions
+        .iter()
+        .position(|d| d.simple_name == "load")
+        .unwrap() as u32;
+    let scopes: Vec<(&str, Option<u32>)> = visitor
+        .reference_names()
+        .zip(&visitor.references)
+        .map(|(name, reference)| (name, reference.scope))
+        .collect();
+    assert!(scopes.contains(&("os", Some(load))));
+    assert!(scopes.contains(&("os.path", Some(load))));
+    assert!(scopes.contains(&("path", Some(load))));
+    assert!(scopes.iter().all(|(_, scope)| *scope == Some(load)));
+
+    // Each distinct name is stored once, however often it is referenced.
+    let names: HashSet<&str> = visitor.reference_names().collect();
+    assert_eq!(visitor.symbols.len(), names.len());
+}
@@ -0,0 +1,53 @@
+// Unit tests for whitelist files
+// Tests loading both whitelist formats
+
+use skylos_rs::whitelist::load_whitelist;
+use std::fs;
+use tempfile::tempdir;
+
+#[test]
+fn test_python_whitelist() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("whitelist.py");
+    fs::write(
+        &path,
+        r#"
+# Used by the framework.
+_.on_startup
+MyModel.Meta
+unused_helper
+"#,
+    )
+    .unwrap();
+
+    let names = load_whitelist(&path).unwrap();
+    for expected in ["on_startup", "MyModel", "Meta", "unused_helper"] {
+        assert!(
+            names.iter().any(|n| n == expected),
+            "{} should be whitelisted",
+            expected
+        );
+    }
+}
+
+#[test]
+fn test_text_whitelist() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("whitelist.txt");
+    fs::write(
+        &path,
+        "# Skylos whitelist\n\npkg.mod.helper  # unused function (pkg/mod.py:3)\n  on_startup\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        load_whitelist(&path).unwrap(),
+        vec!["pkg.mod.helper", "on_startup"]
+    );
+}
+
+#[test]
+fn test_missing_whitelist() {
+    let dir = tempdir().unwrap();
+    assert!(load_whitelist(&dir.path().join("missing.txt")).is_err());
+}